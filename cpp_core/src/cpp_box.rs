@@ -1,6 +1,7 @@
 use crate::ops::{Begin, BeginMut, End, EndMut, Increment, Indirection};
 use crate::vector_ops::{Data, DataMut, Size};
 use crate::{cpp_iter, CppIterator, DynamicCast, Ptr, Ref, StaticDowncast, StaticUpcast};
+use std::hash::{Hash, Hasher};
 use std::ops::Deref;
 use std::{fmt, mem, ptr, slice};
 
@@ -18,6 +19,50 @@ pub trait CppDeletable: Sized {
     unsafe fn delete(&self);
 }
 
+/// Objects that can be copied using C++'s copy constructor.
+///
+/// This trait is automatically implemented by `ritual` for class types
+/// that have an accessible copy constructor. It allows `CppBox<T>` to implement `Clone`.
+pub trait CppClone: CppDeletable {
+    /// Creates a copy of `self` on the heap using C++'s copy constructor.
+    ///
+    /// # Safety
+    ///
+    /// The caller must make sure `self` contains a valid pointer. This function
+    /// may invoke arbitrary foreign code, so no safety guarantees can be made.
+    unsafe fn cpp_clone(&self) -> CppBox<Self>;
+}
+
+/// Objects that can be created using C++'s default constructor.
+///
+/// This trait is automatically implemented by `ritual` for class types
+/// that have an accessible default constructor. It allows `CppBox<T>` to implement `Default`.
+pub trait CppDefault: CppDeletable {
+    /// Creates a new object on the heap using C++'s default constructor.
+    ///
+    /// # Safety
+    ///
+    /// This function
+    /// may invoke arbitrary foreign code, so no safety guarantees can be made.
+    unsafe fn cpp_default() -> CppBox<Self>;
+}
+
+/// Objects that can be hashed using a C++ hash function (like `qHash`).
+///
+/// This trait is automatically implemented by `ritual` for class types
+/// that have a hash function. It allows `CppBox<T>` to implement `Hash`.
+/// If `T` also implements `PartialEq<CppBox<T>>`, `CppBox<T>` implements `Eq`,
+/// so it can be used as a key in `HashMap` and `HashSet`.
+pub trait CppHash {
+    /// Returns the hash of `self` calculated by the C++ hash function.
+    ///
+    /// # Safety
+    ///
+    /// The caller must make sure `self` contains a valid pointer. This function
+    /// may invoke arbitrary foreign code, so no safety guarantees can be made.
+    unsafe fn cpp_hash(&self) -> u64;
+}
+
 /// An owning pointer to a C++ object.
 ///
 /// `CppBox` is automatically used in places where C++ class objects are passed by value
@@ -42,7 +87,8 @@ pub trait CppDeletable: Sized {
 ///
 /// `CppBox` implements `Deref`, allowing to call the object's methods
 /// directly. In addition, methods of the object's first base class are also directly available
-/// thanks to nested `Deref` implementations.
+/// thanks to nested `Deref` implementations. Methods of other base classes are added
+/// to the class itself.
 ///
 /// If the object provides an iterator interface through `begin()` and `end()` functions,
 /// `&CppBox<T>` and `&mut CppBox<T>` will implement `IntoIterator`,
@@ -317,15 +363,74 @@ impl<T: CppDeletable> Drop for CppBox<T> {
     }
 }
 
+/// The box can be moved to another thread if the object can be moved to another thread.
+/// Generated types implement `Send` only if it's declared in the generator's config.
+///
+/// A box of a generated class type without declared thread safety can't be moved
+/// to another thread:
+///
+/// ```compile_fail
+/// use cpp_core::{CppBox, CppDeletable};
+/// use std::marker::PhantomData;
+///
+/// // layout of a generated movable class type
+/// #[repr(transparent)]
+/// struct QString([u8; 8], PhantomData<*mut ()>);
+///
+/// impl CppDeletable for QString {
+///     unsafe fn delete(&self) {}
+/// }
+///
+/// fn assert_send<T: Send>() {}
+/// assert_send::<CppBox<QString>>();
+/// ```
+unsafe impl<T: CppDeletable + Send> Send for CppBox<T> {}
+
+/// The box can be shared between threads if the object can be shared between threads.
+/// Generated types implement `Sync` only if it's declared in the generator's config.
+unsafe impl<T: CppDeletable + Sync> Sync for CppBox<T> {}
+
+/// Copies the stored object using C++'s copy constructor.
+impl<T: CppClone> Clone for CppBox<T> {
+    fn clone(&self) -> Self {
+        unsafe { T::cpp_clone(self) }
+    }
+}
+
+/// Creates an object using C++'s default constructor.
+impl<T: CppDefault> Default for CppBox<T> {
+    fn default() -> Self {
+        unsafe { T::cpp_default() }
+    }
+}
+
+/// Hashes the stored object using the C++ hash function.
+impl<T: CppDeletable + CppHash> Hash for CppBox<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        unsafe { T::cpp_hash(self) }.hash(state);
+    }
+}
+
+/// C++ types having a hash function are expected to have an `operator==`
+/// that is an equivalence relation.
+impl<T: CppDeletable + CppHash + PartialEq<CppBox<T>>> Eq for CppBox<T> {}
+
 impl<T: CppDeletable> fmt::Debug for CppBox<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "CppBox({:?})", self.0)
     }
 }
 
+/// Formats the object using its C++ output stream operator (`operator<<`).
+impl<T: CppDeletable + fmt::Display> fmt::Display for CppBox<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{CppBox, CppDeletable, Ptr};
+    use crate::{CppBox, CppClone, CppDeletable, Ptr};
     use std::cell::RefCell;
     use std::rc::Rc;
 
@@ -355,4 +460,38 @@ mod tests {
         }
         assert!(*value1.borrow() == 42);
     }
+
+    struct Struct2 {
+        value: i32,
+    }
+
+    impl CppDeletable for Struct2 {
+        unsafe fn delete(&self) {
+            drop(Box::from_raw(self as *const Struct2 as *mut Struct2));
+        }
+    }
+
+    impl CppClone for Struct2 {
+        unsafe fn cpp_clone(&self) -> CppBox<Self> {
+            CppBox::from_raw(Box::into_raw(Box::new(Struct2 { value: self.value }))).unwrap()
+        }
+    }
+
+    #[test]
+    fn test_clone() {
+        unsafe {
+            let object1 = CppBox::from_raw(Box::into_raw(Box::new(Struct2 { value: 1 }))).unwrap();
+            let object2 = object1.clone();
+            assert_eq!(object2.value, 1);
+            assert_ne!(object1.as_raw_ptr(), object2.as_raw_ptr());
+        }
+    }
+
+    #[test]
+    fn test_send() {
+        let object1 =
+            unsafe { CppBox::from_raw(Box::into_raw(Box::new(Struct2 { value: 3 }))).unwrap() };
+        let value = std::thread::spawn(move || object1.value).join().unwrap();
+        assert_eq!(value, 3);
+    }
 }
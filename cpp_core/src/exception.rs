@@ -0,0 +1,50 @@
+use crate::take_malloc_string;
+use std::error::Error;
+use std::fmt;
+use std::os::raw::c_char;
+
+/// An error representing a C++ exception caught by the FFI wrapper of a function.
+///
+/// Wrappers of functions that are configured to catch C++ exceptions return
+/// `Result<T, CppException>` instead of `T`. The error contains the message returned by
+/// `std::exception::what()`, or a generic message if the thrown object
+/// is not derived from `std::exception`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CppException {
+    message: String,
+}
+
+impl CppException {
+    /// Creates a `CppException` with the specified message.
+    pub fn new(message: impl Into<String>) -> Self {
+        CppException {
+            message: message.into(),
+        }
+    }
+
+    /// Creates a `CppException` from the message reported by an FFI wrapper
+    /// and frees the message buffer.
+    ///
+    /// ### Safety
+    ///
+    /// `message` must be a non-null pointer to a nul-terminated string allocated with `malloc`.
+    /// It must not be used after calling this function.
+    pub unsafe fn from_raw_message(message: *mut c_char) -> Self {
+        CppException {
+            message: take_malloc_string(message),
+        }
+    }
+
+    /// Returns the exception's message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for CppException {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "C++ exception: {}", self.message)
+    }
+}
+
+impl Error for CppException {}
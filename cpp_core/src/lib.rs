@@ -22,14 +22,35 @@
 //!
 //! Pointer types implement operator traits and delegate them to the corresponding C++ operators.
 //! This means that you can use `ptr1 + ptr2` to access the object's `operator+`.
+//! Similarly, `ptr[index]` accesses the element returned by the object's `operator[]`.
 //!
 //! Pointer types implement `Deref`, allowing to call the object's methods
 //! directly. In addition, methods of the object's first base class are also directly available
-//! thanks to nested `Deref` implementations.
+//! thanks to nested `Deref` implementations. If the class has other base classes,
+//! their methods are added to the class and call the base class's method
+//! on the object converted with `StaticUpcast`.
 //!
 //! If the object provides an iterator interface through `begin()` and `end()` functions,
 //! pointer types will implement `IntoIterator`, so you can iterate on them directly.
 //!
+//! `CppBox<T>` implements `Clone`, `Default` and `Hash` if the C++ class has
+//! a copy constructor, a default constructor and a hash function (like `qHash`),
+//! respectively (see `CppClone`, `CppDefault` and `CppHash` traits). Comparing boxes
+//! with `==` uses the class's `operator==`.
+//!
+//! C++ classes are not thread-safe in general, so generated types implement neither
+//! `Send` nor `Sync` unless their thread safety is declared in the generator's config.
+//! `CppBox<T>` implements `Send` and `Sync` if `T` does.
+//!
+//! `std::shared_ptr<T>` and `std::unique_ptr<T>` are represented by `CppSharedPtr<T>` and
+//! `CppUniquePtr<T>`. They dereference to the object, and `CppBox<CppSharedPtr<T>>`
+//! implements `Clone` by copying the shared pointer (see `CppSharedPtrTarget` and
+//! `CppUniquePtrTarget` traits).
+//!
+//! If the library declares an output stream operator (`operator<<(std::ostream&, const T&)`),
+//! the class type implements `Display` and `Debug`, and pointer types implement `Display`
+//! by forwarding to the object.
+//!
 //! # Casts
 //!
 //! The following traits provide access to casting between C++ class types:
@@ -50,26 +71,50 @@
 //! or even `Ptr<DerivedClass>` (where `DerivedClass` inherits `SomeClass`). You can also
 //! pass a null pointer object (`NullPtr`) if you don't have a value
 //! (`Ptr::null()` is also an option but it can cause type inference issues).
+//!
+//! C++ conversion operators (e.g. `operator bool()`) are available through `CastInto`
+//! on a reference to the object: `let value: bool = (&*object).cast_into();`.
+//! If the target of the conversion is a class type, the result is an owned `CppBox`.
+//! A temporary object can't be referenced by `Ref`, so the box must be stored before
+//! it's passed as `&converted` to a method accepting `impl CastInto<Ref<T>>`.
+//!
+//! # Exceptions
+//!
+//! If the crate is configured to catch C++ exceptions, wrappers of affected functions
+//! return `Result<T, CppException>`. `CppException` contains the message of the caught
+//! exception.
+//!
+//! Functions that report failures through their return value (e.g. a `bool` or an error code)
+//! may be configured to return `Result<T, StatusError<S>>`, where `S` is the type
+//! of the returned status.
 
 #![deny(missing_docs)]
 
 pub use crate::casts::{DynamicCast, StaticDowncast, StaticUpcast};
 pub use crate::convert::{CastFrom, CastInto};
-pub use crate::cpp_box::{CppBox, CppDeletable};
+pub use crate::cpp_box::{CppBox, CppClone, CppDefault, CppDeletable, CppHash};
+pub use crate::exception::CppException;
 pub use crate::iterator::{cpp_iter, CppIterator, EndPtr};
 pub use crate::ptr::{NullPtr, Ptr};
 pub use crate::ref_::Ref;
+pub use crate::smart_ptr::{CppSharedPtr, CppSharedPtrTarget, CppUniquePtr, CppUniquePtrTarget};
+pub use crate::status::StatusError;
+pub use crate::string::take_malloc_string;
 pub use libc::wchar_t;
 
 mod casts;
 pub mod cmp;
 mod convert;
 mod cpp_box;
+mod exception;
 mod iterator;
 pub mod ops;
 mod ops_impls;
 mod ptr;
 mod ref_;
+mod smart_ptr;
+mod status;
+mod string;
 pub mod vector_ops;
 
 // C++ doesn't guarantee these types to be exactly u16 and u32,
@@ -101,3 +101,31 @@ pub trait EndMut {
     /// may invoke arbitrary foreign code, so no safety guarantees can be made.
     unsafe fn end_mut(&self) -> Self::Output;
 }
+
+/// Represents C++'s `operator[]() const`.
+pub trait Subscript<T> {
+    /// Type of the elements.
+    type Output;
+
+    /// Returns a pointer to the element at `index`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must make sure `self` contains a valid pointer. This function
+    /// may invoke arbitrary foreign code, so no safety guarantees can be made.
+    unsafe fn subscript(&self, index: T) -> *const Self::Output;
+}
+
+/// Represents C++'s `operator[]()`.
+pub trait SubscriptMut<T> {
+    /// Type of the elements.
+    type Output;
+
+    /// Returns a mutable pointer to the element at `index`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must make sure `self` contains a valid pointer. This function
+    /// may invoke arbitrary foreign code, so no safety guarantees can be made.
+    unsafe fn subscript_mut(&self, index: T) -> *mut Self::Output;
+}
@@ -1,9 +1,10 @@
 use crate::{
     cmp::{Ge, Gt, Le, Lt},
+    ops::{Subscript, SubscriptMut},
     CppBox, CppDeletable, Ptr, Ref,
 };
 use std::cmp::{Ordering, PartialEq, PartialOrd};
-use std::ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Rem, Shl, Shr, Sub};
+use std::ops::{Add, BitAnd, BitOr, BitXor, Div, Index, IndexMut, Mul, Rem, Shl, Shr, Sub};
 
 macro_rules! define_op {
     ($trait1:ident, $func:ident) => {
@@ -147,3 +148,85 @@ where
         unsafe { (**self).ge(other) }
     }
 }
+
+macro_rules! define_index_op {
+    ($container:ident $(, $bound:ident)?) => {
+        impl<T $(: $bound)?, I> Index<I> for $container<T>
+        where
+            T: Subscript<I>,
+        {
+            type Output = <T as Subscript<I>>::Output;
+
+            fn index(&self, index: I) -> &Self::Output {
+                unsafe { &*(**self).subscript(index) }
+            }
+        }
+
+        impl<T $(: $bound)?, I> IndexMut<I> for $container<T>
+        where
+            T: Subscript<I> + SubscriptMut<I, Output = <T as Subscript<I>>::Output>,
+        {
+            fn index_mut(&mut self, index: I) -> &mut Self::Output {
+                unsafe { &mut *(**self).subscript_mut(index) }
+            }
+        }
+    };
+}
+
+define_index_op!(Ptr);
+define_index_op!(Ref);
+define_index_op!(CppBox, CppDeletable);
+
+#[cfg(test)]
+mod tests {
+    use crate::ops::{Subscript, SubscriptMut};
+    use crate::{CppBox, CppDeletable};
+    use std::cell::UnsafeCell;
+
+    struct Array {
+        values: UnsafeCell<[i32; 3]>,
+    }
+
+    impl Subscript<usize> for Array {
+        type Output = i32;
+
+        unsafe fn subscript(&self, index: usize) -> *const i32 {
+            (*self.values.get()).as_ptr().add(index)
+        }
+    }
+
+    impl SubscriptMut<usize> for Array {
+        type Output = i32;
+
+        unsafe fn subscript_mut(&self, index: usize) -> *mut i32 {
+            (*self.values.get()).as_mut_ptr().add(index)
+        }
+    }
+
+    impl CppDeletable for Array {
+        unsafe fn delete(&self) {
+            drop(Box::from_raw(self as *const Array as *mut Array));
+        }
+    }
+
+    #[test]
+    fn test_index() {
+        unsafe {
+            let mut array = CppBox::from_raw(Box::into_raw(Box::new(Array {
+                values: UnsafeCell::new([1, 2, 3]),
+            })))
+            .unwrap();
+            assert_eq!(array[1], 2);
+            array[1] = 5;
+            assert_eq!(array[1], 5);
+
+            let mut ptr = array.as_ptr();
+            assert_eq!(ptr[2], 3);
+            ptr[2] = 6;
+            assert_eq!(array[2], 6);
+
+            let reference = array.as_ref();
+            assert_eq!(reference[0], 1);
+        }
+    }
+}
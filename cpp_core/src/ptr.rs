@@ -21,7 +21,8 @@ use std::{fmt, slice};
 ///
 /// `Ptr` implements `Deref`, allowing to call the object's methods
 /// directly. In addition, methods of the object's first base class are also directly available
-/// thanks to nested `Deref` implementations.
+/// thanks to nested `Deref` implementations. Methods of other base classes are added
+/// to the class itself.
 ///
 /// `Ptr` can contain a null pointer. `Deref` will panic if attempted to dereference
 /// a null pointer.
@@ -56,6 +57,18 @@ impl<T> fmt::Debug for Ptr<T> {
     }
 }
 
+/// Formats the object using its C++ output stream operator (`operator<<`).
+/// A null pointer is formatted as `(null)`.
+impl<T: fmt::Display> fmt::Display for Ptr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_null() {
+            f.write_str("(null)")
+        } else {
+            fmt::Display::fmt(&**self, f)
+        }
+    }
+}
+
 impl<T> Ptr<T> {
     /// Creates a `Ptr` from a raw pointer.
     ///
@@ -20,7 +20,8 @@ use std::{fmt, ptr, slice};
 ///
 /// `Ref` implements `Deref` allowing to call the object's methods
 /// directly. In addition, methods of the object's first base class are also directly available
-/// thanks to nested `Deref` implementations.
+/// thanks to nested `Deref` implementations. Methods of other base classes are added
+/// to the class itself.
 ///
 /// If the object provides an iterator interface through `begin()` and `end()` functions,
 /// `Ref` will implement `IntoIterator`, so you can iterate on it directly.
@@ -50,6 +51,13 @@ impl<T> fmt::Debug for Ref<T> {
     }
 }
 
+/// Formats the object using its C++ output stream operator (`operator<<`).
+impl<T: fmt::Display> fmt::Display for Ref<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&**self, f)
+    }
+}
+
 impl<T> Ref<T> {
     /// Creates a `Ref` from a `Ptr`. Returns `None` if `ptr` is null.
     ///
@@ -0,0 +1,537 @@
+//! Wrappers of C++ standard library smart pointers.
+
+use crate::{CppBox, CppClone, CppDeletable, Ptr, StaticUpcast};
+use std::ffi::c_void;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::os::raw::c_long;
+
+/// A C++ `std::shared_ptr<T>` object.
+///
+/// `ritual` maps `std::shared_ptr<T>` instantiations to this type if `T` is a class type
+/// declared in the same crate. Like other C++ objects, `CppSharedPtr` is only
+/// accessible through pointers. A `std::shared_ptr<T>` returned by value from a C++ function
+/// becomes `CppBox<CppSharedPtr<T>>`.
+///
+/// `CppBox<CppSharedPtr<T>>` implements `Clone` that creates a new `std::shared_ptr`
+/// sharing the ownership of the object. Dropping the box destroys the `std::shared_ptr`,
+/// deleting the object if it was the last owner.
+///
+/// `CppSharedPtr<T>` implements `Deref<Target = T>`, so the object's methods
+/// can be called directly on the pointer.
+#[repr(C)]
+pub struct CppSharedPtr<T> {
+    _unused: u8,
+    _marker: PhantomData<T>,
+}
+
+/// Class types that can be managed by `CppSharedPtr`.
+///
+/// This trait is automatically implemented by `ritual` for class types
+/// used in `std::shared_ptr` instantiations. All functions are implemented using
+/// FFI wrappers of the corresponding `std::shared_ptr` operations.
+pub trait CppSharedPtrTarget: Sized {
+    /// Calls C++'s `delete` on a heap-allocated `std::shared_ptr` object.
+    ///
+    /// # Safety
+    ///
+    /// The caller must make sure `ptr` is a valid pointer to a `std::shared_ptr`
+    /// object allocated with `new`. This function
+    /// may invoke arbitrary foreign code, so no safety guarantees can be made.
+    unsafe fn shared_ptr_delete(ptr: *mut CppSharedPtr<Self>);
+
+    /// Creates a heap-allocated copy of `ptr` using the copy constructor.
+    ///
+    /// # Safety
+    ///
+    /// The caller must make sure `ptr` is a valid pointer.
+    unsafe fn shared_ptr_copy(ptr: *const CppSharedPtr<Self>) -> *mut CppSharedPtr<Self>;
+
+    /// Returns the stored pointer.
+    ///
+    /// # Safety
+    ///
+    /// The caller must make sure `ptr` is a valid pointer.
+    unsafe fn shared_ptr_get(ptr: *const CppSharedPtr<Self>) -> *mut Self;
+
+    /// Returns the number of `std::shared_ptr` objects managing the object.
+    ///
+    /// # Safety
+    ///
+    /// The caller must make sure `ptr` is a valid pointer.
+    unsafe fn shared_ptr_use_count(ptr: *const CppSharedPtr<Self>) -> c_long;
+
+    /// Creates a heap-allocated `std::shared_ptr` that takes ownership of `raw`.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must be null or point to an object allocated with `new`
+    /// that is not owned by anything else.
+    unsafe fn shared_ptr_from_raw(raw: *mut Self) -> *mut CppSharedPtr<Self>;
+
+    /// Creates a heap-allocated `std::shared_ptr<void>` sharing the ownership with `ptr`.
+    ///
+    /// # Safety
+    ///
+    /// The caller must make sure `ptr` is a valid pointer. The returned object must be
+    /// passed to `CppSharedPtrTarget::shared_ptr_alias` of some type.
+    unsafe fn shared_ptr_to_owner(ptr: *const CppSharedPtr<Self>) -> *mut c_void;
+
+    /// Creates a heap-allocated `std::shared_ptr` that shares the ownership with `owner`
+    /// but stores `raw`. `owner` is deleted by this function.
+    ///
+    /// # Safety
+    ///
+    /// `owner` must be a value returned by `CppSharedPtrTarget::shared_ptr_to_owner`.
+    /// `raw` must be valid at least as long as the object managed by `owner`.
+    unsafe fn shared_ptr_alias(owner: *mut c_void, raw: *mut Self) -> *mut CppSharedPtr<Self>;
+}
+
+impl<T: CppSharedPtrTarget> CppSharedPtr<T> {
+    /// Moves the object into a new `std::shared_ptr`.
+    ///
+    /// ### Safety
+    ///
+    /// The object must not be owned by anything other than `value`.
+    /// This function may invoke arbitrary foreign code, so no safety guarantees can be made.
+    pub unsafe fn from_box(value: CppBox<T>) -> CppBox<Self>
+    where
+        T: CppDeletable,
+    {
+        CppBox::from_raw(T::shared_ptr_from_raw(value.into_raw_ptr()))
+            .expect("std::shared_ptr allocation returned null")
+    }
+
+    /// Returns the stored pointer.
+    ///
+    /// ### Safety
+    ///
+    /// This operation is safe as long as `self` is valid.
+    pub unsafe fn get(&self) -> Ptr<T> {
+        Ptr::from_raw(T::shared_ptr_get(self))
+    }
+
+    /// Returns true if the stored pointer is null.
+    ///
+    /// ### Safety
+    ///
+    /// This operation is safe as long as `self` is valid.
+    pub unsafe fn is_null(&self) -> bool {
+        self.get().is_null()
+    }
+
+    /// Returns the number of `std::shared_ptr` objects managing the object
+    /// (`std::shared_ptr::use_count`).
+    ///
+    /// ### Safety
+    ///
+    /// This operation is safe as long as `self` is valid.
+    // `c_long` is 32-bit on some platforms
+    #[allow(clippy::useless_conversion)]
+    pub unsafe fn use_count(&self) -> i64 {
+        i64::from(T::shared_ptr_use_count(self))
+    }
+
+    /// Returns a new `std::shared_ptr` pointing to the object converted to the base class
+    /// type `U` using `StaticUpcast`. The returned pointer shares the ownership with `self`.
+    ///
+    /// ### Safety
+    ///
+    /// This operation is safe as long as `self` is valid.
+    pub unsafe fn upcast<U>(&self) -> CppBox<CppSharedPtr<U>>
+    where
+        T: StaticUpcast<U>,
+        U: CppSharedPtrTarget,
+    {
+        let owner = T::shared_ptr_to_owner(self);
+        let raw = StaticUpcast::static_upcast(self.get()).as_mut_raw_ptr();
+        CppBox::from_raw(U::shared_ptr_alias(owner, raw))
+            .expect("std::shared_ptr allocation returned null")
+    }
+}
+
+impl<T: CppSharedPtrTarget> CppDeletable for CppSharedPtr<T> {
+    unsafe fn delete(&self) {
+        T::shared_ptr_delete(self as *const Self as *mut Self);
+    }
+}
+
+/// Creates a new `std::shared_ptr` sharing the ownership of the object.
+impl<T: CppSharedPtrTarget> CppClone for CppSharedPtr<T> {
+    unsafe fn cpp_clone(&self) -> CppBox<Self> {
+        CppBox::from_raw(T::shared_ptr_copy(self))
+            .expect("std::shared_ptr allocation returned null")
+    }
+}
+
+/// Allows to call member functions of `T` and its base classes directly on the pointer.
+///
+/// Panics if the stored pointer is null.
+impl<T: CppSharedPtrTarget> Deref for CppSharedPtr<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe {
+            T::shared_ptr_get(self)
+                .as_ref()
+                .expect("attempted to deref a null std::shared_ptr")
+        }
+    }
+}
+
+/// Copies of a `std::shared_ptr` may be used from different threads,
+/// so the object must be both `Send` and `Sync`.
+unsafe impl<T: Send + Sync> Send for CppSharedPtr<T> {}
+
+unsafe impl<T: Send + Sync> Sync for CppSharedPtr<T> {}
+
+impl<T> fmt::Debug for CppSharedPtr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CppSharedPtr({:?})", self as *const Self)
+    }
+}
+
+/// A C++ `std::unique_ptr<T>` object (with the default deleter).
+///
+/// `ritual` maps `std::unique_ptr<T>` instantiations to this type if `T` is a class type
+/// declared in the same crate. A `std::unique_ptr<T>` returned by value from a C++ function
+/// becomes `CppBox<CppUniquePtr<T>>`. Functions accepting a `std::unique_ptr<T>` by value
+/// consume a `CppBox<CppUniquePtr<T>>` because the object is moved out of it.
+///
+/// `CppUniquePtr<T>` implements `Deref<Target = T>`, so the object's methods
+/// can be called directly on the pointer.
+#[repr(C)]
+pub struct CppUniquePtr<T> {
+    _unused: u8,
+    _marker: PhantomData<T>,
+}
+
+/// Class types that can be managed by `CppUniquePtr`.
+///
+/// This trait is automatically implemented by `ritual` for class types
+/// used in `std::unique_ptr` instantiations. All functions are implemented using
+/// FFI wrappers of the corresponding `std::unique_ptr` operations.
+pub trait CppUniquePtrTarget: Sized {
+    /// Calls C++'s `delete` on a heap-allocated `std::unique_ptr` object.
+    ///
+    /// # Safety
+    ///
+    /// The caller must make sure `ptr` is a valid pointer to a `std::unique_ptr`
+    /// object allocated with `new`. This function
+    /// may invoke arbitrary foreign code, so no safety guarantees can be made.
+    unsafe fn unique_ptr_delete(ptr: *mut CppUniquePtr<Self>);
+
+    /// Returns the stored pointer.
+    ///
+    /// # Safety
+    ///
+    /// The caller must make sure `ptr` is a valid pointer.
+    unsafe fn unique_ptr_get(ptr: *const CppUniquePtr<Self>) -> *mut Self;
+
+    /// Creates a heap-allocated `std::unique_ptr` that takes ownership of `raw`.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must be null or point to an object allocated with `new`
+    /// that is not owned by anything else.
+    unsafe fn unique_ptr_from_raw(raw: *mut Self) -> *mut CppUniquePtr<Self>;
+
+    /// Releases the ownership of the stored pointer and returns it.
+    ///
+    /// # Safety
+    ///
+    /// The caller must make sure `ptr` is a valid pointer.
+    unsafe fn unique_ptr_release(ptr: *mut CppUniquePtr<Self>) -> *mut Self;
+}
+
+impl<T: CppUniquePtrTarget> CppUniquePtr<T> {
+    /// Moves the object into a new `std::unique_ptr`.
+    ///
+    /// ### Safety
+    ///
+    /// The object must not be owned by anything other than `value`.
+    /// This function may invoke arbitrary foreign code, so no safety guarantees can be made.
+    pub unsafe fn from_box(value: CppBox<T>) -> CppBox<Self>
+    where
+        T: CppDeletable,
+    {
+        CppBox::from_raw(T::unique_ptr_from_raw(value.into_raw_ptr()))
+            .expect("std::unique_ptr allocation returned null")
+    }
+
+    /// Returns the stored pointer.
+    ///
+    /// ### Safety
+    ///
+    /// This operation is safe as long as `self` is valid.
+    pub unsafe fn get(&self) -> Ptr<T> {
+        Ptr::from_raw(T::unique_ptr_get(self))
+    }
+
+    /// Returns true if the stored pointer is null.
+    ///
+    /// ### Safety
+    ///
+    /// This operation is safe as long as `self` is valid.
+    pub unsafe fn is_null(&self) -> bool {
+        self.get().is_null()
+    }
+
+    /// Releases the ownership of the object and returns it in a `CppBox`.
+    /// Returns `None` if the stored pointer is null.
+    ///
+    /// ### Safety
+    ///
+    /// This operation is safe as long as `self` is valid.
+    pub unsafe fn into_box(ptr: CppBox<Self>) -> Option<CppBox<T>>
+    where
+        T: CppDeletable,
+    {
+        CppBox::from_raw(T::unique_ptr_release(ptr.as_mut_raw_ptr()))
+    }
+
+    /// Moves the object to a new `std::unique_ptr` of the base class type `U`
+    /// using `StaticUpcast`.
+    ///
+    /// ### Safety
+    ///
+    /// This operation is safe as long as `ptr` is valid. The object will be deleted
+    /// through a pointer to `U`, so `U` must have a virtual destructor
+    /// unless `T` and `U` are the same type.
+    pub unsafe fn upcast<U>(ptr: CppBox<Self>) -> CppBox<CppUniquePtr<U>>
+    where
+        T: StaticUpcast<U>,
+        U: CppUniquePtrTarget,
+    {
+        let raw = Ptr::from_raw(T::unique_ptr_release(ptr.as_mut_raw_ptr()));
+        let raw = StaticUpcast::static_upcast(raw).as_mut_raw_ptr();
+        CppBox::from_raw(U::unique_ptr_from_raw(raw))
+            .expect("std::unique_ptr allocation returned null")
+    }
+}
+
+impl<T: CppUniquePtrTarget> CppDeletable for CppUniquePtr<T> {
+    unsafe fn delete(&self) {
+        T::unique_ptr_delete(self as *const Self as *mut Self);
+    }
+}
+
+/// Allows to call member functions of `T` and its base classes directly on the pointer.
+///
+/// Panics if the stored pointer is null.
+impl<T: CppUniquePtrTarget> Deref for CppUniquePtr<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe {
+            T::unique_ptr_get(self)
+                .as_ref()
+                .expect("attempted to deref a null std::unique_ptr")
+        }
+    }
+}
+
+impl<T> fmt::Debug for CppUniquePtr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CppUniquePtr({:?})", self as *const Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::any::Any;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    struct Base {
+        value: i32,
+        // emulates a virtual destructor
+        delete: unsafe fn(*mut Base),
+    }
+
+    #[repr(C)]
+    struct Derived {
+        base: Base,
+        dropped: Rc<Cell<bool>>,
+    }
+
+    impl Derived {
+        fn new(value: i32, dropped: Rc<Cell<bool>>) -> Box<Derived> {
+            unsafe fn delete(ptr: *mut Base) {
+                drop(Box::from_raw(ptr as *mut Derived));
+            }
+            Box::new(Derived {
+                base: Base { value, delete },
+                dropped,
+            })
+        }
+    }
+
+    impl Drop for Derived {
+        fn drop(&mut self) {
+            self.dropped.set(true);
+        }
+    }
+
+    impl CppDeletable for Derived {
+        unsafe fn delete(&self) {
+            drop(Box::from_raw(self as *const Derived as *mut Derived));
+        }
+    }
+
+    impl CppDeletable for Base {
+        unsafe fn delete(&self) {
+            (self.delete)(self as *const Base as *mut Base);
+        }
+    }
+
+    impl StaticUpcast<Base> for Derived {
+        unsafe fn static_upcast(ptr: Ptr<Self>) -> Ptr<Base> {
+            Ptr::from_raw(&(*ptr.as_raw_ptr()).base)
+        }
+    }
+
+    // `std::shared_ptr` is emulated with a pointer and an `Rc` owning the object.
+    struct Shared {
+        raw: *mut c_void,
+        owner: Rc<dyn Any>,
+    }
+
+    unsafe fn new_shared<T>(raw: *mut T, owner: Rc<dyn Any>) -> *mut CppSharedPtr<T> {
+        let raw = raw as *mut c_void;
+        Box::into_raw(Box::new(Shared { raw, owner })) as *mut CppSharedPtr<T>
+    }
+
+    unsafe fn shared<'a, T>(ptr: *const CppSharedPtr<T>) -> &'a Shared {
+        &*(ptr as *const Shared)
+    }
+
+    macro_rules! impl_shared_ptr_target {
+        ($t:ty) => {
+            impl CppSharedPtrTarget for $t {
+                unsafe fn shared_ptr_delete(ptr: *mut CppSharedPtr<Self>) {
+                    drop(Box::from_raw(ptr as *mut Shared));
+                }
+                unsafe fn shared_ptr_copy(
+                    ptr: *const CppSharedPtr<Self>,
+                ) -> *mut CppSharedPtr<Self> {
+                    let shared = shared(ptr);
+                    new_shared(shared.raw as *mut Self, shared.owner.clone())
+                }
+                unsafe fn shared_ptr_get(ptr: *const CppSharedPtr<Self>) -> *mut Self {
+                    shared(ptr).raw as *mut Self
+                }
+                unsafe fn shared_ptr_use_count(ptr: *const CppSharedPtr<Self>) -> c_long {
+                    Rc::strong_count(&shared(ptr).owner) as c_long
+                }
+                unsafe fn shared_ptr_from_raw(raw: *mut Self) -> *mut CppSharedPtr<Self> {
+                    new_shared(raw, Rc::new(Box::from_raw(raw)))
+                }
+                unsafe fn shared_ptr_to_owner(ptr: *const CppSharedPtr<Self>) -> *mut c_void {
+                    Box::into_raw(Box::new(shared(ptr).owner.clone())) as *mut c_void
+                }
+                unsafe fn shared_ptr_alias(
+                    owner: *mut c_void,
+                    raw: *mut Self,
+                ) -> *mut CppSharedPtr<Self> {
+                    new_shared(raw, *Box::from_raw(owner as *mut Rc<dyn Any>))
+                }
+            }
+        };
+    }
+
+    impl_shared_ptr_target!(Base);
+    impl_shared_ptr_target!(Derived);
+
+    // `std::unique_ptr` is emulated with a pointer that is deleted with `CppDeletable`.
+    struct Unique {
+        raw: *mut c_void,
+    }
+
+    macro_rules! impl_unique_ptr_target {
+        ($t:ty) => {
+            impl CppUniquePtrTarget for $t {
+                unsafe fn unique_ptr_delete(ptr: *mut CppUniquePtr<Self>) {
+                    let unique = Box::from_raw(ptr as *mut Unique);
+                    if let Some(object) = (unique.raw as *mut Self).as_ref() {
+                        object.delete();
+                    }
+                }
+                unsafe fn unique_ptr_get(ptr: *const CppUniquePtr<Self>) -> *mut Self {
+                    (*(ptr as *const Unique)).raw as *mut Self
+                }
+                unsafe fn unique_ptr_from_raw(raw: *mut Self) -> *mut CppUniquePtr<Self> {
+                    let raw = raw as *mut c_void;
+                    Box::into_raw(Box::new(Unique { raw })) as *mut CppUniquePtr<Self>
+                }
+                unsafe fn unique_ptr_release(ptr: *mut CppUniquePtr<Self>) -> *mut Self {
+                    let unique = &mut *(ptr as *mut Unique);
+                    std::mem::replace(&mut unique.raw, std::ptr::null_mut()) as *mut Self
+                }
+            }
+        };
+    }
+
+    impl_unique_ptr_target!(Base);
+    impl_unique_ptr_target!(Derived);
+
+    #[test]
+    fn test_shared_ptr() {
+        let dropped = Rc::new(Cell::new(false));
+        let object = Derived::new(42, dropped.clone());
+        unsafe {
+            let object = CppBox::from_raw(Box::into_raw(object)).unwrap();
+            let ptr1 = CppSharedPtr::from_box(object);
+            assert_eq!(ptr1.use_count(), 1);
+            assert_eq!(ptr1.base.value, 42);
+
+            let ptr2 = ptr1.clone();
+            assert_eq!(ptr1.use_count(), 2);
+            assert_eq!(ptr1.get().as_raw_ptr(), ptr2.get().as_raw_ptr());
+
+            let base = ptr2.upcast::<Base>();
+            assert_eq!(base.use_count(), 3);
+            assert_eq!(base.value, 42);
+
+            drop(ptr1);
+            drop(ptr2);
+            assert_eq!(base.use_count(), 1);
+            assert!(!dropped.get());
+            drop(base);
+            assert!(dropped.get());
+        }
+    }
+
+    #[test]
+    fn test_unique_ptr() {
+        let dropped = Rc::new(Cell::new(false));
+        unsafe {
+            let object = CppBox::from_raw(Box::into_raw(Derived::new(1, dropped.clone()))).unwrap();
+            let ptr = CppUniquePtr::from_box(object);
+            assert!(!ptr.is_null());
+            assert_eq!(ptr.base.value, 1);
+
+            let object = CppUniquePtr::into_box(ptr).unwrap();
+            assert_eq!(object.base.value, 1);
+            assert!(!dropped.get());
+            drop(object);
+            assert!(dropped.get());
+
+            dropped.set(false);
+            let object = CppBox::from_raw(Box::into_raw(Derived::new(2, dropped.clone()))).unwrap();
+            let ptr = CppUniquePtr::from_box(object);
+            let raw = ptr.get().as_raw_ptr();
+            let base = CppUniquePtr::upcast::<Base>(ptr);
+            assert_eq!(base.get().as_raw_ptr(), &(*raw).base as *const Base);
+            assert_eq!(base.value, 2);
+            assert!(!dropped.get());
+            drop(base);
+            assert!(dropped.get());
+
+            let null =
+                CppBox::from_raw(Derived::unique_ptr_from_raw(std::ptr::null_mut())).unwrap();
+            assert!(null.is_null());
+            assert!(CppUniquePtr::into_box(null).is_none());
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use std::error::Error;
+use std::fmt;
+
+/// An error representing a failure status returned by a C++ function.
+///
+/// Wrappers of functions that are configured to report their status through the return
+/// value return `Result<T, StatusError<S>>` instead of the status value `S`.
+/// The error contains the returned status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StatusError<S> {
+    status: S,
+}
+
+impl<S> StatusError<S> {
+    /// Creates a `StatusError` with the specified status.
+    pub fn new(status: S) -> Self {
+        StatusError { status }
+    }
+
+    /// Returns the status returned by the function.
+    pub fn status(&self) -> &S {
+        &self.status
+    }
+
+    /// Returns the status returned by the function, consuming the error.
+    pub fn into_status(self) -> S {
+        self.status
+    }
+}
+
+impl<S: fmt::Debug> fmt::Display for StatusError<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "C++ function returned failure status: {:?}", self.status)
+    }
+}
+
+impl<S: fmt::Debug> Error for StatusError<S> {}
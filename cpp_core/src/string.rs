@@ -0,0 +1,16 @@
+use std::ffi::CStr;
+use std::os::raw::c_char;
+
+/// Converts a string returned by an FFI wrapper to a `String` and frees the buffer.
+///
+/// Invalid UTF-8 sequences are replaced with `U+FFFD REPLACEMENT CHARACTER`.
+///
+/// ### Safety
+///
+/// `text` must be a non-null pointer to a nul-terminated string allocated with `malloc`.
+/// It must not be used after calling this function.
+pub unsafe fn take_malloc_string(text: *mut c_char) -> String {
+    let result = CStr::from_ptr(text).to_string_lossy().into_owned();
+    libc::free(text as *mut libc::c_void);
+    result
+}
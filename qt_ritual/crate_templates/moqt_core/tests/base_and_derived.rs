@@ -1,6 +1,7 @@
 use cpp_core::{CppBox, NullPtr, Ref};
 use moqt_core::{
-    AbstractBaseClass1, BaseClass1, DerivedClass1, DerivedClass2, DerivedClass3, DerivedSubClass1,
+    AbstractBaseClass1, BaseClass1, BaseClass2, DerivedClass1, DerivedClass2, DerivedClass3,
+    DerivedSubClass1, MultipleDerivedClass1, MultipleDerivedClass2,
 };
 
 #[test]
@@ -81,3 +82,21 @@ fn pure_virtual_functions() {
         assert_eq!(*base.virtual_function(), 45);
     }
 }
+
+#[test]
+fn multiple_inheritance() {
+    unsafe {
+        let derived1: CppBox<MultipleDerivedClass1> = MultipleDerivedClass1::new();
+        assert_eq!(derived1.base_function(), 1);
+        assert_eq!(derived1.base2_function(), 2);
+        assert_eq!(derived1.hidden_function(), 11);
+
+        let base: Ref<BaseClass2> = derived1.static_upcast();
+        assert_eq!(base.hidden_function(), 11);
+
+        let derived2: CppBox<MultipleDerivedClass2> = MultipleDerivedClass2::new();
+        assert_eq!(derived2.virtual_function(), 43);
+        assert_eq!(derived2.base2_function(), 2);
+        assert_eq!(derived2.hidden_function(), 10);
+    }
+}
@@ -1,5 +1,7 @@
+use cpp_core::{CastInto, CppBox};
 use moqt_core::basic_class::{inner_struct::InnerEnum, UpdateType};
-use moqt_core::{BasicClass, BasicClassField};
+use moqt_core::{BasicClass, BasicClassField, QVectorOfInt};
+use std::os::raw::c_int;
 
 #[test]
 fn basic_class() {
@@ -48,6 +50,47 @@ fn basic_class() {
     }
 }
 
+#[test]
+fn conversion_operators() {
+    unsafe {
+        let field = BasicClassField::new();
+        let value: bool = (&*field).cast_into();
+        assert!(value);
+        field.set(0);
+        let value: bool = (&*field).cast_into();
+        assert!(!value);
+
+        let v = BasicClass::new(1);
+        let value: c_int = (&*v).cast_into();
+        assert_eq!(value, 3);
+
+        let converted: CppBox<QVectorOfInt> = (&*v).cast_into();
+        assert_eq!(converted.count(), 1);
+        assert_eq!(*converted.at(0), 7);
+        // the converted value can be passed to `impl CastInto<Ref<_>>` arguments
+        let vec = QVectorOfInt::new_0a();
+        vec.append_q_vector_of_int(&converted);
+    }
+}
+
+#[test]
+fn rvalue_reference_overload() {
+    unsafe {
+        let v = BasicClass::new(1);
+        let c = BasicClassField::new();
+        c.set(5);
+        // the const reference overload keeps the unsuffixed name
+        assert_eq!(v.replace_class_field(&c), 1);
+        assert_eq!(v.class_field().get(), 5);
+        assert_eq!(c.get(), 5);
+
+        let c = BasicClassField::new();
+        c.set(6);
+        assert_eq!(v.replace_class_field_move(c), 2);
+        assert_eq!(v.class_field().get(), 6);
+    }
+}
+
 #[test]
 fn nested_enum() {
     let x: UpdateType = UpdateType::Add2;
@@ -0,0 +1,45 @@
+use cpp_core::{CppBox, CppHash};
+use moqt_core::{Hashable, StdHashable};
+use std::collections::HashSet;
+
+#[test]
+fn clone_and_default() {
+    unsafe {
+        let a = Hashable::new_1a(5);
+        let b = a.clone();
+        assert_eq!(b.value(), 5);
+        b.set_value(7);
+        assert_eq!(a.value(), 5);
+
+        let c: CppBox<Hashable> = Default::default();
+        assert_eq!(c.value(), 0);
+    }
+}
+
+#[test]
+fn q_hash() {
+    unsafe {
+        let a = Hashable::new_1a(5);
+        assert_eq!(a.cpp_hash(), 5);
+
+        let mut set = HashSet::new();
+        set.insert(Hashable::new_1a(1));
+        set.insert(Hashable::new_1a(2));
+        set.insert(Hashable::new_1a(1));
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&Hashable::new_1a(2)));
+    }
+}
+
+#[test]
+fn std_hash() {
+    unsafe {
+        let a = StdHashable::new(5);
+        assert_eq!(a.cpp_hash(), 5);
+
+        let mut set = HashSet::new();
+        set.insert(StdHashable::new(1));
+        set.insert(StdHashable::new(1));
+        assert_eq!(set.len(), 1);
+    }
+}
@@ -1,4 +1,4 @@
-use moqt_core::{Overloaded, QPoint, QString};
+use moqt_core::{overloaded_function, overloaded_function_int, Overloaded, QPoint, QString};
 
 #[test]
 fn overloaded() {
@@ -17,3 +17,29 @@ fn overloaded() {
         a.match_1a(42);
     }
 }
+
+#[test]
+fn overload_dispatch() {
+    unsafe {
+        let a = Overloaded::from_int(1);
+        let text = QString::from_std_str("text1");
+        assert_eq!(a.describe((5,)), 1);
+        assert_eq!(a.describe((text.as_ref(),)), 2);
+        assert_eq!(a.describe((&text,)), 2);
+        assert_eq!(a.describe_int(5), 1);
+
+        assert_eq!(overloaded_function((5,)), 1);
+        assert_eq!(overloaded_function((text.as_ref(),)), 2);
+        assert_eq!(overloaded_function((&text,)), 2);
+        assert_eq!(overloaded_function_int(5), 1);
+    }
+}
+
+#[test]
+fn function_call_operator() {
+    unsafe {
+        let a = Overloaded::from_int(1);
+        assert_eq!(a.call((4,)), 8);
+        assert_eq!(a.call((4, 5)), 9);
+    }
+}
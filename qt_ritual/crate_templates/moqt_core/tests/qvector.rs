@@ -15,6 +15,24 @@ fn qvector_int() {
     }
 }
 
+#[test]
+fn qvector_index() {
+    unsafe {
+        let mut vec = QVectorOfInt::new_0a();
+        vec.append_int(&10);
+        vec.append_int(&12);
+        assert_eq!(vec[0], 10);
+        assert_eq!(vec[1], 12);
+        vec[1] = 15;
+        assert_eq!(*vec.at(1), 15);
+
+        let mut ptr = vec.as_ptr();
+        ptr[0] = 11;
+        assert_eq!(ptr[0], 11);
+        assert_eq!(vec.as_ref()[1], 15);
+    }
+}
+
 #[test]
 fn qvector_class() {
     unsafe {
@@ -0,0 +1,46 @@
+use cpp_core::Ptr;
+use moqt_core::{call_virtual_function, BaseClass1Overrides, BaseClass1Subclass};
+use std::cell::Cell;
+use std::os::raw::c_int;
+use std::rc::Rc;
+
+struct Overrides {
+    calls: Rc<Cell<c_int>>,
+}
+
+impl BaseClass1Overrides for Overrides {
+    unsafe fn virtual_function(&self, _this: Ptr<BaseClass1Subclass>) -> c_int {
+        self.calls.set(self.calls.get() + 1);
+        50
+    }
+}
+
+struct DefaultOverrides;
+
+impl BaseClass1Overrides for DefaultOverrides {}
+
+#[test]
+fn override_virtual_function() {
+    unsafe {
+        let object = BaseClass1Subclass::new();
+        // the base class implementation is called without overrides
+        assert_eq!(call_virtual_function(&object), 42);
+
+        let calls = Rc::new(Cell::new(0));
+        object.set_overrides(Overrides {
+            calls: Rc::clone(&calls),
+        });
+        assert_eq!(call_virtual_function(&object), 50);
+        assert_eq!(object.virtual_function(), 50);
+        assert_eq!(calls.get(), 2);
+    }
+}
+
+#[test]
+fn default_override() {
+    unsafe {
+        let object = BaseClass1Subclass::new();
+        object.set_overrides(DefaultOverrides);
+        assert_eq!(call_virtual_function(&object), 42);
+    }
+}
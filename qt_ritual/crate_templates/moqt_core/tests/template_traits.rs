@@ -0,0 +1,37 @@
+use moqt_core::{QVectorOfBasicClassField, QVectorOfInt, QVectorOps, ValueHolderOps, ValueHolders};
+use std::os::raw::c_int;
+
+unsafe fn vector_count<V: QVectorOps<T>, T>(vec: &V) -> c_int {
+    vec.count()
+}
+
+unsafe fn set_and_get<H: ValueHolderOps<T>, T>(holder: &H, value: T) -> T {
+    assert!(!holder.has_value());
+    holder.set_value(value);
+    assert!(holder.has_value());
+    holder.value()
+}
+
+#[test]
+fn qvector_ops() {
+    unsafe {
+        let vec = QVectorOfInt::new_0a();
+        vec.append_int(&10);
+        vec.append_int(&12);
+        assert_eq!(vector_count(&*vec), 2);
+
+        let vec = QVectorOfBasicClassField::new_0a();
+        assert_eq!(vector_count(&*vec), 0);
+    }
+}
+
+#[test]
+fn value_holder_ops() {
+    unsafe {
+        let holder = ValueHolders::int_holder();
+        assert_eq!(set_and_get(&*holder, 5), 5);
+
+        let holder = ValueHolders::double_holder();
+        assert!((set_and_get(&*holder, 2.5) - 2.5).abs() < 1e-9);
+    }
+}
@@ -1,5 +1,11 @@
-use moqt_core::{moqt_abs, moqt_core_version};
+use cpp_core::StatusError;
+use moqt_core::{
+    moqt_abs, moqt_call_twice_closure, moqt_check_range, moqt_core_version, moqt_divide_checked,
+    moqt_is_even, moqt_status_text, moqt_sum_slice,
+};
+use std::cell::RefCell;
 use std::ffi::CStr;
+use std::rc::Rc;
 
 #[test]
 fn abs() {
@@ -10,6 +16,49 @@ fn abs() {
     }
 }
 
+#[test]
+fn checked() {
+    unsafe {
+        assert_eq!(moqt_divide_checked(7, 2), Some(3));
+        assert_eq!(moqt_divide_checked(1, 0), None);
+    }
+}
+
+#[test]
+fn status() {
+    unsafe {
+        assert_eq!(moqt_check_range(3, 5), Ok(()));
+        assert_eq!(moqt_check_range(-1, 5), Err(StatusError::new(1)));
+        let err = moqt_check_range(6, 5).unwrap_err();
+        assert_eq!(*err.status(), 2);
+
+        assert_eq!(moqt_is_even(4), Ok(()));
+        assert_eq!(moqt_is_even(3), Err(StatusError::new(false)));
+
+        // unsupported status type, the wrapper returns the value unchanged
+        let text = CStr::from_ptr(moqt_status_text(0)).to_str().unwrap();
+        assert_eq!(text, "ok");
+    }
+}
+
+#[test]
+fn slice() {
+    unsafe {
+        assert_eq!(moqt_sum_slice(&[1, 2, 3]), 6);
+        assert_eq!(moqt_sum_slice(&[]), 0);
+    }
+}
+
+#[test]
+fn closure() {
+    let values = Rc::new(RefCell::new(Vec::new()));
+    let values2 = Rc::clone(&values);
+    unsafe {
+        moqt_call_twice_closure(move |value: i32| values2.borrow_mut().push(value));
+    }
+    assert_eq!(*values.borrow(), vec![1, 2]);
+}
+
 #[test]
 fn version() {
     unsafe {
@@ -1,6 +1,6 @@
 use cpp_core::{CppBox, Ptr};
-use moqt_core::{BasicClass, QBox, QPoint, QPtr, QVectorOfInt};
-use moqt_gui::{get_window, QVectorOfQWindow, QWindow};
+use moqt_core::{BaseClass2, BasicClass, QBox, QPoint, QPtr, QVectorOfInt};
+use moqt_gui::{get_window, QPaintWindow, QVectorOfQWindow, QWindow};
 
 #[test]
 fn test_qwindow() {
@@ -21,6 +21,22 @@ fn test_qwindow() {
     }
 }
 
+#[test]
+fn dependency_base_class() {
+    unsafe {
+        let window: QBox<QPaintWindow> = QPaintWindow::new();
+        let point: CppBox<QPoint> = window.pos();
+        assert_eq!(point.x(), 0);
+
+        // methods of `BaseClass2` from `moqt_core` are forwarded to `QPaintWindow`
+        assert_eq!(window.base2_function(), 2);
+        assert_eq!(window.hidden_function(), 12);
+
+        let base: Ptr<BaseClass2> = window.as_ptr().static_upcast();
+        assert_eq!(base.hidden_function(), 12);
+    }
+}
+
 #[test]
 fn test_get_window() {
     unsafe {
@@ -8,7 +8,7 @@ use ritual::cpp_template_instantiator::instantiate_function;
 use ritual::cpp_type::{CppBuiltInNumericType, CppType};
 use ritual::processor::ProcessorData;
 use ritual::rust_info::{NameType, RustItem, RustPathScope};
-use ritual::rust_type::{RustFinalType, RustPath, RustToFfiTypeConversion};
+use ritual::rust_type::{RustFinalType, RustPath, RustToFfiTypeConversion, StatusSuccess};
 use ritual_common::errors::{bail, err_msg, Result};
 use ritual_common::file_utils::repo_dir_path;
 use ritual_common::string_utils::CaseOperations;
@@ -181,6 +181,8 @@ pub fn core_config(config: &mut Config) -> Result<()> {
             "swap",
             // is not cross-platform and is deprecated anyway
             "QProcess::pid",
+            // specializations are used through the `std::hash` wrappers
+            "std::hash",
         ];
         if blocked.contains(&string.as_str()) {
             return Ok(false);
@@ -291,6 +293,31 @@ pub fn core_config(config: &mut Config) -> Result<()> {
         Ok(())
     });
 
+    if config.crate_properties().name().starts_with("moqt") {
+        config.add_subclass_wrapper(CppPath::from_good_str("BaseClass1"));
+        config.set_std_hash(true);
+        config.add_status_function(
+            CppPath::from_good_str("moqt_check_range"),
+            StatusSuccess::Zero,
+        );
+        config.set_status_hook(|function| {
+            Ok(match function.path.last().name.as_str() {
+                "moqt_is_even" => Some(StatusSuccess::True),
+                // the return type is not supported, so the wrapper is not converted
+                "moqt_status_text" => Some(StatusSuccess::Zero),
+                _ => None,
+            })
+        });
+        config.set_overload_dispatch_hook(|path| {
+            let name = path.last();
+            Ok(if name == "describe" || name == "overloaded_function" {
+                Some(true)
+            } else {
+                None
+            })
+        });
+    }
+
     let tests = if config.crate_properties().name().starts_with("moqt") {
         vec![PreliminaryTest::new(
             "moqt_abs",
@@ -425,10 +452,13 @@ fn connection_to_bool_function() -> CppFunction {
                 true,
                 CppType::Class(CppPath::from_good_str("QMetaObject::Connection")),
             ),
+            type_alias: None,
         }],
         cast: None,
         operator: None,
         declaration_code: None,
         return_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Bool),
+        return_type_alias: None,
+        has_c_linkage: false,
     }
 }
@@ -0,0 +1,33 @@
+#include "Hashable.h"
+
+Hashable::Hashable() : m_value(0) {}
+
+Hashable::Hashable(int value) : m_value(value) {}
+
+Hashable::Hashable(const Hashable& other) : m_value(other.m_value) {}
+
+int Hashable::value() const {
+    return m_value;
+}
+
+void Hashable::setValue(int value) {
+    m_value = value;
+}
+
+bool Hashable::operator==(const Hashable& other) const {
+    return m_value == other.m_value;
+}
+
+unsigned int qHash(const Hashable& value, unsigned int seed) {
+    return static_cast<unsigned int>(value.value()) ^ seed;
+}
+
+StdHashable::StdHashable(int value) : m_value(value) {}
+
+int StdHashable::value() const {
+    return m_value;
+}
+
+bool StdHashable::operator==(const StdHashable& other) const {
+    return m_value == other.m_value;
+}
@@ -0,0 +1,40 @@
+#ifndef HASHABLE_H
+#define HASHABLE_H
+
+#include "moqt_core_exports.h"
+#include <cstddef>
+#include <functional>
+
+class MOQT_CORE_EXPORT Hashable {
+public:
+    Hashable();
+    Hashable(int value);
+    Hashable(const Hashable& other);
+    int value() const;
+    void setValue(int value);
+    bool operator==(const Hashable& other) const;
+
+private:
+    int m_value;
+};
+
+MOQT_CORE_EXPORT unsigned int qHash(const Hashable& value, unsigned int seed = 0);
+
+class MOQT_CORE_EXPORT StdHashable {
+public:
+    StdHashable(int value);
+    int value() const;
+    bool operator==(const StdHashable& other) const;
+
+private:
+    int m_value;
+};
+
+template<>
+struct std::hash<StdHashable> {
+    std::size_t operator()(const StdHashable& value) const {
+        return static_cast<std::size_t>(value.value());
+    }
+};
+
+#endif //HASHABLE_H
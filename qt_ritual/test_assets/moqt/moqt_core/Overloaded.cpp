@@ -16,3 +16,24 @@ void Overloaded::setPos(QPoint pos, bool flag) {}
 
 void Overloaded::match() {}
 void Overloaded::match(int x) {}
+
+int Overloaded::describe(int x) const {
+    return 1;
+}
+int Overloaded::describe(const QString& x) const {
+    return 2;
+}
+
+int Overloaded::operator()(int x) const {
+    return x * 2;
+}
+int Overloaded::operator()(int x, int y) const {
+    return x + y;
+}
+
+int overloadedFunction(int x) {
+    return 1;
+}
+int overloadedFunction(const QString& x) {
+    return 2;
+}
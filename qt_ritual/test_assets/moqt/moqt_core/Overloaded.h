@@ -21,6 +21,15 @@ public:
 
     void match();
     void match(int x);
+
+    int describe(int x) const;
+    int describe(const QString& x) const;
+
+    int operator()(int x) const;
+    int operator()(int x, int y) const;
 };
 
+MOQT_CORE_EXPORT int overloadedFunction(int x);
+MOQT_CORE_EXPORT int overloadedFunction(const QString& x);
+
 #endif //OVERLOADED_H
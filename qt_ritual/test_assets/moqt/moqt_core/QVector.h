@@ -25,6 +25,12 @@ public:
     T& at(int pos) {
         return m_data[pos];
     }
+    T& operator[](int pos) {
+        return m_data[pos];
+    }
+    const T& operator[](int pos) const {
+        return m_data[pos];
+    }
     int count() const {
         return m_size;
     }
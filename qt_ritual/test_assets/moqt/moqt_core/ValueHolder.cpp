@@ -0,0 +1,9 @@
+#include "ValueHolder.h"
+
+ValueHolder<int> ValueHolders::intHolder() {
+    return ValueHolder<int>();
+}
+
+ValueHolder<double> ValueHolders::doubleHolder() {
+    return ValueHolder<double>();
+}
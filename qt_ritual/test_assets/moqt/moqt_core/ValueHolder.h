@@ -0,0 +1,32 @@
+#ifndef VALUE_HOLDER_H
+#define VALUE_HOLDER_H
+
+#include "moqt_core_exports.h"
+
+template<typename T>
+class ValueHolder {
+public:
+    ValueHolder() : m_value(), m_hasValue(false) {}
+    bool hasValue() const {
+        return m_hasValue;
+    }
+    T value() const {
+        return m_value;
+    }
+    void setValue(T value) {
+        m_value = value;
+        m_hasValue = true;
+    }
+
+private:
+    T m_value;
+    bool m_hasValue;
+};
+
+class MOQT_CORE_EXPORT ValueHolders {
+public:
+    static ValueHolder<int> intHolder();
+    static ValueHolder<double> doubleHolder();
+};
+
+#endif // VALUE_HOLDER_H
@@ -1 +1,5 @@
 #include "base_and_derived.h"
+
+int callVirtualFunction(const BaseClass1* object) {
+    return object->virtualFunction();
+}
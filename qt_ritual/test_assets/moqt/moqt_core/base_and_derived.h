@@ -20,6 +20,8 @@ private:
     int m_baseFunctionResult = 0;
 };
 
+MOQT_CORE_EXPORT int callVirtualFunction(const BaseClass1* object);
+
 class MOQT_CORE_EXPORT DerivedClass1 : public BaseClass1 {
 public:
     DerivedClass1() {}
@@ -50,3 +52,22 @@ public:
     DerivedClass3() {}
     int* virtualFunction() override { return new int(45); }
 };
+
+class MOQT_CORE_EXPORT BaseClass2 {
+public:
+    BaseClass2() {}
+    virtual ~BaseClass2() {}
+    int base2Function() const { return 2; }
+    virtual int hiddenFunction() const { return 10; }
+};
+
+class MOQT_CORE_EXPORT MultipleDerivedClass1 : public BaseClass1, public BaseClass2 {
+public:
+    MultipleDerivedClass1() {}
+    int hiddenFunction() const override { return 11; }
+};
+
+class MOQT_CORE_EXPORT MultipleDerivedClass2 : public DerivedClass1, public BaseClass2 {
+public:
+    MultipleDerivedClass2() {}
+};
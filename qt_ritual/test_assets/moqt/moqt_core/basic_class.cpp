@@ -1,4 +1,5 @@
 #include "basic_class.h"
+#include <utility>
 
 BasicClass::BasicClass(int x) : int_field(1), intReference_field(int_field) {
     m_foo = x;
@@ -61,3 +62,13 @@ QFlags<BasicClass::UpdateType> operator|(BasicClass::UpdateType f1, BasicClass::
 
 void BasicClass::setRef(const int& value) {
 }
+
+int BasicClass::replaceClassField(const BasicClassField& value) {
+    class_field = value;
+    return 1;
+}
+
+int BasicClass::replaceClassField(BasicClassField&& value) {
+    class_field = std::move(value);
+    return 2;
+}
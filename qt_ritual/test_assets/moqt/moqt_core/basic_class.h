@@ -17,6 +17,9 @@ public:
     int get() const {
         return r;
     }
+    explicit operator bool() const {
+        return r != 0;
+    }
 private:
     int r;
 };
@@ -53,6 +56,9 @@ public:
 
     void setRef(const int& value);
 
+    int replaceClassField(const BasicClassField& value);
+    int replaceClassField(BasicClassField&& value);
+
 
     /*void overloaded_normal_const_and_static();
     void overloaded_normal_const_and_static() const;
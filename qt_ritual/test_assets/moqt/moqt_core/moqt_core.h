@@ -12,3 +12,5 @@
 #include "Overloaded.h"
 #include "QByteArray.h"
 #include "QVector.h"
+#include "ValueHolder.h"
+#include "Hashable.h"
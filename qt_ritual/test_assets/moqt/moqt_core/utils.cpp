@@ -8,6 +8,46 @@ int moqt_abs(int x) {
     }
 }
 
+int moqt_divide(int a, int b, bool *ok) {
+    if (b == 0) {
+        *ok = false;
+        return 0;
+    }
+    *ok = true;
+    return a / b;
+}
+
+int moqt_sum(const int *values, int count) {
+    int sum = 0;
+    for (int i = 0; i < count; i++) {
+        sum += values[i];
+    }
+    return sum;
+}
+
+int moqt_check_range(int value, int max) {
+    if (value < 0) {
+        return 1;
+    }
+    if (value > max) {
+        return 2;
+    }
+    return 0;
+}
+
+bool moqt_is_even(int value) {
+    return value % 2 == 0;
+}
+
+const char *moqt_status_text(int status) {
+    return status == 0 ? "ok" : "error";
+}
+
+void moqt_call_twice(void (*callback)(void *data, int value), void *data) {
+    callback(data, 1);
+    callback(data, 2);
+}
+
 const char *moqt_core_version() {
     return "0.0.1";
 }
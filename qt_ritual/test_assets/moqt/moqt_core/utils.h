@@ -7,4 +7,16 @@ MOQT_CORE_EXPORT const char* moqt_core_version();
 
 MOQT_CORE_EXPORT int moqt_abs(int x);
 
+MOQT_CORE_EXPORT int moqt_divide(int a, int b, bool* ok);
+
+MOQT_CORE_EXPORT int moqt_sum(const int* values, int count);
+
+MOQT_CORE_EXPORT int moqt_check_range(int value, int max);
+
+MOQT_CORE_EXPORT bool moqt_is_even(int value);
+
+MOQT_CORE_EXPORT const char* moqt_status_text(int status);
+
+MOQT_CORE_EXPORT void moqt_call_twice(void (*callback)(void* data, int value), void* data);
+
 #endif // CTRT1_UTILS_H
@@ -6,6 +6,7 @@
 #include "QPoint.h"
 #include "QObject.h"
 #include "namespaces.h"
+#include "base_and_derived.h"
 
 class MOQT_GUI_EXPORT QWindow : public QObject {
 public:
@@ -27,6 +28,12 @@ private:
     QPoint m_pos;
 };
 
+class MOQT_GUI_EXPORT QPaintWindow : public QWindow, public BaseClass2 {
+public:
+    QPaintWindow() {}
+    int hiddenFunction() const override { return 12; }
+};
+
 MOQT_GUI_EXPORT QWindow* get_window();
 
 MOQT_GUI_EXPORT ns1::Templated1<int> get_same_template1();
@@ -54,21 +54,26 @@ fn add_vector_from_pointers_functions(data: &mut ProcessorData<'_>) -> Result<()
                     name: "first".into(),
                     argument_type: CppType::new_pointer(true, t.clone()),
                     has_default_value: false,
+                    type_alias: None,
                 },
                 CppFunctionArgument {
                     name: "last".into(),
                     argument_type: CppType::new_pointer(true, t.clone()),
                     has_default_value: false,
+                    type_alias: None,
                 },
                 CppFunctionArgument {
                     name: "alloc".into(),
                     argument_type: allocator_type,
                     has_default_value: true,
+                    type_alias: None,
                 },
             ],
             allows_variadic_arguments: false,
             cast: None,
             declaration_code: None,
+            return_type_alias: None,
+            has_c_linkage: false,
         }),
     )?;
     data.add_cpp_item(
@@ -104,16 +109,20 @@ fn add_vector_from_pointers_functions(data: &mut ProcessorData<'_>) -> Result<()
                     name: "first".into(),
                     argument_type: CppType::new_pointer(true, t.clone()),
                     has_default_value: false,
+                    type_alias: None,
                 },
                 CppFunctionArgument {
                     name: "last".into(),
                     argument_type: CppType::new_pointer(true, t),
                     has_default_value: false,
+                    type_alias: None,
                 },
             ],
             allows_variadic_arguments: false,
             cast: None,
             declaration_code: None,
+            return_type_alias: None,
+            has_c_linkage: false,
         }),
     )?;
     Ok(())
@@ -2,10 +2,12 @@
 
 use crate::cpp_checker::PreliminaryTest;
 use crate::cpp_data::{CppItem, CppPath};
+use crate::cpp_ffi_data::CppFlagContainer;
+use crate::cpp_function::CppFunction;
 use crate::cpp_parser::CppParserOutput;
 use crate::processor::{ProcessingSteps, ProcessorData};
 use crate::rust_info::{NameType, RustItem, RustPathScope};
-use crate::rust_type::RustPath;
+use crate::rust_type::{RustPath, StatusSuccess};
 use ritual_common::cpp_build_config::{CppBuildConfig, CppBuildPaths};
 use ritual_common::errors::{bail, Result};
 use ritual_common::target::Target;
@@ -177,6 +179,17 @@ pub type RustItemHook = dyn Fn(&mut RustItem, &ProcessorData<'_>) -> Result<()>
 pub type AfterCppParserHook =
     dyn Fn(&mut ProcessorData<'_>, &CppParserOutput) -> Result<()> + 'static;
 pub type CppItemFilterHook = dyn Fn(&CppItem) -> Result<bool> + 'static;
+pub type CatchExceptionsHook = dyn Fn(&CppFunction) -> Result<Option<bool>> + 'static;
+pub type OverloadDispatchHook = dyn Fn(&RustPath) -> Result<Option<bool>> + 'static;
+pub type BitmaskEnumHook = dyn Fn(&CppPath) -> Result<bool> + 'static;
+pub type OutArgumentsHook = dyn Fn(&CppPath) -> Result<Option<Vec<String>>> + 'static;
+pub type PointerAnnotationHook =
+    dyn Fn(&CppFunction, Option<usize>) -> Result<Option<PointerAnnotation>> + 'static;
+pub type StatusHook = dyn Fn(&CppFunction) -> Result<Option<StatusSuccess>> + 'static;
+pub type SliceArgumentsHook = dyn Fn(&CppPath) -> Result<Option<Vec<(usize, usize)>>> + 'static;
+pub type CallbackConventionHook =
+    dyn Fn(&CppFunction) -> Result<Option<Vec<CallbackConvention>>> + 'static;
+pub type ThreadSafetyHook = dyn Fn(&CppPath) -> Result<Option<ThreadSafety>> + 'static;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkerLibraryConfig {
@@ -219,11 +232,96 @@ pub struct Config {
     rust_item_hook: Option<Box<RustItemHook>>,
     after_cpp_parser_hooks: Vec<Box<AfterCppParserHook>>,
     cpp_item_filter_hook: Option<Box<CppItemFilterHook>>,
+    catch_exceptions: bool,
+    catch_exceptions_hook: Option<Box<CatchExceptionsHook>>,
+    overload_dispatch: bool,
+    overload_dispatch_hook: Option<Box<OverloadDispatchHook>>,
+    flag_containers: Vec<CppFlagContainer>,
+    bitmask_enum_hook: Option<Box<BitmaskEnumHook>>,
+    hash_functions: Vec<CppPath>,
+    std_hash: bool,
+    out_argument_names: Vec<String>,
+    ok_flag_argument_names: Vec<String>,
+    trailing_out_arguments: bool,
+    out_arguments_hook: Option<Box<OutArgumentsHook>>,
+    slice_arguments_hook: Option<Box<SliceArgumentsHook>>,
+    callback_convention_hook: Option<Box<CallbackConventionHook>>,
+    pointer_annotation_rules: Vec<PointerAnnotationRule>,
+    pointer_annotation_hook: Option<Box<PointerAnnotationHook>>,
+    status_functions: Vec<(CppPath, StatusSuccess)>,
+    status_hook: Option<Box<StatusHook>>,
+    subclass_wrappers: Vec<CppPath>,
+    thread_safety_hook: Option<Box<ThreadSafetyHook>>,
     cluster_config: Option<ClusterConfig>,
     cpp_checker_tests: Vec<PreliminaryTest>,
     write_dependencies_local_paths: bool,
 }
 
+/// Semantics of a pointer to an object returned or accepted by a C++ function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PointerAnnotation {
+    /// The pointer is never null. It's represented by `Ref<T>`.
+    NonNull,
+    /// The pointer may be null. A returned pointer is represented by `Option<Ref<T>>`.
+    Nullable,
+    /// Ownership of the object is transferred to the caller (for return values)
+    /// or to the function (for arguments). The object is represented by `CppBox<T>`.
+    Owned,
+}
+
+/// Annotation of a pointer returned or accepted by a C++ function.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointerAnnotationRule {
+    /// Path of the function. The rule applies to all its overloads.
+    pub function: CppPath,
+    /// Name of the argument, or `None` for the return value
+    pub argument: Option<String>,
+    pub annotation: PointerAnnotation,
+}
+
+#[derive(Deserialize)]
+struct PointerAnnotationRulesFile {
+    #[serde(default)]
+    rule: Vec<PointerAnnotationRuleEntry>,
+}
+
+#[derive(Deserialize)]
+struct PointerAnnotationRuleEntry {
+    function: String,
+    argument: Option<String>,
+    annotation: PointerAnnotation,
+}
+
+/// Describes how a C++ function accepts a callback. All indexes of arguments start from 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallbackConvention {
+    /// Index of the argument receiving the pointer to the callback function
+    pub callback_argument: usize,
+    /// Index of the callback function's argument receiving the user data pointer
+    pub callback_data_argument: usize,
+    /// Index of the argument receiving the user data pointer (`void*`)
+    /// that is passed to the callback
+    pub data_argument: usize,
+    /// Index of the argument receiving the function that deletes the user data
+    /// (`void (*)(void*)`). If there is no such argument, the callback can only be
+    /// called before the C++ function returns.
+    pub deleter_argument: Option<usize>,
+}
+
+/// Declares that objects of a C++ class can be used from multiple threads.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ThreadSafety {
+    /// An object can be moved to another thread (the Rust type implements `Send`)
+    pub is_send: bool,
+    /// An object can be accessed from multiple threads at the same time
+    /// (the Rust type implements `Sync`)
+    pub is_sync: bool,
+    /// Explanation of why the class is thread-safe. It's added
+    /// to the documentation of the Rust type.
+    pub rationale: String,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MovableTypesHookOutput {
     Movable,
@@ -252,6 +350,26 @@ impl Config {
             rust_item_hook: Default::default(),
             after_cpp_parser_hooks: Default::default(),
             cpp_item_filter_hook: Default::default(),
+            catch_exceptions: false,
+            catch_exceptions_hook: Default::default(),
+            overload_dispatch: false,
+            overload_dispatch_hook: Default::default(),
+            flag_containers: vec![CppFlagContainer::qflags()],
+            bitmask_enum_hook: Default::default(),
+            hash_functions: vec![CppPath::from_good_str("qHash")],
+            std_hash: false,
+            out_argument_names: Default::default(),
+            ok_flag_argument_names: vec!["ok".to_string()],
+            trailing_out_arguments: false,
+            out_arguments_hook: Default::default(),
+            slice_arguments_hook: Default::default(),
+            callback_convention_hook: Default::default(),
+            pointer_annotation_rules: Default::default(),
+            pointer_annotation_hook: Default::default(),
+            status_functions: Default::default(),
+            status_hook: Default::default(),
+            subclass_wrappers: Default::default(),
+            thread_safety_hook: Default::default(),
             cluster_config: None,
             cpp_checker_tests: Default::default(),
             write_dependencies_local_paths: true,
@@ -499,6 +617,355 @@ impl Config {
         self.cpp_item_filter_hook.as_deref()
     }
 
+    /// Enables catching C++ exceptions in FFI wrappers of all functions of the crate.
+    /// An exception thrown by a wrapped function is reported to Rust
+    /// and the Rust wrapper returns `Result<T, cpp_core::CppException>`
+    /// instead of `T`. Exceptions are not caught by default.
+    /// Exceptions of variadic functions are never caught.
+    pub fn set_catch_exceptions(&mut self, value: bool) {
+        self.catch_exceptions = value;
+    }
+
+    pub fn catch_exceptions(&self) -> bool {
+        self.catch_exceptions
+    }
+
+    /// Sets a hook that decides whether C++ exceptions should be caught in the FFI wrapper
+    /// of a function. If the hook returns `None`, the value set by
+    /// `set_catch_exceptions` is used.
+    pub fn set_catch_exceptions_hook(
+        &mut self,
+        hook: impl Fn(&CppFunction) -> Result<Option<bool>> + 'static,
+    ) {
+        assert!(
+            self.catch_exceptions_hook.is_none(),
+            "only one hook can be set"
+        );
+        self.catch_exceptions_hook = Some(Box::new(hook));
+    }
+
+    pub fn catch_exceptions_hook(&self) -> Option<&CatchExceptionsHook> {
+        self.catch_exceptions_hook.as_deref()
+    }
+
+    /// Enables generation of a single generic function for each group of overloaded
+    /// C++ functions of the crate. The function accepts a tuple of arguments
+    /// and calls the overload matching the types of the tuple's elements.
+    /// The elements accept the same `CastInto` conversions as the arguments of the overloads.
+    /// The dispatch is implemented through a trait in the `overloading` module
+    /// of the generated crate. Functions with suffixed names (e.g. `new_2a`)
+    /// are still generated and can be used for explicit disambiguation.
+    /// Disabled by default, except for overloaded function call operators,
+    /// which are always callable through `call` unless the hook disables it.
+    pub fn set_overload_dispatch(&mut self, value: bool) {
+        self.overload_dispatch = value;
+    }
+
+    pub fn overload_dispatch(&self) -> bool {
+        self.overload_dispatch
+    }
+
+    /// Sets a hook that decides whether the generic dispatch function should be generated
+    /// for the group of overloaded functions with the Rust path `path`. If the hook returns
+    /// `None`, the value set by `set_overload_dispatch` is used.
+    pub fn set_overload_dispatch_hook(
+        &mut self,
+        hook: impl Fn(&RustPath) -> Result<Option<bool>> + 'static,
+    ) {
+        assert!(
+            self.overload_dispatch_hook.is_none(),
+            "only one hook can be set"
+        );
+        self.overload_dispatch_hook = Some(Box::new(hook));
+    }
+
+    pub fn overload_dispatch_hook(&self) -> Option<&OverloadDispatchHook> {
+        self.overload_dispatch_hook.as_deref()
+    }
+
+    /// Registers a C++ class template that holds a combination of flags of an enum
+    /// (like `Flags<E>`). Values of its instantiations are passed through FFI as `int`
+    /// and represented by the enum's wrapper in the Rust API. The enum wrapper
+    /// receives bit operations. `QFlags` is registered by default and
+    /// is additionally wrapped in `qt_core::QFlags`.
+    pub fn add_flag_container(&mut self, container: CppFlagContainer) {
+        self.flag_containers.push(container);
+    }
+
+    pub fn flag_containers(&self) -> &[CppFlagContainer] {
+        &self.flag_containers
+    }
+
+    /// Sets a hook that decides whether the plain C++ enum `path` is a bitmask.
+    /// Bit operations (`BitOr`, `BitAnd`, `BitXor`, `Not`) and `test_flag` are generated
+    /// for the wrappers of such enums, unless `QFlags<E>` is declared for the enum,
+    /// in which case the bit operations produce `QFlags` instead.
+    pub fn set_bitmask_enum_hook(&mut self, hook: impl Fn(&CppPath) -> Result<bool> + 'static) {
+        assert!(self.bitmask_enum_hook.is_none(), "only one hook can be set");
+        self.bitmask_enum_hook = Some(Box::new(hook));
+    }
+
+    pub fn bitmask_enum_hook(&self) -> Option<&BitmaskEnumHook> {
+        self.bitmask_enum_hook.as_deref()
+    }
+
+    /// Registers a C++ hash function (like `qHash`). Overloads of this function
+    /// accepting a single argument of a class type are used to implement `cpp_core::CppHash`
+    /// for that type, making `CppBox<T>` implement `Hash`. Additional arguments
+    /// with default values (like the seed of `qHash`) are omitted.
+    /// `qHash` is registered by default.
+    pub fn add_hash_function(&mut self, path: CppPath) {
+        self.hash_functions.push(path);
+    }
+
+    pub fn hash_functions(&self) -> &[CppPath] {
+        &self.hash_functions
+    }
+
+    /// Enables implementing `cpp_core::CppHash` through `std::hash<T>` for class types
+    /// of the crate. A function calling `std::hash<T>` is generated for each class type
+    /// and is only used if it passes the C++ checker, so this option increases
+    /// the number of checks. Hash functions registered with `add_hash_function`
+    /// take precedence. Disabled by default.
+    pub fn set_std_hash(&mut self, value: bool) {
+        self.std_hash = value;
+    }
+
+    pub fn std_hash(&self) -> bool {
+        self.std_hash
+    }
+
+    /// Registers a name of output arguments. If a function has a non-const pointer
+    /// or reference to a primitive type with this name, an additional Rust wrapper
+    /// is generated. It doesn't accept output arguments and returns a tuple containing the
+    /// function's return value and the values of the output arguments. The original
+    /// wrapper is still generated.
+    pub fn add_out_argument_name(&mut self, name: impl Into<String>) {
+        self.out_argument_names.push(name.into());
+    }
+
+    pub fn out_argument_names(&self) -> &[String] {
+        &self.out_argument_names
+    }
+
+    /// Registers a name of `bool` output arguments reporting success of the function
+    /// (like `bool* ok`). The additional Rust wrapper returns `None` if the C++ function
+    /// reports a failure. `ok` is registered by default.
+    pub fn add_ok_flag_argument_name(&mut self, name: impl Into<String>) {
+        self.ok_flag_argument_names.push(name.into());
+    }
+
+    pub fn ok_flag_argument_names(&self) -> &[String] {
+        &self.ok_flag_argument_names
+    }
+
+    /// Enables treating all non-const pointers and references to primitive types
+    /// at the end of the argument list as output arguments, regardless of their names.
+    /// Disabled by default.
+    pub fn set_trailing_out_arguments(&mut self, value: bool) {
+        self.trailing_out_arguments = value;
+    }
+
+    pub fn trailing_out_arguments(&self) -> bool {
+        self.trailing_out_arguments
+    }
+
+    /// Sets a hook that returns names of output arguments of the C++ function `path`.
+    /// If the hook returns `None`, output arguments are detected based on
+    /// the registered names and `set_trailing_out_arguments`.
+    pub fn set_out_arguments_hook(
+        &mut self,
+        hook: impl Fn(&CppPath) -> Result<Option<Vec<String>>> + 'static,
+    ) {
+        assert!(
+            self.out_arguments_hook.is_none(),
+            "only one hook can be set"
+        );
+        self.out_arguments_hook = Some(Box::new(hook));
+    }
+
+    pub fn out_arguments_hook(&self) -> Option<&OutArgumentsHook> {
+        self.out_arguments_hook.as_deref()
+    }
+
+    /// Sets a hook that returns pairs of indexes of a pointer argument and a length argument
+    /// of the C++ function `path`. For functions having such pairs, an additional
+    /// Rust wrapper is generated. It accepts a slice (`&[T]` or `&mut [T]`, or `&str` for
+    /// `const char*`) in place of each pair. The original wrapper is still generated.
+    ///
+    /// If the hook returns `None`, a pointer to a numeric type followed by an integer
+    /// argument with a name ending with `len`, `length`, `size` or `count` is considered
+    /// a pair.
+    pub fn set_slice_arguments_hook(
+        &mut self,
+        hook: impl Fn(&CppPath) -> Result<Option<Vec<(usize, usize)>>> + 'static,
+    ) {
+        assert!(
+            self.slice_arguments_hook.is_none(),
+            "only one hook can be set"
+        );
+        self.slice_arguments_hook = Some(Box::new(hook));
+    }
+
+    pub fn slice_arguments_hook(&self) -> Option<&SliceArgumentsHook> {
+        self.slice_arguments_hook.as_deref()
+    }
+
+    /// Sets a hook that returns conventions of callback arguments of the C++ function.
+    /// For functions having a callback, an additional Rust wrapper is generated.
+    /// It accepts a `FnMut` closure in place of the callback, the user data and the deleter.
+    /// Only one callback per function is supported, so the first applicable convention
+    /// is used. The original wrapper is still generated.
+    ///
+    /// If the hook returns `None`, the convention is detected automatically.
+    /// A function pointer argument is considered a callback if the pointed function
+    /// receives `void*` as its first or last argument and the C++ function has exactly
+    /// one other `void*` argument. An argument of type `void (*)(void*)` is
+    /// considered the deleter. If a detected convention has no deleter, the closure
+    /// is never dropped because the C++ function may store the callback. Return
+    /// a convention without a deleter from the hook to declare that the callback
+    /// is only called before the C++ function returns.
+    pub fn set_callback_convention_hook(
+        &mut self,
+        hook: impl Fn(&CppFunction) -> Result<Option<Vec<CallbackConvention>>> + 'static,
+    ) {
+        assert!(
+            self.callback_convention_hook.is_none(),
+            "only one hook can be set"
+        );
+        self.callback_convention_hook = Some(Box::new(hook));
+    }
+
+    pub fn callback_convention_hook(&self) -> Option<&CallbackConventionHook> {
+        self.callback_convention_hook.as_deref()
+    }
+
+    /// Adds an annotation of a pointer returned or accepted by a C++ function.
+    /// The annotation affects the type of the returned value or the argument
+    /// in the Rust wrapper of the function.
+    pub fn add_pointer_annotation_rule(&mut self, rule: PointerAnnotationRule) {
+        self.pointer_annotation_rules.push(rule);
+    }
+
+    /// Adds annotations of pointers declared in the TOML format:
+    ///
+    /// ```toml
+    /// [[rule]]
+    /// function = "QListWidget::takeItem"
+    /// annotation = "owned"
+    ///
+    /// [[rule]]
+    /// function = "QObject::setParent"
+    /// argument = "parent"
+    /// annotation = "nullable"
+    /// ```
+    ///
+    /// `annotation` can be `non_null`, `nullable` or `owned`. If `argument` is omitted,
+    /// the rule applies to the return value.
+    pub fn add_pointer_annotation_rules_from_toml(&mut self, text: &str) -> Result<()> {
+        let file: PointerAnnotationRulesFile = toml::from_str(text)?;
+        for entry in file.rule {
+            self.pointer_annotation_rules.push(PointerAnnotationRule {
+                function: entry.function.parse()?,
+                argument: entry.argument,
+                annotation: entry.annotation,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn pointer_annotation_rules(&self) -> &[PointerAnnotationRule] {
+        &self.pointer_annotation_rules
+    }
+
+    /// Sets a hook that returns the annotation of the pointer returned by `function`
+    /// (if the argument index is `None`) or accepted as its argument. If the hook
+    /// returns `None`, rules added with `add_pointer_annotation_rule` are used.
+    pub fn set_pointer_annotation_hook(
+        &mut self,
+        hook: impl Fn(&CppFunction, Option<usize>) -> Result<Option<PointerAnnotation>> + 'static,
+    ) {
+        assert!(
+            self.pointer_annotation_hook.is_none(),
+            "only one hook can be set"
+        );
+        self.pointer_annotation_hook = Some(Box::new(hook));
+    }
+
+    pub fn pointer_annotation_hook(&self) -> Option<&PointerAnnotationHook> {
+        self.pointer_annotation_hook.as_deref()
+    }
+
+    /// Marks the value returned by the C++ function `path` (including all its overloads)
+    /// as a status of the operation. The Rust wrapper of the function returns
+    /// `Result<(), cpp_core::StatusError<S>>` (where `S` is the type of the status)
+    /// instead of the status. The function is considered successful if the returned value
+    /// satisfies `success`.
+    ///
+    /// If the function has output arguments, the additional wrapper returning their values
+    /// returns them in the `Ok` variant.
+    pub fn add_status_function(&mut self, path: CppPath, success: StatusSuccess) {
+        self.status_functions.push((path, success));
+    }
+
+    pub fn status_functions(&self) -> &[(CppPath, StatusSuccess)] {
+        &self.status_functions
+    }
+
+    /// Sets a hook that returns the success condition if the value returned by
+    /// the C++ function is a status (see `add_status_function`). If the hook returns `None`,
+    /// functions added with `add_status_function` are used.
+    pub fn set_status_hook(
+        &mut self,
+        hook: impl Fn(&CppFunction) -> Result<Option<StatusSuccess>> + 'static,
+    ) {
+        assert!(self.status_hook.is_none(), "only one hook can be set");
+        self.status_hook = Some(Box::new(hook));
+    }
+
+    pub fn status_hook(&self) -> Option<&StatusHook> {
+        self.status_hook.as_deref()
+    }
+
+    /// Requests generation of a subclass wrapper for the polymorphic class `base_class`.
+    ///
+    /// The wrapper is a C++ class derived from `base_class` that forwards calls of
+    /// its virtual functions to Rust. The generated crate contains a trait
+    /// with a method for each overridable virtual function. An object implementing
+    /// this trait can be attached to an instance of the wrapper. Protected functions
+    /// of `base_class` are also exposed by the wrapper.
+    pub fn add_subclass_wrapper(&mut self, base_class: CppPath) {
+        self.subclass_wrappers.push(base_class);
+    }
+
+    pub fn subclass_wrappers(&self) -> &[CppPath] {
+        &self.subclass_wrappers
+    }
+
+    /// Sets a hook that declares thread safety of the C++ class `path`.
+    /// If the hook returns a value, `unsafe impl Send` and/or `unsafe impl Sync`
+    /// are generated for the Rust type of the class, allowing to pass `CppBox<T>`
+    /// of this type between threads. The rationale is included in the type's documentation.
+    ///
+    /// C++ classes are not thread-safe in general, so the generated types implement
+    /// neither `Send` nor `Sync` unless the hook returns a value.
+    /// It's the responsibility of the hook to only declare classes that are
+    /// actually safe to use in this way.
+    pub fn set_thread_safety_hook(
+        &mut self,
+        hook: impl Fn(&CppPath) -> Result<Option<ThreadSafety>> + 'static,
+    ) {
+        assert!(
+            self.thread_safety_hook.is_none(),
+            "only one hook can be set"
+        );
+        self.thread_safety_hook = Some(Box::new(hook));
+    }
+
+    pub fn thread_safety_hook(&self) -> Option<&ThreadSafetyHook> {
+        self.thread_safety_hook.as_deref()
+    }
+
     pub fn set_cluster_config(&mut self, cluster_config: ClusterConfig) {
         self.cluster_config = Some(cluster_config);
     }
@@ -23,9 +23,12 @@ fn create_cast_method(cast: CppCast, from: &CppType, to: &CppType) -> Result<Cpp
             name: "ptr".to_string(),
             argument_type: from.clone(),
             has_default_value: false,
+            type_alias: None,
         }],
         allows_variadic_arguments: false,
         declaration_code: None,
+        return_type_alias: None,
+        has_c_linkage: false,
         cast: Some(cast),
     };
     Ok(CppItem::Function(function))
@@ -1,10 +1,11 @@
 use crate::config::Config;
 use crate::cpp_checks::Condition;
 use crate::cpp_ffi_data::{
-    CppFfiArgumentMeaning, CppFfiFunctionKind, CppFfiType, CppFieldAccessorType,
-    CppToFfiTypeConversion, QtSignalWrapper, QtSlotWrapper,
+    CppFfiArgumentMeaning, CppFfiFunctionKind, CppFfiType, CppFfiVariadicArguments,
+    CppFieldAccessorType, CppFlagContainer, CppSubclassWrapper, CppToFfiTypeConversion,
+    CppVirtualFunctionOverride, QtSignalWrapper, QtSlotWrapper,
 };
-use crate::cpp_ffi_data::{CppFfiFunction, CppFfiItem};
+use crate::cpp_ffi_data::{CppFfiFunction, CppFfiItem, CppSmartPointerOperation};
 use crate::cpp_function::{CppFunction, ReturnValueAllocationPlace};
 use crate::cpp_type::CppPointerLikeTypeKind;
 use crate::cpp_type::CppType;
@@ -30,6 +31,9 @@ impl Generator<'_> {
         for arg in &method.arguments {
             arg_texts.push(arg.to_cpp_code()?);
         }
+        if method.variadic_arguments.is_some() {
+            arg_texts.push("...".to_string());
+        }
         let name_with_args = format!("{}({})", method.path.to_cpp_code()?, arg_texts.join(", "));
         let return_type = method.return_type.ffi_type();
         let r = if let CppType::FunctionPointer(..) = return_type {
@@ -96,6 +100,154 @@ impl Generator<'_> {
         ))
     }
 
+    /// Generates code for a subclass wrapper
+    fn subclass_wrapper(&self, wrapper: &CppSubclassWrapper) -> Result<String> {
+        let class_name = wrapper.class_path.to_cpp_code()?;
+        let base_class = wrapper.base_class.to_cpp_code()?;
+
+        let mut public_functions = Vec::new();
+        for constructor in &wrapper.constructors {
+            public_functions.push(format!(
+                "    {}({}) : {}({}) {{}}",
+                class_name,
+                wrapper_parameters(constructor)?,
+                base_class,
+                forwarded_arguments(constructor)
+            ));
+        }
+        for item in &wrapper.overrides {
+            let func_type = CppType::FunctionPointer(item.function_type.clone());
+            public_functions.push(format!(
+                "    void {}({}, void (*deleter)(void*), void* data) {{ m_{}.set(callback, deleter, data); }}",
+                item.setter_name(),
+                func_type.to_cpp_code(Some("callback"))?,
+                item.callback_name
+            ));
+            if item.has_base_function() {
+                public_functions.push(self.forwarding_function(
+                    &item.function,
+                    &item.base_function_name(),
+                    &base_class,
+                )?);
+            }
+        }
+        for function in &wrapper.protected_functions {
+            public_functions.push(self.forwarding_function(
+                function,
+                &function.path.last().name,
+                &base_class,
+            )?);
+        }
+
+        let overrides = wrapper
+            .overrides
+            .iter()
+            .map_if_ok(|item| self.virtual_function_override(item, &base_class))?;
+
+        let callbacks = wrapper.overrides.iter().map_if_ok(|item| -> Result<_> {
+            let func_type = CppType::FunctionPointer(item.function_type.clone());
+            Ok(format!(
+                "    ritual::Callback<{}> m_{};",
+                func_type.to_cpp_code(Some(""))?,
+                item.callback_name
+            ))
+        })?;
+
+        Ok(format!(
+            include_str!("../templates/c_lib/subclass_wrapper.h"),
+            class_name = class_name,
+            base_class = base_class,
+            public_functions = public_functions.join("\n"),
+            overrides = overrides.join("\n"),
+            callbacks = callbacks.join("\n"),
+        ))
+    }
+
+    /// Generates a method of a subclass wrapper that calls `function`
+    /// of the base class.
+    fn forwarding_function(
+        &self,
+        function: &CppFunction,
+        name: &str,
+        base_class: &str,
+    ) -> Result<String> {
+        let member = function
+            .member
+            .as_ref()
+            .ok_or_else(|| err_msg("class member expected"))?;
+        Ok(format!(
+            "    {static_text}{return_type} {name}({parameters}){const_text} {{ return {base_class}::{function_name}({arguments}); }}",
+            static_text = if member.is_static { "static " } else { "" },
+            return_type = function.return_type.to_cpp_code(None)?,
+            name = name,
+            parameters = wrapper_parameters(function)?,
+            const_text = if member.is_const { " const" } else { "" },
+            base_class = base_class,
+            function_name = function.path.last().name,
+            arguments = forwarded_arguments(function),
+        ))
+    }
+
+    /// Generates an override of a virtual function that calls the callback if it's set
+    /// and falls back to the base class implementation otherwise.
+    fn virtual_function_override(
+        &self,
+        item: &CppVirtualFunctionOverride,
+        base_class: &str,
+    ) -> Result<String> {
+        let function = &item.function;
+        let return_type = function.return_type.to_cpp_code(None)?;
+        let callback_args = once(format!("m_{}.data()", item.callback_name))
+            .chain(
+                item.arguments
+                    .iter()
+                    .enumerate()
+                    .map_if_ok(|(num, t)| self.convert_type_to_ffi(t, format!("arg{}", num)))?,
+            )
+            .join(", ");
+        let call = format!("callback({})", callback_args);
+        let call_code = if function.return_type.is_void() {
+            format!("{}; return;", call)
+        } else if let CppToFfiTypeConversion::ValueToPointer { .. } = item.return_type.conversion()
+        {
+            // the callback returns a pointer to a heap-allocated object,
+            // so it's copied and deleted here
+            format!(
+                "auto result = {}; {} value = *result; delete result; return value;",
+                call, return_type
+            )
+        } else {
+            format!(
+                "return {};",
+                self.convert_type_from_ffi(&item.return_type, call)?
+            )
+        };
+        let fallback_code = if item.has_base_function() {
+            format!(
+                "return {}::{}({});",
+                base_class,
+                function.path.last().name,
+                forwarded_arguments(function)
+            )
+        } else {
+            format!(
+                "ritual::missing_override(\"{}\");",
+                function.path.to_cpp_pseudo_code()
+            )
+        };
+        let is_const = function.member.as_ref().map_or(false, |m| m.is_const);
+        Ok(format!(
+            "    {return_type} {name}({parameters}){const_text} override {{\n        auto callback = m_{callback_name}.get();\n        if (callback) {{\n            {call_code}\n        }}\n        {fallback_code}\n    }}",
+            return_type = return_type,
+            name = function.path.last().name,
+            parameters = wrapper_parameters(function)?,
+            const_text = if is_const { " const" } else { "" },
+            callback_name = item.callback_name,
+            call_code = call_code,
+            fallback_code = fallback_code,
+        ))
+    }
+
     /// Generates code that wraps `expression` of type `type1.original_type` and
     /// converts it to type `type1.ffi_type`
     fn convert_type_to_ffi(&self, type1: &CppFfiType, expression: String) -> Result<String> {
@@ -108,8 +260,57 @@ impl Generator<'_> {
                 type1.original_type().to_cpp_code(None)?,
                 expression
             ),
-            CppToFfiTypeConversion::ReferenceToPointer => format!("&{}", expression),
-            CppToFfiTypeConversion::QFlagsToInt => format!("int({})", expression),
+            CppToFfiTypeConversion::ReferenceToPointer
+            | CppToFfiTypeConversion::RValueReferenceToPointer => format!("&{}", expression),
+            CppToFfiTypeConversion::FlagsToInt { container } => {
+                flags_to_int(container, &expression)
+            }
+            CppToFfiTypeConversion::FunctionToCallback => {
+                bail!("std::function can't be converted to a callback");
+            }
+        })
+    }
+
+    /// Generates code that converts `expression` of type `type1.ffi_type`
+    /// back to type `type1.original_type`.
+    fn convert_type_from_ffi(&self, type1: &CppFfiType, expression: String) -> Result<String> {
+        Ok(match type1.conversion() {
+            CppToFfiTypeConversion::ValueToPointer { .. }
+                if type1.original_type().is_unique_ptr() =>
+            {
+                // `std::unique_ptr` can't be copied, so the ownership is moved
+                // to the called function
+                format!("std::move(*{})", expression)
+            }
+            CppToFfiTypeConversion::ValueToPointer { .. }
+            | CppToFfiTypeConversion::ReferenceToPointer => format!("*{}", expression),
+            CppToFfiTypeConversion::RValueReferenceToPointer => {
+                format!("std::move(*{})", expression)
+            }
+            CppToFfiTypeConversion::NoChange | CppToFfiTypeConversion::ImplicitCast { .. } => {
+                expression
+            }
+            CppToFfiTypeConversion::FlagsToInt { container } => {
+                let type_text = if let CppType::PointerLike {
+                    kind,
+                    is_const,
+                    target,
+                } = type1.original_type()
+                {
+                    if *kind == CppPointerLikeTypeKind::Reference && *is_const {
+                        target.to_cpp_code(None)?
+                    } else {
+                        bail!("Unsupported original type for FlagsToInt conversion");
+                    }
+                } else {
+                    type1.original_type().to_cpp_code(None)?
+                };
+                let function = container.from_int_function.as_ref().unwrap_or(&type_text);
+                format!("{}({})", function, expression)
+            }
+            CppToFfiTypeConversion::FunctionToCallback => {
+                bail!("std::function requires the callback's data and deleter");
+            }
         })
     }
 
@@ -158,8 +359,14 @@ impl Generator<'_> {
             CppToFfiTypeConversion::ReferenceToPointer => {
                 result = format!("&{}", result);
             }
-            CppToFfiTypeConversion::QFlagsToInt => {
-                result = format!("int({})", result);
+            CppToFfiTypeConversion::RValueReferenceToPointer => {
+                bail!("rvalue references are not supported in return types");
+            }
+            CppToFfiTypeConversion::FlagsToInt { container } => {
+                result = flags_to_int(container, &result);
+            }
+            CppToFfiTypeConversion::FunctionToCallback => {
+                bail!("std::function can't be returned as a callback");
             }
         }
 
@@ -183,38 +390,60 @@ impl Generator<'_> {
             .iter()
             .filter(|arg| arg.meaning.is_argument())
             .map_if_ok(|argument| -> Result<_> {
-                let mut result = argument.name.clone();
-                match argument.argument_type.conversion() {
-                    CppToFfiTypeConversion::ValueToPointer { .. }
-                    | CppToFfiTypeConversion::ReferenceToPointer => result = format!("*{}", result),
-                    CppToFfiTypeConversion::NoChange
-                    | CppToFfiTypeConversion::ImplicitCast { .. } => {}
-                    CppToFfiTypeConversion::QFlagsToInt => {
-                        let type_text = if let CppType::PointerLike {
-                            kind,
-                            is_const,
-                            target,
-                        } = argument.argument_type.original_type()
-                        {
-                            if *kind == CppPointerLikeTypeKind::Reference && *is_const {
-                                target.to_cpp_code(None)?
-                            } else {
-                                bail!("Unsupported original type for QFlagsToUInt conversion");
-                            }
-                        } else {
-                            argument.argument_type.original_type().to_cpp_code(None)?
-                        };
-                        result = format!("{}({})", type_text, result);
-                    }
+                if argument.argument_type.conversion()
+                    == &CppToFfiTypeConversion::FunctionToCallback
+                {
+                    let find_name = |meaning: CppFfiArgumentMeaning| {
+                        method
+                            .arguments
+                            .iter()
+                            .find(|arg| arg.meaning == meaning)
+                            .map(|arg| arg.name.as_str())
+                            .ok_or_else(|| err_msg("callback data or deleter argument not found"))
+                    };
+                    let index = match argument.meaning {
+                        CppFfiArgumentMeaning::Argument(index) => index,
+                        _ => bail!("unexpected meaning of callback argument"),
+                    };
+                    Ok(format!(
+                        "ritual::make_function({}, {}, {})",
+                        argument.name,
+                        find_name(CppFfiArgumentMeaning::CallbackData(index))?,
+                        find_name(CppFfiArgumentMeaning::CallbackDeleter(index))?
+                    ))
+                } else {
+                    self.convert_type_from_ffi(&argument.argument_type, argument.name.clone())
                 }
-                Ok(result)
             })?;
         Ok(r.join(", "))
     }
 
+    /// Generates code for the value returned by an FFI function
+    /// calculating the hash of its argument with `std::hash`.
+    fn std_hash_expression(&self, function: &CppFfiFunction) -> Result<String> {
+        let value_type = function
+            .arguments
+            .get(0)
+            .ok_or_else(|| err_msg("std::hash function must have an argument"))?
+            .argument_type
+            .original_type()
+            .pointer_like_to_target()?;
+        Ok(format!(
+            "std::hash<{}>()({})",
+            value_type.to_cpp_code(None)?,
+            self.arguments_values(function)?
+        ))
+    }
+
     /// Generates code for the value returned by the FFI method.
     #[allow(clippy::collapsible_if)]
     fn returned_expression(&self, item: DbItem<&CppFfiFunction>) -> Result<String> {
+        if let CppFfiFunctionKind::SmartPointer { operation, .. } = &item.item.kind {
+            return smart_pointer_expression(item.item, *operation);
+        }
+        if item.item.kind == CppFfiFunctionKind::StdHash {
+            return self.std_hash_expression(item.item);
+        }
         let cpp_item = self
             .0
             .source_cpp_item(&item.id)?
@@ -266,7 +495,7 @@ impl Generator<'_> {
                 }
             } else {
                 let path = cpp_item.item.path().ok_or_else(|| {
-                    err_msg("cpp item (function or field) expected to have a path")
+                    err_msg("cpp item (function, field or variable) expected to have a path")
                 })?;
 
                 if let Some(arg) = method
@@ -279,22 +508,47 @@ impl Generator<'_> {
                     path.to_cpp_code()?
                 }
             };
-            if let CppFfiFunctionKind::FieldAccessor { accessor_type, .. } = &method.kind {
-                if accessor_type == &CppFieldAccessorType::Setter {
-                    format!(
-                        "{} = {}",
-                        result_without_args,
-                        self.arguments_values(method)?
-                    )
-                } else {
-                    result_without_args
+            match &method.kind {
+                CppFfiFunctionKind::FieldAccessor { accessor_type }
+                | CppFfiFunctionKind::VariableAccessor { accessor_type } => {
+                    if accessor_type == &CppFieldAccessorType::Setter {
+                        format!(
+                            "{} = {}",
+                            result_without_args,
+                            self.arguments_values(method)?
+                        )
+                    } else {
+                        result_without_args
+                    }
                 }
-            } else {
-                format!(
-                    "{}({})",
+                CppFfiFunctionKind::Function => {
+                    if let Some(CppFfiVariadicArguments::VaList { va_list_function }) =
+                        &method.variadic_arguments
+                    {
+                        format!(
+                            "{}({}, variadic_args)",
+                            va_list_function.to_cpp_code()?,
+                            self.arguments_values(method)?
+                        )
+                    } else {
+                        format!(
+                            "{}({})",
+                            result_without_args,
+                            self.arguments_values(method)?
+                        )
+                    }
+                }
+                CppFfiFunctionKind::StreamToString => format!(
+                    "ritual::write_to_string([&](std::ostream& stream) {{ {}(stream, {}); }})",
                     result_without_args,
                     self.arguments_values(method)?
-                )
+                ),
+                CppFfiFunctionKind::SmartPointer { .. } => {
+                    bail!("unexpected smart pointer function");
+                }
+                CppFfiFunctionKind::StdHash => {
+                    bail!("unexpected std::hash function");
+                }
             }
         };
         self.convert_return_type(item, result)
@@ -313,18 +567,18 @@ impl Generator<'_> {
             .map_or(false, |f| f.is_destructor());
 
         let method = item.item;
-        if is_destructor && method.allocation_place == ReturnValueAllocationPlace::Heap {
+        let body = if is_destructor && method.allocation_place == ReturnValueAllocationPlace::Heap {
             if let Some(arg) = method
                 .arguments
                 .iter()
                 .find(|x| x.meaning == CppFfiArgumentMeaning::This)
             {
-                Ok(format!("delete {};\n", arg.name))
+                format!("delete {};\n", arg.name)
             } else {
                 panic!("Error: no this argument found\n{:?}", method);
             }
         } else {
-            Ok(format!(
+            format!(
                 "{}{};\n",
                 if method.return_type.ffi_type().is_void() {
                     ""
@@ -332,12 +586,91 @@ impl Generator<'_> {
                     "return "
                 },
                 self.returned_expression(item)?
-            ))
+            )
+        };
+        let body = if let Some(arg) = method
+            .arguments
+            .iter()
+            .find(|x| x.meaning == CppFfiArgumentMeaning::Exception)
+        {
+            self.wrap_with_try_catch(method, &body, &arg.name)
+        } else {
+            body
+        };
+        if let Some(CppFfiVariadicArguments::VaList { .. }) = &method.variadic_arguments {
+            self.wrap_with_va_list(method, &body)
+        } else {
+            Ok(body)
         }
     }
 
+    /// Wraps `body` of the FFI method in a lambda called between `va_start` and `va_end`,
+    /// making variadic arguments available as `variadic_args`.
+    fn wrap_with_va_list(&self, method: &CppFfiFunction, body: &str) -> Result<String> {
+        let last_arg = method
+            .arguments
+            .last()
+            .ok_or_else(|| err_msg("variadic function must have a named argument"))?;
+        let return_type = method.return_type.ffi_type();
+        let return_type_code = if return_type.is_function_pointer() {
+            return_type.to_cpp_code(Some(""))?
+        } else {
+            return_type.to_cpp_code(None)?
+        };
+        let is_void = return_type.is_void();
+        Ok(format!(
+            "va_list variadic_args;\n  \
+             va_start(variadic_args, {last_arg});\n  \
+             {maybe_result}[&]() -> {return_type} {{\n  {body}  }}();\n  \
+             va_end(variadic_args);\n{maybe_return}",
+            last_arg = last_arg.name,
+            maybe_result = if is_void { "" } else { "auto ritual_result = " },
+            return_type = return_type_code,
+            body = body,
+            maybe_return = if is_void {
+                ""
+            } else {
+                "  return ritual_result;\n"
+            },
+        ))
+    }
+
+    /// Wraps `body` of the FFI method in a `try` block. Caught exceptions are reported
+    /// through the `exception_arg` output argument, and a value-initialized
+    /// return value is returned instead.
+    fn wrap_with_try_catch(
+        &self,
+        method: &CppFfiFunction,
+        body: &str,
+        exception_arg: &str,
+    ) -> String {
+        format!(
+            "*{arg} = nullptr;\n  \
+             try {{\n  {body}  }} catch (const std::exception& e) {{\n    \
+             ritual::set_exception({arg}, e.what());\n  \
+             }} catch (...) {{\n    \
+             ritual::set_exception({arg}, \"unknown C++ exception\");\n  \
+             }}\n{fallback}",
+            arg = exception_arg,
+            body = body,
+            fallback = if method.return_type.ffi_type().is_void() {
+                ""
+            } else {
+                "  return {};\n"
+            },
+        )
+    }
+
     /// Generates implementation of the FFI method for the source file.
     fn function_implementation(&self, method: DbItem<&CppFfiFunction>) -> Result<String> {
+        if method.item.variadic_arguments == Some(CppFfiVariadicArguments::DirectLinkage) {
+            // the original function has C linkage and is called directly,
+            // so it's only redeclared with the same linkage
+            return Ok(format!(
+                "extern \"C\" {};\n\n",
+                self.function_signature(method.item)?
+            ));
+        }
         Ok(format!(
             "RITUAL_EXPORT {} {{\n  {}}}\n\n",
             self.function_signature(method.item)?,
@@ -427,6 +760,15 @@ impl Generator<'_> {
                     let code = self.qt_signal_wrapper(qt_signal_wrapper)?;
                     write!(cpp_file, "{}", self.wrap_with_condition(&code, &condition))?;
                 }
+                CppFfiItem::SubclassWrapper(subclass_wrapper) => {
+                    let checks = self.0.cpp_checks(&ffi_item.id)?;
+                    if !checks.any_success() {
+                        continue;
+                    }
+                    let condition = checks.condition(self.0.environments());
+                    let code = self.subclass_wrapper(subclass_wrapper)?;
+                    write!(cpp_file, "{}", self.wrap_with_condition(&code, &condition))?;
+                }
                 _ => {}
             }
         }
@@ -509,6 +851,101 @@ pub fn qt_signal_wrapper(db: &DatabaseClient, wrapper: &QtSignalWrapper) -> Resu
     Generator(db).qt_signal_wrapper(wrapper)
 }
 
+pub fn subclass_wrapper(db: &DatabaseClient, wrapper: &CppSubclassWrapper) -> Result<String> {
+    Generator(db).subclass_wrapper(wrapper)
+}
+
+/// Generates code that converts `expression` of a flag container type to `int`.
+fn flags_to_int(container: &CppFlagContainer, expression: &str) -> String {
+    let function = container.to_int_function.as_deref().unwrap_or("int");
+    format!("{}({})", function, expression)
+}
+
+/// Generates code for the value returned by an FFI function performing
+/// `operation` on a smart pointer.
+fn smart_pointer_expression(
+    function: &CppFfiFunction,
+    operation: CppSmartPointerOperation,
+) -> Result<String> {
+    let find_argument = |meaning: CppFfiArgumentMeaning| {
+        function
+            .arguments
+            .iter()
+            .find(|arg| arg.meaning == meaning)
+            .ok_or_else(|| format_err!("argument not found: {:?}", meaning))
+    };
+    let this_name = || find_argument(CppFfiArgumentMeaning::This).map(|arg| &arg.name);
+    let this_type = || -> Result<String> {
+        let arg = find_argument(CppFfiArgumentMeaning::This)?;
+        arg.argument_type
+            .ffi_type()
+            .pointer_like_to_target()?
+            .to_cpp_code(None)
+    };
+    Ok(match operation {
+        CppSmartPointerOperation::Delete => format!("delete {}", this_name()?),
+        CppSmartPointerOperation::Copy => format!("new {}(*{})", this_type()?, this_name()?),
+        CppSmartPointerOperation::Get => format!("{}->get()", this_name()?),
+        CppSmartPointerOperation::UseCount => format!("{}->use_count()", this_name()?),
+        CppSmartPointerOperation::FromRaw => format!(
+            "new {}({})",
+            function
+                .return_type
+                .ffi_type()
+                .pointer_like_to_target()?
+                .to_cpp_code(None)?,
+            find_argument(CppFfiArgumentMeaning::Argument(0))?.name
+        ),
+        CppSmartPointerOperation::ToOwner => {
+            format!("new std::shared_ptr<void>(*{})", this_name()?)
+        }
+        CppSmartPointerOperation::Alias => format!(
+            "ritual::alias_shared_ptr({}, {})",
+            find_argument(CppFfiArgumentMeaning::Argument(0))?.name,
+            find_argument(CppFfiArgumentMeaning::Argument(1))?.name
+        ),
+        CppSmartPointerOperation::Release => format!("{}->release()", this_name()?),
+    })
+}
+
+/// Generates declarations of arguments of `function` named `arg0`, `arg1`, etc.
+fn wrapper_parameters(function: &CppFunction) -> Result<String> {
+    let parameters =
+        function
+            .arguments
+            .iter()
+            .enumerate()
+            .map_if_ok(|(num, arg)| -> Result<_> {
+                Ok(format!(
+                    "{} arg{}",
+                    arg.argument_type.to_cpp_code(None)?,
+                    num
+                ))
+            })?;
+    Ok(parameters.join(", "))
+}
+
+/// Generates values of arguments declared by `wrapper_parameters`
+/// for passing them to another function.
+fn forwarded_arguments(function: &CppFunction) -> String {
+    function
+        .arguments
+        .iter()
+        .enumerate()
+        .map(|(num, arg)| {
+            if let CppType::PointerLike {
+                kind: CppPointerLikeTypeKind::RValueReference,
+                ..
+            } = &arg.argument_type
+            {
+                format!("std::move(arg{})", num)
+            } else {
+                format!("arg{}", num)
+            }
+        })
+        .join(", ")
+}
+
 pub fn generate_cpp_file(
     db: &DatabaseClient,
     file_path: &Path,
@@ -2,7 +2,7 @@
 
 use crate::cpp_function::CppFunction;
 pub use crate::cpp_operator::CppOperator;
-use crate::cpp_type::{CppTemplateParameter, CppType};
+use crate::cpp_type::{std_function_type, CppTemplateParameter, CppType};
 use crate::database::DatabaseClient;
 use itertools::Itertools;
 use ritual_common::errors::{bail, ensure, Error, Result};
@@ -57,7 +57,6 @@ pub struct CppClassField {
     pub field_type: CppType,
     /// Visibility
     pub visibility: CppVisibility,
-    pub is_static: bool,
 }
 
 impl CppClassField {
@@ -65,7 +64,6 @@ impl CppClassField {
         self.path == other.path
             && self.field_type == other.field_type
             && self.visibility == other.visibility
-            && self.is_static == other.is_static
     }
 
     pub fn short_text(&self) -> String {
@@ -83,6 +81,109 @@ impl CppClassField {
     }
 }
 
+/// Global variable or static data member of a class
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct CppVariable {
+    pub path: CppPath,
+    /// Variable type
+    pub variable_type: CppType,
+    /// True if the variable is declared `const`
+    pub is_const: bool,
+    /// Visibility (always `Public` for variables outside of classes)
+    pub visibility: CppVisibility,
+}
+
+impl CppVariable {
+    pub fn is_same(&self, other: &CppVariable) -> bool {
+        self.path == other.path
+            && self.variable_type == other.variable_type
+            && self.is_const == other.is_const
+            && self.visibility == other.visibility
+    }
+
+    pub fn short_text(&self) -> String {
+        let visibility_text = match self.visibility {
+            CppVisibility::Public => "",
+            CppVisibility::Protected => "protected ",
+            CppVisibility::Private => "private ",
+        };
+        format!(
+            "{}{}{} {}",
+            visibility_text,
+            if self.is_const { "const " } else { "" },
+            self.variable_type.to_cpp_pseudo_code(),
+            self.path.to_cpp_pseudo_code(),
+        )
+    }
+}
+
+/// C++ `typedef` or `using` type alias declaration
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct CppTypeAlias {
+    pub path: CppPath,
+    /// Type referred to by the alias
+    pub target: CppType,
+}
+
+impl CppTypeAlias {
+    pub fn short_text(&self) -> String {
+        format!(
+            "using {} = {}",
+            self.path.to_cpp_pseudo_code(),
+            self.target.to_cpp_pseudo_code()
+        )
+    }
+}
+
+/// Value of a compile-time C++ constant
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub enum CppConstantValue {
+    Bool(bool),
+    Integer(i64),
+    /// Floating point value in a form that is valid in both C++ and Rust
+    FloatingPoint(String),
+    String(String),
+}
+
+/// Object-like macro or `constexpr`/`static const` variable
+/// that evaluates to a literal value
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct CppConstant {
+    pub path: CppPath,
+    /// Type of the constant (`const char*` for string literals)
+    pub constant_type: CppType,
+    pub value: CppConstantValue,
+    /// True if the constant is defined by a preprocessor macro
+    pub is_macro: bool,
+    /// Location of the definition
+    pub origin_location: CppOriginLocation,
+}
+
+impl CppConstant {
+    pub fn is_same(&self, other: &CppConstant) -> bool {
+        self.path == other.path && self.value == other.value
+    }
+
+    pub fn short_text(&self) -> String {
+        let value_text = match &self.value {
+            CppConstantValue::Bool(value) => value.to_string(),
+            CppConstantValue::Integer(value) => value.to_string(),
+            CppConstantValue::FloatingPoint(value) => value.clone(),
+            CppConstantValue::String(value) => format!("{:?}", value),
+        };
+        if self.is_macro {
+            format!("#define {} {}", self.path.to_cpp_pseudo_code(), value_text)
+        } else {
+            format!(
+                "constexpr {} {} = {}",
+                self.constant_type.to_cpp_pseudo_code(),
+                self.path.to_cpp_pseudo_code(),
+                value_text
+            )
+        }
+    }
+}
+
 /// Item of base class list in a class declaration
 #[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
 pub struct CppBaseSpecifier {
@@ -154,6 +255,15 @@ impl CppPath {
     }
 
     pub fn to_cpp_code(&self) -> Result<String> {
+        if let Some(function_type) = std_function_type(self) {
+            // the template argument is a function type, not a function pointer type
+            let parent = CppPath::from_items(self.items[..self.items.len() - 1].to_vec());
+            return Ok(format!(
+                "{}::function< {} >",
+                parent.to_cpp_code()?,
+                function_type.to_cpp_function_type_code()?
+            ));
+        }
         Ok(self
             .items
             .iter()
@@ -369,7 +479,10 @@ impl fmt::Debug for CppPathItem {
 /// Information about a C++ type declaration
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Hash)]
 pub enum CppTypeDeclarationKind {
-    Enum,
+    Enum {
+        /// Integer type used to represent values of the enum
+        underlying_type: CppType,
+    },
     Class,
 }
 
@@ -394,7 +507,7 @@ impl CppTypeDeclarationKind {
     }
 
     pub fn is_enum(&self) -> bool {
-        matches!(self, CppTypeDeclarationKind::Enum)
+        matches!(self, CppTypeDeclarationKind::Enum { .. })
     }
 }
 
@@ -412,6 +525,9 @@ pub enum CppItem {
     Function(CppFunction),
     ClassField(CppClassField),
     ClassBase(CppBaseSpecifier),
+    Variable(CppVariable),
+    TypeAlias(CppTypeAlias),
+    Constant(CppConstant),
 }
 
 impl CppItem {
@@ -461,6 +577,27 @@ impl CppItem {
                     false
                 }
             }
+            Variable(v) => {
+                if let Variable(v2) = &other {
+                    v.is_same(v2)
+                } else {
+                    false
+                }
+            }
+            TypeAlias(v) => {
+                if let TypeAlias(v2) = &other {
+                    v == v2
+                } else {
+                    false
+                }
+            }
+            Constant(v) => {
+                if let Constant(v2) = &other {
+                    v.is_same(v2)
+                } else {
+                    false
+                }
+            }
         }
     }
 
@@ -471,6 +608,9 @@ impl CppItem {
             CppItem::EnumValue(data) => &data.path,
             CppItem::Function(data) => &data.path,
             CppItem::ClassField(data) => &data.path,
+            CppItem::Variable(data) => &data.path,
+            CppItem::TypeAlias(data) => &data.path,
+            CppItem::Constant(data) => &data.path,
             CppItem::ClassBase(_) => return None,
         };
         Some(path)
@@ -479,7 +619,7 @@ impl CppItem {
     pub fn all_involved_types(&self) -> Vec<CppType> {
         match self {
             CppItem::Type(t) => match t.kind {
-                CppTypeDeclarationKind::Enum => vec![CppType::Enum {
+                CppTypeDeclarationKind::Enum { .. } => vec![CppType::Enum {
                     path: t.path.clone(),
                 }],
                 CppTypeDeclarationKind::Class { .. } => vec![CppType::Class(t.path.clone())],
@@ -501,6 +641,9 @@ impl CppItem {
                 CppType::Class(base.base_class_type.clone()),
                 CppType::Class(base.derived_class_type.clone()),
             ],
+            CppItem::Variable(variable) => vec![variable.variable_type.clone()],
+            CppItem::TypeAlias(alias) => vec![alias.target.clone()],
+            CppItem::Constant(constant) => vec![constant.constant_type.clone()],
         }
     }
 
@@ -525,6 +668,27 @@ impl CppItem {
             None
         }
     }
+    pub fn as_variable_ref(&self) -> Option<&CppVariable> {
+        if let CppItem::Variable(data) = self {
+            Some(data)
+        } else {
+            None
+        }
+    }
+    pub fn as_type_alias_ref(&self) -> Option<&CppTypeAlias> {
+        if let CppItem::TypeAlias(data) = self {
+            Some(data)
+        } else {
+            None
+        }
+    }
+    pub fn as_constant_ref(&self) -> Option<&CppConstant> {
+        if let CppItem::Constant(data) = self {
+            Some(data)
+        } else {
+            None
+        }
+    }
     pub fn as_enum_value_ref(&self) -> Option<&CppEnumValue> {
         if let CppItem::EnumValue(data) = self {
             Some(data)
@@ -561,6 +725,9 @@ impl CppItem {
             CppItem::EnumValue(value) => format!("enum value {}", value.path.to_cpp_pseudo_code()),
             CppItem::Function(value) => value.short_text(),
             CppItem::ClassField(value) => value.short_text(),
+            CppItem::Variable(value) => value.short_text(),
+            CppItem::TypeAlias(value) => value.short_text(),
+            CppItem::Constant(value) => value.short_text(),
             CppItem::ClassBase(_) => format!("{:?}", self),
         }
     }
@@ -573,7 +740,9 @@ impl fmt::Display for CppItem {
                 format!("namespace {}", namespace.path.to_cpp_pseudo_code())
             }
             CppItem::Type(type1) => match type1.kind {
-                CppTypeDeclarationKind::Enum => format!("enum {}", type1.path.to_cpp_pseudo_code()),
+                CppTypeDeclarationKind::Enum { .. } => {
+                    format!("enum {}", type1.path.to_cpp_pseudo_code())
+                }
                 CppTypeDeclarationKind::Class { .. } => {
                     format!("class {}", type1.path.to_cpp_pseudo_code())
                 }
@@ -585,6 +754,9 @@ impl fmt::Display for CppItem {
                 value.value
             ),
             CppItem::ClassField(field) => field.short_text(),
+            CppItem::Variable(variable) => variable.short_text(),
+            CppItem::TypeAlias(alias) => alias.short_text(),
+            CppItem::Constant(constant) => constant.short_text(),
             CppItem::ClassBase(class_base) => {
                 let virtual_text = if class_base.is_virtual {
                     "virtual "
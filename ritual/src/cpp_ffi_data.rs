@@ -1,10 +1,12 @@
 use crate::cpp_code_generator;
-use crate::cpp_data::CppPath;
-use crate::cpp_function::ReturnValueAllocationPlace;
-use crate::cpp_type::{CppBuiltInNumericType, CppFunctionPointerType, CppType};
+use crate::cpp_data::{CppPath, CppVisibility};
+use crate::cpp_function::{CppFunction, ReturnValueAllocationPlace};
+use crate::cpp_type::{
+    std_function_type, CppBuiltInNumericType, CppFunctionPointerType, CppSmartPointerKind, CppType,
+};
 use crate::database::DatabaseClient;
 use itertools::Itertools;
-use ritual_common::errors::{bail, Result};
+use ritual_common::errors::{bail, err_msg, Result};
 use serde_derive::{Deserialize, Serialize};
 
 /// Variation of a field accessor method
@@ -57,6 +59,56 @@ impl CppCast {
     }
 }
 
+/// Operation on a standard library smart pointer performed by an FFI function.
+/// These functions back the implementations of `cpp_core::CppSharedPtrTarget`
+/// and `cpp_core::CppUniquePtrTarget`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+pub enum CppSmartPointerOperation {
+    /// Deletes a heap-allocated smart pointer object
+    Delete,
+    /// Creates a heap-allocated copy of a `std::shared_ptr`
+    Copy,
+    /// Returns the stored raw pointer
+    Get,
+    /// Returns the number of `std::shared_ptr` objects sharing the ownership
+    UseCount,
+    /// Creates a heap-allocated smart pointer that takes ownership of a raw pointer
+    FromRaw,
+    /// Converts a `std::shared_ptr` to a heap-allocated `std::shared_ptr<void>`
+    /// sharing the ownership
+    ToOwner,
+    /// Creates a `std::shared_ptr` that shares the ownership of a `std::shared_ptr<void>`
+    /// (consuming it) but stores a different pointer
+    Alias,
+    /// Releases the ownership of the stored pointer of a `std::unique_ptr`
+    Release,
+}
+
+impl CppSmartPointerOperation {
+    /// Returns all operations required by the smart pointer of `kind`.
+    pub fn all(kind: CppSmartPointerKind) -> &'static [Self] {
+        use self::CppSmartPointerOperation::*;
+        match kind {
+            CppSmartPointerKind::Shared => &[Delete, Copy, Get, UseCount, FromRaw, ToOwner, Alias],
+            CppSmartPointerKind::Unique => &[Delete, Get, FromRaw, Release],
+        }
+    }
+
+    /// Returns a short name used in FFI function names.
+    pub fn name(self) -> &'static str {
+        match self {
+            CppSmartPointerOperation::Delete => "delete",
+            CppSmartPointerOperation::Copy => "copy",
+            CppSmartPointerOperation::Get => "get",
+            CppSmartPointerOperation::UseCount => "use_count",
+            CppSmartPointerOperation::FromRaw => "from_raw",
+            CppSmartPointerOperation::ToOwner => "to_owner",
+            CppSmartPointerOperation::Alias => "alias",
+            CppSmartPointerOperation::Release => "release",
+        }
+    }
+}
+
 /// Information about real nature of a C++ FFI method.
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 #[allow(clippy::large_enum_variant)]
@@ -69,6 +121,71 @@ pub enum CppFfiFunctionKind {
         /// Type of the accessor
         accessor_type: CppFieldAccessorType,
     },
+    /// This is a getter or setter for a global variable
+    /// or a static data member.
+    VariableAccessor {
+        /// Type of the accessor
+        accessor_type: CppFieldAccessorType,
+    },
+    /// This is a function that writes its argument to a `std::ostringstream`
+    /// using the original `operator<<(std::ostream&, const T&)` and returns
+    /// the resulting text allocated with `malloc`.
+    StreamToString,
+    /// This is an operation on a `std::shared_ptr` or `std::unique_ptr`
+    /// instantiation used by the smart pointer types of `cpp_core`.
+    SmartPointer {
+        kind: CppSmartPointerKind,
+        operation: CppSmartPointerOperation,
+    },
+    /// This is a function that returns the hash of its argument
+    /// calculated by `std::hash<T>`.
+    StdHash,
+}
+
+impl CppFfiFunctionKind {
+    pub fn is_smart_pointer(&self) -> bool {
+        matches!(self, CppFfiFunctionKind::SmartPointer { .. })
+    }
+}
+
+/// Information about a C++ class template that stores a combination
+/// of flags of an enum, like `QFlags<E>`.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub struct CppFlagContainer {
+    /// Path of the class template without template arguments
+    pub template_path: CppPath,
+    /// Name of the C++ function that converts a value of the container to `int`.
+    /// If `None`, the value is converted using `int(value)`.
+    pub to_int_function: Option<String>,
+    /// Name of the C++ function that converts an `int` to a value of the container.
+    /// If `None`, the value is constructed using `Container<E>(value)`.
+    pub from_int_function: Option<String>,
+}
+
+impl CppFlagContainer {
+    /// Returns description of Qt's `QFlags<E>`.
+    pub fn qflags() -> Self {
+        CppFlagContainer {
+            template_path: CppPath::from_good_str("QFlags"),
+            to_int_function: None,
+            from_int_function: None,
+        }
+    }
+
+    /// Returns true if `path` is an instantiation of this class template
+    /// with a single template argument.
+    pub fn matches(&self, path: &CppPath) -> bool {
+        let template_items = self.template_path.items();
+        let items = path.items();
+        items.len() == template_items.len()
+            && items[..items.len() - 1] == template_items[..template_items.len() - 1]
+            && path.last().name == self.template_path.last().name
+            && path
+                .last()
+                .template_arguments
+                .as_ref()
+                .map_or(false, |args| args.len() == 1)
+    }
 }
 
 /// Relation between original C++ method's argument value
@@ -83,11 +200,20 @@ pub enum CppToFfiTypeConversion {
     /// C++ argument is a reference (like QPoint&)
     /// and FFI argument is a pointer (like QPoint*)
     ReferenceToPointer,
-    /// C++ argument is QFlags<T>
-    /// and FFI argument is uint
-    QFlagsToInt,
+    /// C++ argument is an rvalue reference (like QPoint&&)
+    /// and FFI argument is a pointer (like QPoint*).
+    /// The pointed object is moved into the C++ method using `std::move`.
+    RValueReferenceToPointer,
+    /// C++ argument is a flag container (like `QFlags<T>`)
+    /// and FFI argument is int
+    FlagsToInt { container: CppFlagContainer },
     /// Implicit conversion is used.
     ImplicitCast { ffi_type: CppType },
+    /// C++ argument is a `std::function<R(Args...)>` (passed by value or by reference)
+    /// and FFI argument is a callback of type `R (*)(void*, Args...)`.
+    /// The `std::function` is constructed from the callback and the values of
+    /// the arguments with `CallbackData` and `CallbackDeleter` meanings.
+    FunctionToCallback,
 }
 
 /// Information that indicates how an FFI function argument
@@ -104,6 +230,18 @@ pub enum CppFfiArgumentMeaning {
     /// This argument receives pointer to the buffer where
     /// the return value should be transferred to using placement new.
     ReturnValue,
+    /// This argument receives pointer to the message of a C++ exception
+    /// caught in the FFI function, or null if no exception was thrown.
+    /// The message is allocated with `malloc`.
+    Exception,
+    /// This argument receives the user data passed to the callback
+    /// in the C++ method's argument with the specified index
+    /// (which is a `std::function`).
+    CallbackData(usize),
+    /// This argument receives the function that deletes the user data
+    /// of the callback in the C++ method's argument with the specified index.
+    /// It's called when the last copy of the `std::function` is destroyed.
+    CallbackDeleter(usize),
 }
 
 impl CppFfiArgumentMeaning {
@@ -141,6 +279,16 @@ impl CppFfiFunctionArgument {
     }
 }
 
+/// Method of passing variadic arguments of a C++ function through FFI
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub enum CppFfiVariadicArguments {
+    /// The wrapper collects variadic arguments into a `va_list`
+    /// and passes it to `va_list_function` (e.g. `vprintf` for `printf`).
+    VaList { va_list_function: CppPath },
+    /// The original C function is called directly without a wrapper.
+    DirectLinkage,
+}
+
 /// Information about arguments and return type of a FFI function
 /// with no final function name
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -159,6 +307,9 @@ pub struct CppFfiFunction {
     pub path: CppPath,
 
     pub kind: CppFfiFunctionKind,
+
+    /// Passing method of variadic arguments if the function accepts them
+    pub variadic_arguments: Option<CppFfiVariadicArguments>,
 }
 
 impl CppFfiFunction {
@@ -191,6 +342,19 @@ impl CppFfiFunction {
                     false
                 }
             }
+            CppFfiFunctionKind::VariableAccessor { accessor_type } => {
+                if let CppFfiFunctionKind::VariableAccessor {
+                    accessor_type: other_accessor_type,
+                } = &other.kind
+                {
+                    accessor_type == other_accessor_type
+                } else {
+                    false
+                }
+            }
+            CppFfiFunctionKind::StreamToString => other.kind == CppFfiFunctionKind::StreamToString,
+            CppFfiFunctionKind::SmartPointer { .. } => other.kind == self.kind,
+            CppFfiFunctionKind::StdHash => other.kind == CppFfiFunctionKind::StdHash,
         }
     }
 }
@@ -220,7 +384,8 @@ impl CppFfiType {
                 original_type,
                 conversion,
             }),
-            CppToFfiTypeConversion::ReferenceToPointer => {
+            CppToFfiTypeConversion::ReferenceToPointer
+            | CppToFfiTypeConversion::RValueReferenceToPointer => {
                 let target = original_type.pointer_like_to_target()?;
                 let is_const = original_type.pointer_like_is_const()?;
                 Ok(CppFfiType {
@@ -229,7 +394,7 @@ impl CppFfiType {
                     conversion,
                 })
             }
-            CppToFfiTypeConversion::QFlagsToInt => Ok(CppFfiType {
+            CppToFfiTypeConversion::FlagsToInt { .. } => Ok(CppFfiType {
                 ffi_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
                 original_type,
                 conversion,
@@ -239,6 +404,26 @@ impl CppFfiType {
                 ffi_type,
                 conversion,
             }),
+            CppToFfiTypeConversion::FunctionToCallback => {
+                let function_type = match &original_type {
+                    CppType::PointerLike { target, .. } => &**target,
+                    other => other,
+                };
+                let function_type = match function_type {
+                    CppType::Class(path) => std_function_type(path),
+                    _ => None,
+                }
+                .ok_or_else(|| err_msg("FunctionToCallback requires std::function type"))?;
+                let mut callback_type = function_type.clone();
+                callback_type
+                    .arguments
+                    .insert(0, CppType::new_pointer(false, CppType::Void));
+                Ok(CppFfiType {
+                    ffi_type: CppType::FunctionPointer(callback_type),
+                    original_type,
+                    conversion,
+                })
+            }
         }
     }
 
@@ -286,12 +471,66 @@ pub struct QtSlotWrapper {
     pub function_type: CppFunctionPointerType,
 }
 
+/// Information about a virtual function that can be overridden
+/// through a subclass wrapper
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CppVirtualFunctionOverride {
+    /// Original virtual function
+    pub function: CppFunction,
+    /// Identifier of the override, unique within the wrapper.
+    /// Used in names of the generated callback setter and base function accessor.
+    pub callback_name: String,
+    /// Arguments of the callback (without the data pointer).
+    pub arguments: Vec<CppFfiType>,
+    /// Return type of the callback.
+    pub return_type: CppFfiType,
+    /// The function pointer type accepted by the callback setter
+    pub function_type: CppFunctionPointerType,
+}
+
+impl CppVirtualFunctionOverride {
+    /// Name of the wrapper method that sets the callback.
+    pub fn setter_name(&self) -> String {
+        format!("set_{}_callback", self.callback_name)
+    }
+
+    /// Name of the wrapper method that calls the base class implementation.
+    pub fn base_function_name(&self) -> String {
+        format!("{}_base", self.callback_name)
+    }
+
+    /// Returns true if the base class implementation of the function
+    /// can be called from the subclass.
+    pub fn has_base_function(&self) -> bool {
+        self.function.member.as_ref().map_or(false, |m| {
+            !m.is_pure_virtual && m.visibility != CppVisibility::Private
+        })
+    }
+}
+
+/// Information about a generated C++ subclass that forwards
+/// virtual function calls to callbacks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CppSubclassWrapper {
+    /// Class being subclassed
+    pub base_class: CppPath,
+    /// Generated name of the wrapper class
+    pub class_path: CppPath,
+    /// Constructors of the base class available to the wrapper
+    pub constructors: Vec<CppFunction>,
+    /// Virtual functions that can be overridden
+    pub overrides: Vec<CppVirtualFunctionOverride>,
+    /// Protected non-virtual functions exposed by the wrapper
+    pub protected_functions: Vec<CppFunction>,
+}
+
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum CppFfiItem {
     Function(CppFfiFunction),
     QtSlotWrapper(QtSlotWrapper),
     QtSignalWrapper(QtSignalWrapper),
+    SubclassWrapper(CppSubclassWrapper),
 }
 
 impl CppFfiItem {
@@ -323,6 +562,14 @@ impl CppFfiItem {
         }
     }
 
+    pub fn as_subclass_wrapper_ref(&self) -> Option<&CppSubclassWrapper> {
+        if let CppFfiItem::SubclassWrapper(data) = self {
+            Some(data)
+        } else {
+            None
+        }
+    }
+
     pub fn is_slot_wrapper(&self) -> bool {
         matches!(self, CppFfiItem::QtSlotWrapper(_))
     }
@@ -331,6 +578,10 @@ impl CppFfiItem {
         matches!(self, CppFfiItem::QtSignalWrapper(_))
     }
 
+    pub fn is_subclass_wrapper(&self) -> bool {
+        matches!(self, CppFfiItem::SubclassWrapper(_))
+    }
+
     pub fn short_text(&self) -> String {
         match self {
             CppFfiItem::Function(function) => function.path.to_cpp_pseudo_code(),
@@ -350,6 +601,10 @@ impl CppFfiItem {
                     .map(CppType::to_cpp_pseudo_code)
                     .join(", ")
             ),
+            CppFfiItem::SubclassWrapper(wrapper) => format!(
+                "subclass wrapper for {}",
+                wrapper.base_class.to_cpp_pseudo_code()
+            ),
         }
     }
 
@@ -376,6 +631,13 @@ impl CppFfiItem {
                     false
                 }
             }
+            CppFfiItem::SubclassWrapper(wrapper) => {
+                if let CppFfiItem::SubclassWrapper(other_wrapper) = other {
+                    wrapper.base_class == other_wrapper.base_class
+                } else {
+                    false
+                }
+            }
         }
     }
 
@@ -384,13 +646,16 @@ impl CppFfiItem {
             CppFfiItem::Function(f) => &f.path,
             CppFfiItem::QtSlotWrapper(s) => &s.class_path,
             CppFfiItem::QtSignalWrapper(s) => &s.class_path,
+            CppFfiItem::SubclassWrapper(s) => &s.class_path,
         }
     }
 
     pub fn is_source_item(&self) -> bool {
         match self {
             CppFfiItem::Function(_) => false,
-            CppFfiItem::QtSlotWrapper(_) | CppFfiItem::QtSignalWrapper(_) => true,
+            CppFfiItem::QtSlotWrapper(_)
+            | CppFfiItem::QtSignalWrapper(_)
+            | CppFfiItem::SubclassWrapper(_) => true,
         }
     }
 
@@ -403,6 +668,9 @@ impl CppFfiItem {
             CppFfiItem::QtSignalWrapper(signal_wrapper) => {
                 cpp_code_generator::qt_signal_wrapper(db, signal_wrapper)
             }
+            CppFfiItem::SubclassWrapper(subclass_wrapper) => {
+                cpp_code_generator::subclass_wrapper(db, subclass_wrapper)
+            }
         }
     }
 }
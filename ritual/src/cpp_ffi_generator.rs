@@ -1,70 +1,64 @@
+use crate::config::Config;
 use crate::cpp_data::CppClassField;
 use crate::cpp_data::CppItem;
 use crate::cpp_data::CppPath;
 use crate::cpp_data::CppPathItem;
+use crate::cpp_data::CppVariable;
 use crate::cpp_data::CppVisibility;
-use crate::cpp_ffi_data::CppFfiType;
 use crate::cpp_ffi_data::{CppFfiArgumentMeaning, CppToFfiTypeConversion};
 use crate::cpp_ffi_data::{CppFfiFunction, CppFfiFunctionKind, CppFieldAccessorType};
-use crate::cpp_ffi_data::{CppFfiFunctionArgument, CppFfiItem};
+use crate::cpp_ffi_data::{CppFfiFunctionArgument, CppFfiItem, CppFfiVariadicArguments};
+use crate::cpp_ffi_data::{CppFfiType, CppFlagContainer, CppSmartPointerOperation};
 use crate::cpp_function::ReturnValueAllocationPlace;
-use crate::cpp_function::{CppFunction, CppFunctionArgument, CppFunctionKind};
+use crate::cpp_function::{CppFunction, CppFunctionArgument, CppFunctionKind, CppOperator};
+use crate::cpp_type::CppFunctionPointerType;
 use crate::cpp_type::CppPointerLikeTypeKind;
-use crate::cpp_type::CppType;
 use crate::cpp_type::CppTypeRole;
-use crate::cpp_type::{is_qflags, CppFunctionPointerType};
-use crate::database::DbItem;
+use crate::cpp_type::{smart_pointer_type, CppSmartPointerKind};
+use crate::cpp_type::{std_function_type, CppBuiltInNumericType, CppType};
+use crate::database::{DatabaseClient, DbItem};
 use crate::processor::ProcessorData;
 use itertools::Itertools;
 use log::{debug, trace};
 use ritual_common::errors::{bail, Result, ResultExt};
 use std::collections::HashSet;
+use std::iter::once;
 
 /// Converts this C++ type to its adaptation for FFI interface,
 /// removing all features not supported by C ABI
 /// (e.g. references and passing objects by value).
 #[allow(clippy::collapsible_if)]
-pub fn ffi_type(original_type: &CppType, role: CppTypeRole) -> Result<CppFfiType> {
+pub fn ffi_type(
+    original_type: &CppType,
+    role: CppTypeRole,
+    flag_containers: &[CppFlagContainer],
+) -> Result<CppFfiType> {
+    let find_flag_container = |path: &CppPath| {
+        flag_containers
+            .iter()
+            .find(|container| container.matches(path))
+            .cloned()
+    };
     let inner = || -> Result<CppFfiType> {
         if original_type.is_or_contains_template_parameter() {
             bail!("template parameters cannot be expressed in FFI");
         }
         let conversion = match original_type {
-            CppType::FunctionPointer(CppFunctionPointerType {
-                return_type,
-                arguments,
-                allows_variadic_arguments,
-            }) => {
-                if *allows_variadic_arguments {
-                    bail!("function pointers with variadic arguments are not supported");
-                }
-                let mut all_types = arguments.iter().collect_vec();
-                all_types.push(return_type.as_ref());
-                for arg in all_types {
-                    match *arg {
-                        CppType::FunctionPointer(..) => {
-                            bail!(
-                                "function pointers containing nested function pointers are \
-                                 not supported"
-                            );
-                        }
-                        CppType::Class(..) => {
-                            bail!(
-                                "Function pointers containing classes by value are not \
-                                 supported"
-                            );
-                        }
-                        _ => {}
-                    }
-                    if arg.contains_reference() {
-                        bail!("Function pointers containing references are not supported");
-                    }
-                }
+            CppType::FunctionPointer(function_type) => {
+                check_callback_type(function_type)?;
                 CppToFfiTypeConversion::NoChange
             }
+            CppType::Class(_) if original_type.is_va_list() => {
+                bail!("va_list arguments are not supported");
+            }
             CppType::Class(path) => {
-                if is_qflags(&path) {
-                    CppToFfiTypeConversion::QFlagsToInt
+                if let Some(function_type) =
+                    std_function_type(path).filter(|_| role != CppTypeRole::ReturnType)
+                {
+                    check_callback_type(function_type)?;
+                    CppToFfiTypeConversion::FunctionToCallback
+                } else if let Some(container) = find_flag_container(path) {
+                    CppToFfiTypeConversion::FlagsToInt { container }
                 } else {
                     CppToFfiTypeConversion::ValueToPointer {
                         is_ffi_const: role != CppTypeRole::ReturnType,
@@ -80,18 +74,43 @@ pub fn ffi_type(original_type: &CppType, role: CppTypeRole) -> Result<CppFfiType
                     CppPointerLikeTypeKind::Pointer => CppToFfiTypeConversion::NoChange,
                     CppPointerLikeTypeKind::Reference => {
                         match &**target {
-                            CppType::Class(path) if *is_const && is_qflags(path) => {
-                                // TODO: use a separate conversion type (QFlagsConstRefToUInt)?
-                                CppToFfiTypeConversion::QFlagsToInt
+                            CppType::Class(path) if *is_const => {
+                                if let Some(function_type) = std_function_type(path)
+                                    .filter(|_| role != CppTypeRole::ReturnType)
+                                {
+                                    check_callback_type(function_type)?;
+                                    CppToFfiTypeConversion::FunctionToCallback
+                                } else if let Some(container) = find_flag_container(path) {
+                                    // TODO: use a separate conversion type (FlagsConstRefToInt)?
+                                    CppToFfiTypeConversion::FlagsToInt { container }
+                                } else {
+                                    CppToFfiTypeConversion::ReferenceToPointer
+                                }
                             }
                             _ => CppToFfiTypeConversion::ReferenceToPointer,
                         }
                     }
                     CppPointerLikeTypeKind::RValueReference => {
-                        bail!("rvalue references are not supported");
+                        if role == CppTypeRole::ReturnType {
+                            bail!("rvalue references are not supported in return types");
+                        }
+                        match &**target {
+                            CppType::Class(path) => {
+                                if let Some(function_type) = std_function_type(path) {
+                                    check_callback_type(function_type)?;
+                                    CppToFfiTypeConversion::FunctionToCallback
+                                } else {
+                                    CppToFfiTypeConversion::RValueReferenceToPointer
+                                }
+                            }
+                            _ => CppToFfiTypeConversion::RValueReferenceToPointer,
+                        }
                     }
                 }
             }
+            CppType::TemplateArgumentValue(_) => {
+                bail!("template argument values cannot be expressed in FFI");
+            }
             _ => CppToFfiTypeConversion::NoChange,
         };
         CppFfiType::new(original_type.clone(), conversion)
@@ -99,6 +118,32 @@ pub fn ffi_type(original_type: &CppType, role: CppTypeRole) -> Result<CppFfiType
     Ok(inner().with_context(|_| format!("Can't express type to FFI: {:?}", original_type))?)
 }
 
+/// Checks that a callback of `function_type` can be passed through FFI.
+fn check_callback_type(function_type: &CppFunctionPointerType) -> Result<()> {
+    if function_type.allows_variadic_arguments && function_type.arguments.is_empty() {
+        bail!("variadic function pointers without named arguments are not supported");
+    }
+    let all_types = function_type
+        .arguments
+        .iter()
+        .chain(once(function_type.return_type.as_ref()));
+    for arg in all_types {
+        match *arg {
+            CppType::FunctionPointer(..) => {
+                bail!("function pointers containing nested function pointers are not supported");
+            }
+            CppType::Class(..) => {
+                bail!("Function pointers containing classes by value are not supported");
+            }
+            _ => {}
+        }
+        if arg.contains_reference() {
+            bail!("Function pointers containing references are not supported");
+        }
+    }
+    Ok(())
+}
+
 pub struct FfiNameProvider {
     names: HashSet<String>,
     prefix: String,
@@ -147,6 +192,7 @@ impl FfiNameProvider {
 pub fn run(data: &mut ProcessorData<'_>) -> Result<()> {
     // movable types are not supported for now
     let movable_types = Vec::new();
+    let flag_containers = data.config.flag_containers();
 
     let mut name_provider = FfiNameProvider::new(data);
 
@@ -162,18 +208,32 @@ pub fn run(data: &mut ProcessorData<'_>) -> Result<()> {
             continue;
         }
         let result = match &item.item {
-            CppItem::Function(method) => {
-                generate_ffi_methods_for_method(method, &movable_types, &mut name_provider)
-                    .map(|v| v.into_iter().collect_vec())
-            }
+            CppItem::Function(method) => generate_ffi_methods_for_method(
+                method,
+                find_va_list_function(data.db, method),
+                &movable_types,
+                flag_containers,
+                &mut name_provider,
+                data.config,
+            )
+            .map(|v| v.into_iter().collect_vec()),
             CppItem::ClassField(field) => {
-                generate_field_accessors(field, &movable_types, &mut name_provider)
+                generate_field_accessors(field, &movable_types, flag_containers, &mut name_provider)
                     .map(|v| v.into_iter().collect_vec())
             }
+            CppItem::Variable(variable) => generate_variable_accessors(
+                variable,
+                &movable_types,
+                flag_containers,
+                &mut name_provider,
+            )
+            .map(|v| v.into_iter().collect_vec()),
             CppItem::ClassBase(_)
             | CppItem::Type(_)
             | CppItem::EnumValue(_)
-            | CppItem::Namespace(_) => {
+            | CppItem::Namespace(_)
+            | CppItem::TypeAlias(_)
+            | CppItem::Constant(_) => {
                 // no FFI methods for these items
                 continue;
             }
@@ -194,31 +254,384 @@ pub fn run(data: &mut ProcessorData<'_>) -> Result<()> {
             }
         }
     }
+    generate_smart_pointer_functions(data, &mut name_provider)?;
+    if data.config.std_hash() {
+        generate_std_hash_functions(data, &mut name_provider)?;
+    }
+    Ok(())
+}
+
+/// Generates FFI functions calculating hashes of values of class types
+/// of the current crate with `std::hash`. The functions for types
+/// that don't have a `std::hash` specialization are removed by the C++ checker.
+fn generate_std_hash_functions(
+    data: &mut ProcessorData<'_>,
+    name_provider: &mut FfiNameProvider,
+) -> Result<()> {
+    let class_types = data
+        .db
+        .cpp_items()
+        .filter_map(|item| {
+            let declaration = item.item.as_type_ref()?;
+            let class_type = CppType::Class(declaration.path.clone());
+            if declaration.kind.is_class() && !class_type.is_or_contains_template_parameter() {
+                Some((item.id, class_type))
+            } else {
+                None
+            }
+        })
+        .collect_vec();
+
+    for (source_id, class_type) in class_types {
+        let function = CppFfiFunction {
+            arguments: vec![CppFfiFunctionArgument {
+                name: "value".to_string(),
+                argument_type: ffi_type(
+                    &CppType::new_reference(true, class_type.clone()),
+                    CppTypeRole::NotReturnType,
+                    data.config.flag_containers(),
+                )?,
+                meaning: CppFfiArgumentMeaning::Argument(0),
+            }],
+            return_type: CppFfiType::new(
+                CppType::PointerSizedInteger {
+                    path: CppPath::from_good_str("size_t"),
+                    is_signed: false,
+                },
+                CppToFfiTypeConversion::NoChange,
+            )?,
+            allocation_place: ReturnValueAllocationPlace::NotApplicable,
+            path: name_provider.create_path(&format!("{}_std_hash", class_type.ascii_caption())),
+            kind: CppFfiFunctionKind::StdHash,
+            variadic_arguments: None,
+        };
+        data.db
+            .add_ffi_item(Some(source_id), CppFfiItem::Function(function))?;
+    }
+    Ok(())
+}
+
+/// Generates FFI functions implementing operations on `std::shared_ptr` and
+/// `std::unique_ptr` instantiations used in signatures of the generated FFI functions.
+/// The functions are only generated for target classes declared in the current crate
+/// because the corresponding `cpp_core` traits can only be implemented there.
+fn generate_smart_pointer_functions(
+    data: &mut ProcessorData<'_>,
+    name_provider: &mut FfiNameProvider,
+) -> Result<()> {
+    let mut processed = HashSet::new();
+    let mut pointer_types = Vec::new();
+    // smart pointer functions generated earlier come first,
+    // so each target only gets one set of functions
+    let ffi_functions = data
+        .db
+        .ffi_items()
+        .filter_map(|item| item.item.as_function_ref())
+        .sorted_by_key(|function| !function.kind.is_smart_pointer());
+    for function in ffi_functions {
+        let all_types = function
+            .arguments
+            .iter()
+            .map(|arg| &arg.argument_type)
+            .chain(once(&function.return_type));
+        for ffi_type in all_types {
+            let pointer_type = match ffi_type.original_type() {
+                CppType::PointerLike { target, .. } => &**target,
+                other => other,
+            };
+            if let CppType::Class(path) = pointer_type {
+                if let Some((kind, target)) = smart_pointer_type(path) {
+                    if processed.insert((kind, target.clone())) && !function.kind.is_smart_pointer()
+                    {
+                        pointer_types.push((kind, pointer_type.clone(), target.clone()));
+                    }
+                }
+            }
+        }
+    }
+
+    for (kind, pointer_type, target) in pointer_types {
+        let source_id = data
+            .db
+            .cpp_items()
+            .find(|item| {
+                item.item
+                    .as_type_ref()
+                    .map_or(false, |t| CppType::Class(t.path.clone()) == target)
+            })
+            .map(|item| item.id);
+        let source_id = if let Some(id) = source_id {
+            id
+        } else {
+            trace!(
+                "skipping smart pointer functions for {}: target type is not declared \
+                 in the current crate",
+                pointer_type.to_cpp_pseudo_code()
+            );
+            continue;
+        };
+        for &operation in CppSmartPointerOperation::all(kind) {
+            let function = generate_smart_pointer_function(
+                kind,
+                operation,
+                &pointer_type,
+                &target,
+                name_provider,
+            )?;
+            data.db
+                .add_ffi_item(Some(source_id.clone()), CppFfiItem::Function(function))?;
+        }
+    }
     Ok(())
 }
 
+fn smart_pointer_argument(
+    name: &str,
+    argument_type: CppType,
+    meaning: CppFfiArgumentMeaning,
+) -> Result<CppFfiFunctionArgument> {
+    Ok(CppFfiFunctionArgument {
+        name: name.to_string(),
+        argument_type: CppFfiType::new(argument_type, CppToFfiTypeConversion::NoChange)?,
+        meaning,
+    })
+}
+
+/// Generates an FFI function performing `operation` on `pointer_type`
+/// (an instantiation of a smart pointer of `kind` pointing to `target`).
+fn generate_smart_pointer_function(
+    kind: CppSmartPointerKind,
+    operation: CppSmartPointerOperation,
+    pointer_type: &CppType,
+    target: &CppType,
+    name_provider: &mut FfiNameProvider,
+) -> Result<CppFfiFunction> {
+    let this_argument = |is_const: bool| {
+        smart_pointer_argument(
+            "ptr",
+            CppType::new_pointer(is_const, pointer_type.clone()),
+            CppFfiArgumentMeaning::This,
+        )
+    };
+    let raw_argument = |index: usize| {
+        smart_pointer_argument(
+            "raw",
+            CppType::new_pointer(false, target.clone()),
+            CppFfiArgumentMeaning::Argument(index),
+        )
+    };
+    let void_pointer = CppType::new_pointer(false, CppType::Void);
+    let new_pointer = CppType::new_pointer(false, pointer_type.clone());
+    let raw_pointer = CppType::new_pointer(false, target.clone());
+
+    let (arguments, return_type) = match operation {
+        CppSmartPointerOperation::Delete => (vec![this_argument(false)?], CppType::Void),
+        CppSmartPointerOperation::Copy => (vec![this_argument(true)?], new_pointer),
+        CppSmartPointerOperation::Get => (vec![this_argument(true)?], raw_pointer),
+        CppSmartPointerOperation::UseCount => (
+            vec![this_argument(true)?],
+            CppType::BuiltInNumeric(CppBuiltInNumericType::Long),
+        ),
+        CppSmartPointerOperation::FromRaw => (vec![raw_argument(0)?], new_pointer),
+        CppSmartPointerOperation::ToOwner => (vec![this_argument(true)?], void_pointer),
+        CppSmartPointerOperation::Alias => (
+            vec![
+                smart_pointer_argument("owner", void_pointer, CppFfiArgumentMeaning::Argument(0))?,
+                raw_argument(1)?,
+            ],
+            new_pointer,
+        ),
+        CppSmartPointerOperation::Release => (vec![this_argument(false)?], raw_pointer),
+    };
+    Ok(CppFfiFunction {
+        arguments,
+        return_type: CppFfiType::new(return_type, CppToFfiTypeConversion::NoChange)?,
+        allocation_place: ReturnValueAllocationPlace::NotApplicable,
+        path: name_provider.create_path(&format!(
+            "{}_{}",
+            pointer_type.ascii_caption(),
+            operation.name()
+        )),
+        kind: CppFfiFunctionKind::SmartPointer { kind, operation },
+        variadic_arguments: None,
+    })
+}
+
 fn generate_ffi_methods_for_method(
     method: &CppFunction,
+    va_list_function: Option<CppPath>,
     movable_types: &[CppPath],
+    flag_containers: &[CppFlagContainer],
     name_provider: &mut FfiNameProvider,
+    config: &Config,
 ) -> Result<Vec<CppFfiItem>> {
-    Ok(vec![CppFfiItem::Function(to_ffi_method(
+    if is_stream_output_operator(method) {
+        // `std::ostream` is not usable from Rust, so the operator is only
+        // exposed through a wrapper that writes to a string
+        let ffi_function = generate_stream_to_string(method, flag_containers, name_provider)?;
+        return Ok(vec![CppFfiItem::Function(ffi_function)]);
+    }
+    let mut ffi_function = to_ffi_method(
         NewFfiFunctionKind::Function {
             cpp_function: method.clone(),
+            va_list_function,
         },
         movable_types,
+        flag_containers,
         name_provider,
-    )?)])
+    )?;
+    // there is no wrapper that could catch exceptions if the original function is called
+    // directly, and the exception argument can't follow the named arguments of a `va_list`
+    // wrapper because they are used by `va_start` and the FFI function is re-exported as is
+    if ffi_function.variadic_arguments.is_none() && should_catch_exceptions(method, config)? {
+        add_exception_argument(&mut ffi_function)?;
+    }
+    Ok(vec![CppFfiItem::Function(ffi_function)])
+}
+
+/// Returns true if `path` is `std::ostream` or the equivalent `std::basic_ostream<char>`.
+fn is_std_ostream(path: &CppPath) -> bool {
+    if path.items()[0].name != "std" {
+        return false;
+    }
+    let last = path.last();
+    match last.name.as_str() {
+        "ostream" => last.template_arguments.is_none(),
+        "basic_ostream" => last.template_arguments.as_ref().map_or(false, |args| {
+            args.get(0) == Some(&CppType::BuiltInNumeric(CppBuiltInNumericType::Char))
+        }),
+        _ => false,
+    }
+}
+
+/// Returns true if `function` is a free `operator<<(std::ostream&, const T&)`
+/// where `T` is a class type.
+pub fn is_stream_output_operator(function: &CppFunction) -> bool {
+    if function.member.is_some()
+        || function.operator != Some(CppOperator::BitwiseLeftShift)
+        || function.arguments.len() != 2
+    {
+        return false;
+    }
+    let is_stream = match &function.arguments[0].argument_type {
+        CppType::PointerLike {
+            kind: CppPointerLikeTypeKind::Reference,
+            is_const: false,
+            target,
+        } => matches!(&**target, CppType::Class(path) if is_std_ostream(path)),
+        _ => false,
+    };
+    let value_type = match &function.arguments[1].argument_type {
+        CppType::PointerLike {
+            kind: CppPointerLikeTypeKind::Reference,
+            is_const: true,
+            target,
+        } => &**target,
+        other => other,
+    };
+    is_stream && value_type.is_class()
+}
+
+/// Generates an FFI function that writes its argument using the stream output
+/// operator `function` and returns the resulting text.
+fn generate_stream_to_string(
+    function: &CppFunction,
+    flag_containers: &[CppFlagContainer],
+    name_provider: &mut FfiNameProvider,
+) -> Result<CppFfiFunction> {
+    let text_type =
+        CppType::new_pointer(false, CppType::BuiltInNumeric(CppBuiltInNumericType::Char));
+    Ok(CppFfiFunction {
+        arguments: vec![CppFfiFunctionArgument {
+            name: "value".to_string(),
+            argument_type: ffi_type(
+                &function.arguments[1].argument_type,
+                CppTypeRole::NotReturnType,
+                flag_containers,
+            )?,
+            meaning: CppFfiArgumentMeaning::Argument(1),
+        }],
+        return_type: ffi_type(&text_type, CppTypeRole::ReturnType, flag_containers)?,
+        allocation_place: ReturnValueAllocationPlace::NotApplicable,
+        path: name_provider.create_path(&format!("{}_to_string", function.path.ascii_caption())),
+        kind: CppFfiFunctionKind::StreamToString,
+        variadic_arguments: None,
+    })
+}
+
+/// Finds a function that accepts a `va_list` in place of variadic arguments
+/// of `function` (e.g. `vprintf` for `printf`).
+fn find_va_list_function(db: &DatabaseClient, function: &CppFunction) -> Option<CppPath> {
+    if !function.allows_variadic_arguments {
+        return None;
+    }
+    let mut path = function.path.clone();
+    path.last_mut().name = format!("v{}", function.path.last().name);
+    db.all_cpp_items()
+        .filter_map(|item| item.item.as_function_ref())
+        .find(|other| {
+            other.path == path
+                && !other.allows_variadic_arguments
+                && other.return_type == function.return_type
+                && other.arguments.len() == function.arguments.len() + 1
+                && other
+                    .arguments
+                    .last()
+                    .map_or(false, |arg| arg.argument_type.is_va_list())
+                && other
+                    .arguments
+                    .iter()
+                    .zip(&function.arguments)
+                    .all(|(a, b)| a.argument_type == b.argument_type)
+        })
+        .map(|other| other.path.clone())
+}
+
+/// Checks if the FFI wrapper of `function` should catch C++ exceptions.
+fn should_catch_exceptions(function: &CppFunction, config: &Config) -> Result<bool> {
+    // destructors and casts are not expected to throw, and their wrappers
+    // must keep the return type expected by `CppDeletable` and cast traits
+    if function.is_destructor() || function.cast.is_some() {
+        return Ok(false);
+    }
+    if let Some(hook) = config.catch_exceptions_hook() {
+        if let Some(value) = hook(function)? {
+            return Ok(value);
+        }
+    }
+    Ok(config.catch_exceptions())
+}
+
+/// Adds an output argument that receives the message of the caught C++ exception.
+pub fn add_exception_argument(function: &mut CppFfiFunction) -> Result<()> {
+    let message_type =
+        CppType::new_pointer(false, CppType::BuiltInNumeric(CppBuiltInNumericType::Char));
+    function.arguments.push(CppFfiFunctionArgument {
+        name: "exception".to_string(),
+        argument_type: CppFfiType::new(
+            CppType::new_pointer(false, message_type),
+            CppToFfiTypeConversion::NoChange,
+        )?,
+        meaning: CppFfiArgumentMeaning::Exception,
+    });
+    Ok(())
 }
 
 pub enum NewFfiFunctionKind {
     Function {
         cpp_function: CppFunction,
+        /// Function accepting a `va_list` that should be called
+        /// if `cpp_function` is variadic
+        va_list_function: Option<CppPath>,
     },
     FieldAccessor {
         accessor_type: CppFieldAccessorType,
         field: CppClassField,
     },
+    VariableAccessor {
+        accessor_type: CppFieldAccessorType,
+        variable: CppVariable,
+    },
 }
 
 /// Creates FFI function signature for this function:
@@ -229,15 +642,20 @@ pub enum NewFfiFunctionKind {
 pub fn to_ffi_method(
     kind: NewFfiFunctionKind,
     movable_types: &[CppPath],
+    flag_containers: &[CppFlagContainer],
     name_provider: &mut FfiNameProvider,
 ) -> Result<CppFfiFunction> {
     let ascii_caption = match &kind {
         NewFfiFunctionKind::Function { cpp_function, .. } => cpp_function.path.ascii_caption(),
         NewFfiFunctionKind::FieldAccessor {
-            field,
+            field: CppClassField { path, .. },
+            accessor_type,
+        }
+        | NewFfiFunctionKind::VariableAccessor {
+            variable: CppVariable { path, .. },
             accessor_type,
         } => {
-            let field_caption = field.path.ascii_caption();
+            let field_caption = path.ascii_caption();
             match *accessor_type {
                 CppFieldAccessorType::CopyGetter | CppFieldAccessorType::ConstRefGetter => {
                     field_caption
@@ -253,11 +671,15 @@ pub fn to_ffi_method(
         return_type: CppFfiType::void(),
         path: name_provider.create_path(&ascii_caption),
         allocation_place: ReturnValueAllocationPlace::NotApplicable,
+        variadic_arguments: None,
         kind: match kind {
             NewFfiFunctionKind::Function { .. } => CppFfiFunctionKind::Function,
             NewFfiFunctionKind::FieldAccessor { accessor_type, .. } => {
                 CppFfiFunctionKind::FieldAccessor { accessor_type }
             }
+            NewFfiFunctionKind::VariableAccessor { accessor_type, .. } => {
+                CppFfiFunctionKind::VariableAccessor { accessor_type }
+            }
         },
     };
 
@@ -273,23 +695,20 @@ pub fn to_ffi_method(
             field,
             accessor_type,
         } => {
-            if field.is_static {
-                None
-            } else {
-                let class_type = CppType::Class(field.path.parent()?);
-                let is_const = match *accessor_type {
-                    CppFieldAccessorType::CopyGetter | CppFieldAccessorType::ConstRefGetter => true,
-                    CppFieldAccessorType::MutRefGetter | CppFieldAccessorType::Setter => false,
-                };
-                Some(CppType::new_pointer(is_const, class_type))
-            }
+            let class_type = CppType::Class(field.path.parent()?);
+            let is_const = match *accessor_type {
+                CppFieldAccessorType::CopyGetter | CppFieldAccessorType::ConstRefGetter => true,
+                CppFieldAccessorType::MutRefGetter | CppFieldAccessorType::Setter => false,
+            };
+            Some(CppType::new_pointer(is_const, class_type))
         }
+        NewFfiFunctionKind::VariableAccessor { .. } => None,
     };
 
     if let Some(this_arg_type) = this_arg_type {
         r.arguments.push(CppFfiFunctionArgument {
             name: "this_ptr".to_string(),
-            argument_type: ffi_type(&this_arg_type, CppTypeRole::NotReturnType)?,
+            argument_type: ffi_type(&this_arg_type, CppTypeRole::NotReturnType, flag_containers)?,
             meaning: CppFfiArgumentMeaning::This,
         });
     }
@@ -297,7 +716,12 @@ pub fn to_ffi_method(
     let normal_args = match &kind {
         NewFfiFunctionKind::Function { cpp_function, .. } => {
             if cpp_function.allows_variadic_arguments {
-                bail!("Variable arguments are not supported");
+                if cpp_function.member.is_some() {
+                    bail!("Variable arguments are not supported in member functions");
+                }
+                if cpp_function.arguments.is_empty() {
+                    bail!("Variable arguments without named arguments are not supported");
+                }
             }
 
             if cpp_function.is_destructor() {
@@ -313,14 +737,27 @@ pub fn to_ffi_method(
             cpp_function.arguments.clone()
         }
         NewFfiFunctionKind::FieldAccessor {
-            field,
+            field:
+                CppClassField {
+                    field_type: value_type,
+                    ..
+                },
+            accessor_type,
+        }
+        | NewFfiFunctionKind::VariableAccessor {
+            variable:
+                CppVariable {
+                    variable_type: value_type,
+                    ..
+                },
             accessor_type,
         } => {
             if accessor_type == &CppFieldAccessorType::Setter {
                 let arg = CppFunctionArgument {
                     name: "value".to_string(),
-                    argument_type: field.field_type.clone(),
+                    argument_type: value_type.clone(),
                     has_default_value: false,
+                    type_alias: None,
                 };
                 vec![arg]
             } else {
@@ -330,12 +767,36 @@ pub fn to_ffi_method(
     };
 
     for (index, arg) in normal_args.iter().enumerate() {
-        let c_type = ffi_type(&arg.argument_type, CppTypeRole::NotReturnType)?;
+        let c_type = ffi_type(
+            &arg.argument_type,
+            CppTypeRole::NotReturnType,
+            flag_containers,
+        )?;
+        let is_callback = c_type.conversion() == &CppToFfiTypeConversion::FunctionToCallback;
         r.arguments.push(CppFfiFunctionArgument {
             name: arg.name.clone(),
             argument_type: c_type,
             meaning: CppFfiArgumentMeaning::Argument(index),
         });
+        if is_callback {
+            // the `std::function` is constructed from the callback, its data and its deleter
+            let void_ptr = CppType::new_pointer(false, CppType::Void);
+            let deleter_type = CppType::FunctionPointer(CppFunctionPointerType {
+                return_type: Box::new(CppType::Void),
+                arguments: vec![void_ptr.clone()],
+                allows_variadic_arguments: false,
+            });
+            r.arguments.push(CppFfiFunctionArgument {
+                name: format!("{}_data", arg.name),
+                argument_type: CppFfiType::new(void_ptr, CppToFfiTypeConversion::NoChange)?,
+                meaning: CppFfiArgumentMeaning::CallbackData(index),
+            });
+            r.arguments.push(CppFfiFunctionArgument {
+                name: format!("{}_deleter", arg.name),
+                argument_type: CppFfiType::new(deleter_type, CppToFfiTypeConversion::NoChange)?,
+                meaning: CppFfiArgumentMeaning::CallbackDeleter(index),
+            });
+        }
     }
 
     let real_return_type = match &kind {
@@ -346,23 +807,34 @@ pub fn to_ffi_method(
             _ => cpp_function.return_type.clone(),
         },
         NewFfiFunctionKind::FieldAccessor {
-            field,
+            field:
+                CppClassField {
+                    field_type: value_type,
+                    ..
+                },
+            accessor_type,
+        }
+        | NewFfiFunctionKind::VariableAccessor {
+            variable:
+                CppVariable {
+                    variable_type: value_type,
+                    ..
+                },
             accessor_type,
         } => match *accessor_type {
-            CppFieldAccessorType::CopyGetter => field.field_type.clone(),
+            CppFieldAccessorType::CopyGetter => value_type.clone(),
             CppFieldAccessorType::ConstRefGetter => {
-                CppType::new_reference(true, field.field_type.clone())
-            }
-            CppFieldAccessorType::MutRefGetter => {
-                CppType::new_reference(false, field.field_type.clone())
+                CppType::new_reference(true, value_type.clone())
             }
+            CppFieldAccessorType::MutRefGetter => CppType::new_reference(false, value_type.clone()),
             CppFieldAccessorType::Setter => CppType::Void,
         },
     };
-    let real_return_type_ffi = ffi_type(&real_return_type, CppTypeRole::ReturnType)?;
+    let real_return_type_ffi =
+        ffi_type(&real_return_type, CppTypeRole::ReturnType, flag_containers)?;
     match &real_return_type {
-        // QFlags is converted to uint in FFI
-        CppType::Class(path) if !is_qflags(path) => {
+        // flag containers are converted to int in FFI
+        CppType::Class(path) if !flag_containers.iter().any(|c| c.matches(path)) => {
             if movable_types.iter().any(|t| t == path) {
                 r.arguments.push(CppFfiFunctionArgument {
                     name: "output".to_string(),
@@ -380,6 +852,38 @@ pub fn to_ffi_method(
         }
     }
 
+    if let NewFfiFunctionKind::Function {
+        cpp_function,
+        va_list_function,
+    } = &kind
+    {
+        if cpp_function.allows_variadic_arguments {
+            r.variadic_arguments = Some(if let Some(va_list_function) = va_list_function {
+                CppFfiVariadicArguments::VaList {
+                    va_list_function: va_list_function.clone(),
+                }
+            } else {
+                // without a `va_list` counterpart, variadic arguments can't be forwarded,
+                // so the function itself must be callable through C ABI
+                if cpp_function.path.items().len() != 1 || !cpp_function.has_c_linkage {
+                    bail!("Variable arguments are only supported in global C functions");
+                }
+                let mut all_types = r
+                    .arguments
+                    .iter()
+                    .map(|arg| &arg.argument_type)
+                    .chain(once(&r.return_type));
+                if all_types.any(|t| t.conversion() != &CppToFfiTypeConversion::NoChange)
+                    || r.allocation_place != ReturnValueAllocationPlace::NotApplicable
+                {
+                    bail!("Variable arguments require C-compatible types of other arguments");
+                }
+                r.path = cpp_function.path.clone();
+                CppFfiVariadicArguments::DirectLinkage
+            });
+        }
+    }
+
     Ok(r)
 }
 
@@ -387,6 +891,7 @@ pub fn to_ffi_method(
 fn generate_field_accessors(
     field: &CppClassField,
     movable_types: &[CppPath],
+    flag_containers: &[CppFlagContainer],
     name_provider: &mut FfiNameProvider,
 ) -> Result<Vec<CppFfiItem>> {
     let mut new_methods = Vec::new();
@@ -395,7 +900,7 @@ fn generate_field_accessors(
             field: field.clone(),
             accessor_type,
         };
-        let ffi_function = to_ffi_method(kind, movable_types, name_provider)?;
+        let ffi_function = to_ffi_method(kind, movable_types, flag_containers, name_provider)?;
         Ok(CppFfiItem::Function(ffi_function))
     };
 
@@ -414,6 +919,39 @@ fn generate_field_accessors(
     Ok(new_methods)
 }
 
+/// Adds fictional getter and setter methods for a public global variable
+/// or static data member. Setters are not generated for const variables.
+fn generate_variable_accessors(
+    variable: &CppVariable,
+    movable_types: &[CppPath],
+    flag_containers: &[CppFlagContainer],
+    name_provider: &mut FfiNameProvider,
+) -> Result<Vec<CppFfiItem>> {
+    let mut new_methods = Vec::new();
+    let mut create_method = |accessor_type| -> Result<CppFfiItem> {
+        let kind = NewFfiFunctionKind::VariableAccessor {
+            variable: variable.clone(),
+            accessor_type,
+        };
+        let ffi_function = to_ffi_method(kind, movable_types, flag_containers, name_provider)?;
+        Ok(CppFfiItem::Function(ffi_function))
+    };
+
+    if variable.variable_type.is_class() {
+        new_methods.push(create_method(CppFieldAccessorType::ConstRefGetter)?);
+        if !variable.is_const {
+            new_methods.push(create_method(CppFieldAccessorType::MutRefGetter)?);
+        }
+    } else {
+        new_methods.push(create_method(CppFieldAccessorType::CopyGetter)?);
+    }
+    if !variable.is_const {
+        new_methods.push(create_method(CppFieldAccessorType::Setter)?);
+    }
+
+    Ok(new_methods)
+}
+
 fn check_preconditions(item: &CppItem, source_ffi_item: Option<DbItem<&CppFfiItem>>) -> Result<()> {
     match item {
         CppItem::Function(function) => {
@@ -449,6 +987,14 @@ fn check_preconditions(item: &CppItem, source_ffi_item: Option<DbItem<&CppFfiIte
                 bail!("field is protected");
             }
         }
+        CppItem::Variable(variable) => {
+            if variable.visibility == CppVisibility::Private {
+                bail!("variable is private");
+            }
+            if variable.visibility == CppVisibility::Protected {
+                bail!("variable is protected");
+            }
+        }
         _ => {}
     }
     if item
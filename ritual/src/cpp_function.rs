@@ -23,6 +23,9 @@ pub struct CppFunctionArgument {
     /// Flag indicating that the argument has default value and
     /// therefore can be omitted when calling the method
     pub has_default_value: bool,
+    /// Path of the typedef or type alias used to spell the argument type
+    /// in the original declaration, if any
+    pub type_alias: Option<CppPath>,
 }
 
 impl CppFunctionArgument {
@@ -121,6 +124,11 @@ pub struct CppFunction {
     /// C++ code of the method's declaration.
     /// None if the method was not explicitly declared.
     pub declaration_code: Option<String>,
+    /// Path of the typedef or type alias used to spell the return type
+    /// in the original declaration, if any
+    pub return_type_alias: Option<CppPath>,
+    /// True if the function is declared with C language linkage (`extern "C"`)
+    pub has_c_linkage: bool,
 }
 
 /// Chosen type allocation place for the method
@@ -332,6 +340,74 @@ impl CppFunction {
         arg == self.arguments[0].argument_type
     }
 
+    /// Returns true if this method is a move constructor.
+    pub fn is_move_constructor(&self) -> bool {
+        self.is_constructor() && self.has_single_rvalue_reference_to_class_argument()
+    }
+
+    /// Returns true if this method is a move assignment operator.
+    pub fn is_move_assignment_operator(&self) -> bool {
+        self.operator == Some(CppOperator::Assignment)
+            && self.has_single_rvalue_reference_to_class_argument()
+    }
+
+    fn has_single_rvalue_reference_to_class_argument(&self) -> bool {
+        if self.arguments.len() != 1 {
+            return false;
+        }
+        let class_path = match self.class_path() {
+            Ok(path) => path,
+            Err(_) => return false,
+        };
+        let arg = CppType::PointerLike {
+            kind: CppPointerLikeTypeKind::RValueReference,
+            is_const: false,
+            target: Box::new(CppType::Class(class_path)),
+        };
+        arg == self.arguments[0].argument_type
+    }
+
+    /// Returns true if `other` is the same function, except that it accepts
+    /// const references in place of some of the rvalue references accepted by `self`.
+    pub fn is_rvalue_reference_overload_of(&self, other: &CppFunction) -> bool {
+        let member_matches = match (&self.member, &other.member) {
+            (Some(m1), Some(m2)) => m1.is_const == m2.is_const && m1.is_static == m2.is_static,
+            (None, None) => true,
+            _ => false,
+        };
+        if !member_matches
+            || self.path != other.path
+            || self.arguments.len() != other.arguments.len()
+            || self.allows_variadic_arguments != other.allows_variadic_arguments
+        {
+            return false;
+        }
+        let mut has_rvalue_reference = false;
+        for (arg, other_arg) in self.arguments.iter().zip(&other.arguments) {
+            if arg.argument_type == other_arg.argument_type {
+                continue;
+            }
+            match (&arg.argument_type, &other_arg.argument_type) {
+                (
+                    CppType::PointerLike {
+                        kind: CppPointerLikeTypeKind::RValueReference,
+                        is_const: false,
+                        target,
+                    },
+                    CppType::PointerLike {
+                        kind: CppPointerLikeTypeKind::Reference,
+                        is_const: true,
+                        target: other_target,
+                    },
+                ) if target == other_target => {
+                    has_rvalue_reference = true;
+                }
+                _ => return false,
+            }
+        }
+        has_rvalue_reference
+    }
+
     /// Returns true if this method is a destructor.
     pub fn is_destructor(&self) -> bool {
         match &self.member {
@@ -3,7 +3,7 @@ use crate::cpp_function::{
     CppFunction, CppFunctionArgument, CppFunctionKind, CppFunctionMemberData,
 };
 use crate::cpp_operator::CppOperator;
-use crate::cpp_type::CppType;
+use crate::cpp_type::{std_function_type, CppType};
 use crate::database::ItemWithSource;
 use crate::processor::ProcessorData;
 use ritual_common::errors::Result;
@@ -45,6 +45,8 @@ pub fn run(data: &mut ProcessorData<'_>) -> Result<()> {
                 allows_variadic_arguments: false,
                 cast: None,
                 declaration_code: None,
+                return_type_alias: None,
+                has_c_linkage: false,
             };
             methods.push(ItemWithSource::new(&type1.id, destructor));
 
@@ -68,6 +70,8 @@ pub fn run(data: &mut ProcessorData<'_>) -> Result<()> {
                 arguments: vec![],
                 allows_variadic_arguments: false,
                 declaration_code: None,
+                return_type_alias: None,
+                has_c_linkage: false,
                 cast: None,
             };
             methods.push(ItemWithSource::new(&type1.id, default_constructor));
@@ -76,6 +80,7 @@ pub fn run(data: &mut ProcessorData<'_>) -> Result<()> {
                 argument_type: CppType::new_reference(true, CppType::Class(class_path.clone())),
                 name: "other".to_string(),
                 has_default_value: false,
+                type_alias: None,
             };
 
             let copy_constructor = CppFunction {
@@ -99,6 +104,8 @@ pub fn run(data: &mut ProcessorData<'_>) -> Result<()> {
                 allows_variadic_arguments: false,
                 cast: None,
                 declaration_code: None,
+                return_type_alias: None,
+                has_c_linkage: false,
             };
             methods.push(ItemWithSource::new(&type1.id, copy_constructor));
 
@@ -123,6 +130,8 @@ pub fn run(data: &mut ProcessorData<'_>) -> Result<()> {
                 allows_variadic_arguments: false,
                 cast: None,
                 declaration_code: None,
+                return_type_alias: None,
+                has_c_linkage: false,
             };
             methods.push(ItemWithSource::new(&type1.id, assignment_operator));
         }
@@ -132,3 +141,59 @@ pub fn run(data: &mut ProcessorData<'_>) -> Result<()> {
     }
     Ok(())
 }
+
+/// Adds `operator()` to every instantiation of `std::function`, allowing to call
+/// function objects returned by C++ functions. The operator is declared in a partial
+/// specialization of the template, so it's not available through template instantiation.
+pub fn add_std_function_call_operators(data: &mut ProcessorData<'_>) -> Result<()> {
+    let mut methods = Vec::new();
+    for type1 in data
+        .db
+        .cpp_items()
+        .filter_map(|item| item.filter_map(|item| item.as_type_ref()))
+        .filter(|item| item.item.kind.is_class())
+    {
+        let class_path = &type1.item.path;
+        let function_type = match std_function_type(class_path) {
+            Some(function_type) if !function_type.allows_variadic_arguments => function_type,
+            _ => continue,
+        };
+
+        let call_operator = CppFunction {
+            path: class_path.join(CppPathItem::from_good_str("operator()")),
+            member: Some(CppFunctionMemberData {
+                is_virtual: false,
+                is_pure_virtual: false,
+                is_const: true,
+                is_static: false,
+                visibility: CppVisibility::Public,
+                is_signal: false,
+                is_slot: false,
+                kind: CppFunctionKind::Regular,
+            }),
+            operator: Some(CppOperator::FunctionCall),
+            return_type: (*function_type.return_type).clone(),
+            arguments: function_type
+                .arguments
+                .iter()
+                .enumerate()
+                .map(|(index, arg_type)| CppFunctionArgument {
+                    argument_type: arg_type.clone(),
+                    name: format!("arg{}", index + 1),
+                    has_default_value: false,
+                    type_alias: None,
+                })
+                .collect(),
+            allows_variadic_arguments: false,
+            cast: None,
+            declaration_code: None,
+            return_type_alias: None,
+            has_c_linkage: false,
+        };
+        methods.push(ItemWithSource::new(&type1.id, call_operator));
+    }
+    for item in methods {
+        data.add_cpp_item(Some(item.source_id), CppItem::Function(item.item))?;
+    }
+    Ok(())
+}
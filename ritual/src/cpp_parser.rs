@@ -1,8 +1,9 @@
 use crate::config::Config;
 use crate::cpp_code_generator::{all_include_directives, write_include_directives};
 use crate::cpp_data::{
-    CppBaseSpecifier, CppClassField, CppEnumValue, CppItem, CppNamespace, CppOriginLocation,
-    CppPath, CppPathItem, CppTypeDeclaration, CppTypeDeclarationKind, CppVisibility,
+    CppBaseSpecifier, CppClassField, CppConstant, CppConstantValue, CppEnumValue, CppItem,
+    CppNamespace, CppOriginLocation, CppPath, CppPathItem, CppTypeAlias, CppTypeDeclaration,
+    CppTypeDeclarationKind, CppVariable, CppVisibility,
 };
 use crate::cpp_function::{
     CppFunction, CppFunctionArgument, CppFunctionKind, CppFunctionMemberData,
@@ -10,7 +11,7 @@ use crate::cpp_function::{
 use crate::cpp_operator::CppOperator;
 use crate::cpp_type::{
     CppBuiltInNumericType, CppFunctionPointerType, CppPointerLikeTypeKind, CppSpecificNumericType,
-    CppSpecificNumericTypeKind, CppTemplateParameter, CppType,
+    CppSpecificNumericTypeKind, CppTemplateArgumentValue, CppTemplateParameter, CppType,
 };
 use crate::database::ItemId;
 use crate::processor::ProcessorData;
@@ -129,11 +130,19 @@ fn get_template_arguments(entity: Entity<'_>) -> Option<Vec<CppType>> {
     let args = entity
         .get_children()
         .into_iter()
-        .filter(|c| c.get_kind() == EntityKind::TemplateTypeParameter)
+        .filter(|c| {
+            c.get_kind() == EntityKind::TemplateTypeParameter
+                || c.get_kind() == EntityKind::NonTypeTemplateParameter
+        })
         .enumerate()
         .map(|(i, c)| {
+            let default_name = if c.get_kind() == EntityKind::NonTypeTemplateParameter {
+                format!("Value{}", i + 1)
+            } else {
+                format!("Type{}", i + 1)
+            };
             CppType::TemplateParameter(CppTemplateParameter {
-                name: c.get_name().unwrap_or_else(|| format!("Type{}", i + 1)),
+                name: c.get_name().unwrap_or(default_name),
                 index: i,
                 nested_level,
             })
@@ -209,6 +218,28 @@ fn get_full_name_display(entity: Entity<'_>) -> String {
     }
 }
 
+/// Returns true if `entity` is declared in an `extern "C"` linkage specification.
+/// Redeclarations inherit the linkage of the first declaration.
+fn has_c_linkage(entity: Entity<'_>) -> bool {
+    for declaration in &[entity, entity.get_canonical_entity()] {
+        let mut current = declaration.get_lexical_parent();
+        while let Some(parent) = current {
+            if parent.get_kind() == EntityKind::LinkageSpec {
+                // the innermost specification determines the linkage
+                let language = parent
+                    .get_range()
+                    .and_then(|range| range.tokenize().get(1).map(|t| t.get_spelling()));
+                if language.as_ref().map(String::as_str) == Some("\"C\"") {
+                    return true;
+                }
+                break;
+            }
+            current = parent.get_lexical_parent();
+        }
+    }
+    false
+}
+
 #[cfg(test)]
 fn init_clang() -> Result<Clang> {
     for _ in 0..12000 {
@@ -429,6 +460,51 @@ impl CppParser<'_, '_> {
             .find(|i| f(i))
     }
 
+    /// Parses value of a non-type template argument (integer, bool or enum constant).
+    fn parse_template_argument_value(&self, text: &str) -> Option<CppType> {
+        let mut text = text.trim();
+        // clang may represent enum and other typed constants as C-style casts
+        if text.starts_with('(') {
+            if let Some(index) = text.find(')') {
+                text = text[index + 1..].trim();
+            }
+        }
+        let value = match text {
+            "true" => CppTemplateArgumentValue::Bool(true),
+            "false" => CppTemplateArgumentValue::Bool(false),
+            _ => {
+                if let Some(value) = parse_integer_literal(text) {
+                    CppTemplateArgumentValue::Integer(value)
+                } else {
+                    let text = text.trim_start_matches("::");
+                    let enum_value = self
+                        .data
+                        .db
+                        .all_cpp_items()
+                        .filter_map(|item| item.item.as_enum_value_ref())
+                        .find(|value| {
+                            if value.path.to_cpp_pseudo_code() == text {
+                                return true;
+                            }
+                            // values of unscoped enums are also accessible
+                            // without the enum's name
+                            match value.path.parent().and_then(|p| p.parent()) {
+                                Ok(scope) => {
+                                    scope.join(value.path.last().clone()).to_cpp_pseudo_code()
+                                        == text
+                                }
+                                Err(_) => value.path.last().name == text,
+                            }
+                        })?;
+                    CppTemplateArgumentValue::EnumValue {
+                        path: enum_value.path.clone(),
+                    }
+                }
+            }
+        };
+        Some(CppType::TemplateArgumentValue(value))
+    }
+
     /// Attempts to parse an unexposed type, i.e. a type the used `clang` API
     /// is not able to describe. Either `type1` or `string` must be specified,
     /// and both may be specified at the same time.
@@ -591,6 +667,9 @@ impl CppParser<'_, '_> {
                 }
             }
         }
+        if let Some(value) = self.parse_template_argument_value(&name) {
+            return Ok(value);
+        }
 
         if let Some((class_text, args)) = parse_template_args(&name) {
             if self
@@ -639,6 +718,10 @@ impl CppParser<'_, '_> {
                 display_name
             );
         }
+        if display_name == "__va_list_tag *" || display_name == "struct __va_list_tag *" {
+            // `va_list` argument decayed to a pointer
+            return Ok(CppType::Class(CppPath::from_good_str("va_list")));
+        }
         if display_name == "std::__cxx11::basic_string::const_reference"
             || display_name == "std::vector::const_reference"
         {
@@ -739,9 +822,27 @@ impl CppParser<'_, '_> {
                             if arg_types.is_empty() {
                                 bail!("arg_types is empty");
                             }
-                            for arg_type in arg_types {
+                            let arg_texts = parse_template_args(&type1.get_display_name())
+                                .map(|(_, args)| args);
+                            for (index, arg_type) in arg_types.into_iter().enumerate() {
                                 match arg_type {
-                                    None => bail!("Template argument is None"),
+                                    None => {
+                                        // non-type template argument is only available
+                                        // in the type's display name
+                                        let value = arg_texts
+                                            .as_ref()
+                                            .and_then(|texts| texts.get(index))
+                                            .and_then(|text| {
+                                                self.parse_template_argument_value(text)
+                                            })
+                                            .ok_or_else(|| {
+                                                format_err!(
+                                                    "Unsupported template argument: {:?}",
+                                                    type1
+                                                )
+                                            })?;
+                                        r.push(value);
+                                    }
                                     Some(arg_type) => {
                                         match self.parse_type(arg_type, context_template_args) {
                                             Ok(parsed_type) => r.push(parsed_type),
@@ -880,6 +981,28 @@ impl CppParser<'_, '_> {
         }
     }
 
+    /// Returns path of the typedef or type alias that was used to spell `type1`
+    /// (possibly behind a pointer or a reference).
+    fn parse_type_alias_usage(&self, type1: Type<'_>) -> Option<CppPath> {
+        match type1.get_kind() {
+            TypeKind::Typedef => {
+                let mut name = type1.get_display_name();
+                if name.starts_with("const ") {
+                    name = name[6..].trim().to_string();
+                }
+                if self.parse_special_typedef(&name).is_some() {
+                    return None;
+                }
+                get_path(type1.get_declaration()?).ok()
+            }
+            TypeKind::Elaborated => self.parse_type_alias_usage(type1.get_elaborated_type()?),
+            TypeKind::Pointer | TypeKind::LValueReference | TypeKind::RValueReference => {
+                self.parse_type_alias_usage(type1.get_pointee_type()?)
+            }
+            _ => None,
+        }
+    }
+
     /// Checks if the typedef `name` has a special meaning for the parser.
     fn parse_special_typedef(&self, name: &str) -> Option<CppType> {
         match name {
@@ -949,6 +1072,9 @@ impl CppParser<'_, '_> {
                 path: CppPath::from_good_str(name),
                 is_signed: false,
             }),
+            "va_list" | "__gnuc_va_list" | "__builtin_va_list" | "std::va_list" => {
+                Some(CppType::Class(CppPath::from_good_str("va_list")))
+            }
             "std::initializer_list::size_type"
             | "std::__cxx11::basic_string::size_type"
             | "std::vector::size_type" => Some(CppType::PointerSizedInteger {
@@ -1066,6 +1192,7 @@ impl CppParser<'_, '_> {
                 name,
                 argument_type,
                 has_default_value,
+                type_alias: self.parse_type_alias_usage(clang_type),
             });
         }
 
@@ -1087,16 +1214,7 @@ impl CppParser<'_, '_> {
         }
 
         let template_arguments = match entity.get_kind() {
-            EntityKind::FunctionTemplate => {
-                if entity
-                    .get_children()
-                    .into_iter()
-                    .any(|c| c.get_kind() == EntityKind::NonTypeTemplateParameter)
-                {
-                    bail!("Non-type template parameter is not supported");
-                }
-                get_template_arguments(entity)
-            }
+            EntityKind::FunctionTemplate => get_template_arguments(entity),
             _ => None,
         };
 
@@ -1247,6 +1365,8 @@ impl CppParser<'_, '_> {
             return_type: return_type_parsed,
             cast: None,
             declaration_code,
+            return_type_alias: self.parse_type_alias_usage(return_type),
+            has_c_linkage: has_c_linkage(entity),
         };
 
         self.add_output(
@@ -1258,6 +1378,31 @@ impl CppParser<'_, '_> {
         Ok(())
     }
 
+    /// Returns the integer type used to represent values of the enum `entity`.
+    fn parse_enum_underlying_type(&self, entity: Entity<'_>) -> Result<CppType> {
+        let type1 = entity
+            .get_enum_underlying_type()
+            .ok_or_else(|| err_msg("failed to get underlying type of enum"))?;
+        let underlying_type = self
+            .parse_type(type1, &[])
+            .or_else(|_| self.parse_type(type1.get_canonical_type(), &[]))?;
+        let is_integer = match &underlying_type {
+            CppType::BuiltInNumeric(numeric) => {
+                numeric.is_signed_integer()
+                    || numeric.is_unsigned_integer()
+                    || numeric.is_integer_with_undefined_signedness()
+            }
+            CppType::SpecificNumeric(numeric) => {
+                matches!(numeric.kind, CppSpecificNumericTypeKind::Integer { .. })
+            }
+            _ => false,
+        };
+        if !is_integer {
+            bail!("unsupported underlying type of enum: {:?}", underlying_type);
+        }
+        Ok(underlying_type)
+    }
+
     /// Parses an enum `entity`.
     fn parse_enum(&mut self, entity: Entity<'_>) -> Result<()> {
         let include_file = self.entity_include_file(entity).with_context(|_| {
@@ -1268,11 +1413,12 @@ impl CppParser<'_, '_> {
             )
         })?;
         let enum_name = get_path(entity)?;
+        let underlying_type = self.parse_enum_underlying_type(entity)?;
         self.add_output(
             include_file.clone(),
             get_origin_location(entity)?,
             CppItem::Type(CppTypeDeclaration {
-                kind: CppTypeDeclarationKind::Enum,
+                kind: CppTypeDeclarationKind::Enum { underlying_type },
                 path: enum_name.clone(),
             }),
         )?;
@@ -1323,13 +1469,147 @@ impl CppParser<'_, '_> {
                     Accessibility::Protected => CppVisibility::Protected,
                     Accessibility::Private => CppVisibility::Private,
                 },
-                is_static: entity.get_kind() == EntityKind::VarDecl,
             }),
         )?;
 
         Ok(())
     }
 
+    /// Parses a global variable or a static data member `entity`.
+    fn parse_variable(&mut self, entity: Entity<'_>) -> Result<()> {
+        let include_file = self
+            .entity_include_file(entity)
+            .with_context(|_| err_msg("Origin of variable is unknown"))?;
+        let path = get_path(entity)?;
+        let clang_type = entity
+            .get_type()
+            .ok_or_else(|| err_msg("failed to get variable type"))?;
+        let variable_type = self
+            .parse_type(clang_type, &get_context_template_args(entity))
+            .with_context(|_| err_msg("failed to parse variable type"))?;
+        if clang_type.is_const_qualified() {
+            if let Some(value) = parse_variable_initializer(entity, &variable_type) {
+                self.add_output(
+                    include_file,
+                    get_origin_location(entity)?,
+                    CppItem::Constant(CppConstant {
+                        path,
+                        constant_type: variable_type,
+                        value,
+                        is_macro: false,
+                        origin_location: get_origin_location(entity)?,
+                    }),
+                )?;
+                return Ok(());
+            }
+        }
+        self.add_output(
+            include_file,
+            get_origin_location(entity)?,
+            CppItem::Variable(CppVariable {
+                path,
+                variable_type,
+                is_const: clang_type.is_const_qualified(),
+                visibility: match entity.get_accessibility().unwrap_or(Accessibility::Public) {
+                    Accessibility::Public => CppVisibility::Public,
+                    Accessibility::Protected => CppVisibility::Protected,
+                    Accessibility::Private => CppVisibility::Private,
+                },
+            }),
+        )?;
+
+        Ok(())
+    }
+
+    /// Parses a `typedef` or `using` declaration `entity`.
+    fn parse_type_alias(&mut self, entity: Entity<'_>) -> Result<()> {
+        let include_file = self
+            .entity_include_file(entity)
+            .with_context(|_| err_msg("Origin of type alias is unknown"))?;
+        let path = get_path(entity)?;
+        if self.parse_special_typedef(&path.to_cpp_code()?).is_some() {
+            bail!("typedef has a special meaning for the parser");
+        }
+        let underlying_type = entity
+            .get_typedef_underlying_type()
+            .ok_or_else(|| err_msg("failed to get underlying type"))?;
+        let target = self
+            .parse_type(underlying_type, &get_context_template_args(entity))
+            .with_context(|_| err_msg("failed to parse underlying type"))?;
+        match &target {
+            CppType::Class(target_path) | CppType::Enum { path: target_path }
+                if target_path == &path =>
+            {
+                // `typedef struct A { ... } A;`
+                bail!("type alias has the same name as its target");
+            }
+            _ => {}
+        }
+        self.add_output(
+            include_file,
+            get_origin_location(entity)?,
+            CppItem::TypeAlias(CppTypeAlias { path, target }),
+        )?;
+        Ok(())
+    }
+
+    /// Parses object-like macros defined in translation unit `entity`
+    /// that expand to a literal value.
+    fn parse_macros(&mut self, entity: Entity<'_>) -> Result<()> {
+        for child in entity.get_children() {
+            if child.get_kind() != EntityKind::MacroDefinition {
+                continue;
+            }
+            if let Err(error) = self.parse_macro(child) {
+                trace!(
+                    "skipping macro: {}: {}",
+                    child.get_name().unwrap_or_default(),
+                    error
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Parses an object-like macro `entity`.
+    fn parse_macro(&mut self, entity: Entity<'_>) -> Result<()> {
+        let name = entity
+            .get_name()
+            .ok_or_else(|| err_msg("failed to get macro name"))?;
+        let path = CppPath::from_str(&name)?;
+        if !self.is_in_target_paths(entity)? || !self.is_path_allowed(&path)? {
+            return Ok(());
+        }
+        let tokens = entity
+            .get_range()
+            .ok_or_else(|| err_msg("failed to get macro range"))?
+            .tokenize();
+        if tokens.len() < 2 {
+            bail!("macro has no value");
+        }
+        let name_offset = tokens[0].get_location().get_spelling_location().offset;
+        let next_offset = tokens[1].get_location().get_spelling_location().offset;
+        if tokens[1].get_spelling() == "(" && next_offset == name_offset + name.len() as u32 {
+            bail!("function-like macro");
+        }
+        let body = tokens[1..].iter().map(|t| t.get_spelling()).collect_vec();
+        let (constant_type, value) =
+            parse_literal_constant(&body).ok_or_else(|| err_msg("macro value is not a literal"))?;
+        let origin_location = get_origin_location(entity)?;
+        self.add_output(
+            self.entity_include_file(entity)?,
+            origin_location.clone(),
+            CppItem::Constant(CppConstant {
+                path,
+                constant_type,
+                value,
+                is_macro: true,
+                origin_location,
+            }),
+        )?;
+        Ok(())
+    }
+
     // we pass parent manually because both lexical and semantic parent are missing for these
     // entities for some reason
     fn parse_class_base(
@@ -1378,14 +1658,6 @@ impl CppParser<'_, '_> {
         let full_name = get_path(entity)?;
         let template_arguments = get_template_arguments(entity);
         if entity.get_kind() == EntityKind::ClassTemplate {
-            if entity
-                .get_children()
-                .into_iter()
-                .any(|c| c.get_kind() == EntityKind::NonTypeTemplateParameter)
-            {
-                bail!("Non-type template parameter is not supported");
-            }
-
             if template_arguments.is_none() {
                 dump_entity(entity, 0);
                 bail!("missing template arguments");
@@ -1395,8 +1667,7 @@ impl CppParser<'_, '_> {
         }
         let mut current_base_index = 0;
         for child in entity.get_children() {
-            if child.get_kind() == EntityKind::FieldDecl || child.get_kind() == EntityKind::VarDecl
-            {
+            if child.get_kind() == EntityKind::FieldDecl {
                 if let Err(err) = self.parse_class_field(child, &full_name) {
                     debug!(
                         "failed to parse class field: {}: {}",
@@ -1416,9 +1687,6 @@ impl CppParser<'_, '_> {
                 }
                 current_base_index += 1;
             }
-            if child.get_kind() == EntityKind::NonTypeTemplateParameter {
-                bail!("Non-type template parameter is not supported");
-            }
         }
         self.add_output(
             include_file,
@@ -1454,34 +1722,42 @@ impl CppParser<'_, '_> {
         Ok(os_str_to_str(file_name)?.to_string())
     }
 
-    /// Returns false if this `entity` was blacklisted in some way.
-    fn should_process_entity(&self, entity: Entity<'_>) -> Result<bool> {
-        if entity.get_kind() == EntityKind::TranslationUnit {
-            return Ok(true);
-        }
+    /// Returns true if `entity` is located in one of the target headers.
+    fn is_in_target_paths(&self, entity: Entity<'_>) -> Result<bool> {
         if let Ok(file_path) = self.entity_include_path(entity) {
             let file_path = canonicalize(Path::new(&file_path))?;
-            if !self.current_target_paths.is_empty()
-                && !self
+            Ok(self.current_target_paths.is_empty()
+                || self
                     .current_target_paths
                     .iter()
-                    .any(|x| file_path.starts_with(x))
-            {
-                return Ok(false);
-            }
+                    .any(|x| file_path.starts_with(x)))
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Returns false if `path` was blacklisted by `cpp_parser_path_hook`.
+    fn is_path_allowed(&self, path: &CppPath) -> Result<bool> {
+        if let Some(hook) = self.data.config.cpp_parser_path_hook() {
+            hook(path)
         } else {
+            Ok(true)
+        }
+    }
+
+    /// Returns false if this `entity` was blacklisted in some way.
+    fn should_process_entity(&self, entity: Entity<'_>) -> Result<bool> {
+        if entity.get_kind() == EntityKind::TranslationUnit {
+            return Ok(true);
+        }
+        if !self.is_in_target_paths(entity)? {
             return Ok(false);
         }
         if let Ok(full_name) = get_path(entity) {
-            if let Some(hook) = self.data.config.cpp_parser_path_hook() {
-                if !hook(&full_name)? {
-                    return Ok(false);
-                }
-            }
+            self.is_path_allowed(&full_name)
         } else {
-            return Ok(false);
+            Ok(false)
         }
-        Ok(true)
     }
 
     fn parse(&mut self, entity: Entity<'_>) -> Result<()> {
@@ -1489,6 +1765,8 @@ impl CppParser<'_, '_> {
         self.parse_types(entity)?;
         debug!("Parsing functions");
         self.parse_functions(entity)?;
+        debug!("Parsing macros");
+        self.parse_macros(entity)?;
         for hook in self.data.config.after_cpp_parser_hooks() {
             hook(self.data, &self.output)?;
         }
@@ -1585,6 +1863,29 @@ impl CppParser<'_, '_> {
                     trace!("entity: {:?}", entity);
                 }
             }
+            EntityKind::TypedefDecl | EntityKind::TypeAliasDecl => {
+                if entity.get_accessibility() == Some(Accessibility::Private) {
+                    return Ok(()); // skipping private stuff
+                }
+                if let Err(error) = self.parse_type_alias(entity) {
+                    debug!(
+                        "failed to parse type alias: {}: {}",
+                        get_full_name_display(entity),
+                        error
+                    );
+                    trace!("entity: {:?}", entity);
+                }
+            }
+            EntityKind::VarDecl => {
+                if let Err(error) = self.parse_variable(entity) {
+                    debug!(
+                        "failed to parse variable: {}: {}",
+                        get_full_name_display(entity),
+                        error
+                    );
+                    trace!("entity: {:?}", entity);
+                }
+            }
             EntityKind::StructDecl
             | EntityKind::ClassDecl
             | EntityKind::ClassTemplate
@@ -1696,3 +1997,237 @@ fn should_parse_template_args_works() {
     assert_eq!(parse_template_args("name<arg,arg2"), None);
     assert_eq!(parse_template_args("name<arg<arg3,arg4>,arg2"), None);
 }
+
+/// Parses a C++ integer literal, e.g. `42`, `-1`, `0x10u` or `8ULL`.
+fn parse_integer_literal(text: &str) -> Option<i64> {
+    let text = text.trim();
+    let (is_negative, text) = if let Some(text) = text.strip_prefix('-') {
+        (true, text.trim_start())
+    } else {
+        (false, text)
+    };
+    let text = text.trim_end_matches(|c| c == 'u' || c == 'U' || c == 'l' || c == 'L');
+    let value = if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        i64::from_str_radix(hex, 16).ok()?
+    } else if text.len() > 1 && text.starts_with('0') {
+        i64::from_str_radix(&text[1..], 8).ok()?
+    } else {
+        text.parse::<i64>().ok()?
+    };
+    Some(if is_negative { -value } else { value })
+}
+
+/// Parses tokens of a macro body or a variable initializer
+/// consisting of a single literal (possibly negated and parenthesized)
+/// or a sequence of string literals.
+fn parse_literal_constant(tokens: &[String]) -> Option<(CppType, CppConstantValue)> {
+    let mut tokens = tokens;
+    while tokens.len() > 2 && tokens[0] == "(" && tokens[tokens.len() - 1] == ")" {
+        tokens = &tokens[1..tokens.len() - 1];
+    }
+    if tokens.is_empty() {
+        return None;
+    }
+    if tokens.iter().all(|token| token.starts_with('"')) {
+        let mut value = String::new();
+        for token in tokens {
+            value.push_str(&parse_string_literal(token)?);
+        }
+        let char_type = CppType::BuiltInNumeric(CppBuiltInNumericType::Char);
+        return Some((
+            CppType::new_pointer(true, char_type),
+            CppConstantValue::String(value),
+        ));
+    }
+    let (is_negative, text) = match tokens {
+        [text] => (false, text),
+        [sign, text] if sign == "-" => (true, text),
+        _ => return None,
+    };
+    if !is_negative && (text == "true" || text == "false") {
+        return Some((
+            CppType::BuiltInNumeric(CppBuiltInNumericType::Bool),
+            CppConstantValue::Bool(text == "true"),
+        ));
+    }
+    if let Some(value) = parse_integer_literal(text) {
+        let is_unsigned = text.contains(|c| c == 'u' || c == 'U');
+        let is_long_long = text.contains("ll") || text.contains("LL");
+        let value = if is_negative { -value } else { value };
+        let numeric_type = if is_unsigned {
+            if is_long_long || value > i64::from(u32::MAX) {
+                CppBuiltInNumericType::ULongLong
+            } else {
+                CppBuiltInNumericType::UInt
+            }
+        } else if is_long_long || value > i64::from(i32::MAX) || value < i64::from(i32::MIN) {
+            CppBuiltInNumericType::LongLong
+        } else {
+            CppBuiltInNumericType::Int
+        };
+        if is_unsigned && value < 0 {
+            return None;
+        }
+        return Some((
+            CppType::BuiltInNumeric(numeric_type),
+            CppConstantValue::Integer(value),
+        ));
+    }
+    if !text.starts_with(|c: char| c.is_ascii_digit() || c == '.')
+        || text.starts_with("0x")
+        || text.starts_with("0X")
+    {
+        // not a number or a hexadecimal floating point literal (not supported)
+        return None;
+    }
+    let is_float = text.ends_with('f') || text.ends_with('F');
+    let number = text.trim_end_matches(|c| c == 'f' || c == 'F' || c == 'l' || c == 'L');
+    let value = number.parse::<f64>().ok()?;
+    let value = if is_negative { -value } else { value };
+    let numeric_type = if is_float {
+        CppBuiltInNumericType::Float
+    } else {
+        CppBuiltInNumericType::Double
+    };
+    Some((
+        CppType::BuiltInNumeric(numeric_type),
+        CppConstantValue::FloatingPoint(format!("{:?}", value)),
+    ))
+}
+
+/// Parses a plain (non-prefixed) C++ string literal token, e.g. `"a\tb"`.
+fn parse_string_literal(token: &str) -> Option<String> {
+    let text = token.strip_prefix('"')?.strip_suffix('"')?;
+    let mut value = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        let escaped = match chars.next()? {
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            '0' => '\0',
+            c @ '\\' | c @ '"' | c @ '\'' | c @ '?' => c,
+            _ => return None,
+        };
+        value.push(escaped);
+    }
+    Some(value)
+}
+
+/// Evaluates the initializer of a constant variable `entity` if it is a literal
+/// compatible with `variable_type`.
+fn parse_variable_initializer(
+    entity: Entity<'_>,
+    variable_type: &CppType,
+) -> Option<CppConstantValue> {
+    let tokens = entity
+        .get_range()?
+        .tokenize()
+        .into_iter()
+        .map(|token| token.get_spelling())
+        .collect_vec();
+    let start = tokens.iter().position(|t| t == "=" || t == "{")?;
+    let mut initializer = &tokens[start + 1..];
+    if let Some(end) = initializer.iter().position(|t| t == ";") {
+        initializer = &initializer[..end];
+    }
+    if tokens[start] == "{" {
+        initializer = initializer.strip_suffix(&["}".to_string()])?;
+    }
+    let (_, value) = parse_literal_constant(initializer)?;
+    match (variable_type, value) {
+        (
+            CppType::BuiltInNumeric(CppBuiltInNumericType::Bool),
+            value @ CppConstantValue::Bool(_),
+        ) => Some(value),
+        (CppType::BuiltInNumeric(numeric), CppConstantValue::Integer(value))
+            if numeric.is_float() =>
+        {
+            Some(CppConstantValue::FloatingPoint(format!(
+                "{:?}",
+                value as f64
+            )))
+        }
+        (CppType::BuiltInNumeric(numeric), value @ CppConstantValue::FloatingPoint(_))
+            if numeric.is_float() =>
+        {
+            Some(value)
+        }
+        (CppType::BuiltInNumeric(numeric), value @ CppConstantValue::Integer(_))
+            if numeric.is_signed_integer() || numeric.is_unsigned_integer() =>
+        {
+            Some(value)
+        }
+        (CppType::SpecificNumeric(numeric), value @ CppConstantValue::Integer(_))
+            if numeric.kind != CppSpecificNumericTypeKind::FloatingPoint =>
+        {
+            Some(value)
+        }
+        (CppType::PointerSizedInteger { .. }, value @ CppConstantValue::Integer(_)) => Some(value),
+        (
+            CppType::PointerLike {
+                kind: CppPointerLikeTypeKind::Pointer,
+                is_const: true,
+                target,
+            },
+            value @ CppConstantValue::String(_),
+        ) if **target == CppType::BuiltInNumeric(CppBuiltInNumericType::Char) => Some(value),
+        _ => None,
+    }
+}
+
+#[test]
+fn parse_literal_constant_works() {
+    fn parse(tokens: &[&str]) -> Option<(CppType, CppConstantValue)> {
+        parse_literal_constant(&tokens.iter().map(|t| t.to_string()).collect_vec())
+    }
+    let int_type = CppType::BuiltInNumeric(CppBuiltInNumericType::Int);
+    assert_eq!(
+        parse(&["42"]),
+        Some((int_type.clone(), CppConstantValue::Integer(42)))
+    );
+    assert_eq!(
+        parse(&["(", "-", "1", ")"]),
+        Some((int_type, CppConstantValue::Integer(-1)))
+    );
+    assert_eq!(
+        parse(&["0xFFFFFFFFu"]),
+        Some((
+            CppType::BuiltInNumeric(CppBuiltInNumericType::UInt),
+            CppConstantValue::Integer(0xFFFF_FFFF)
+        ))
+    );
+    assert_eq!(
+        parse(&["1.5f"]),
+        Some((
+            CppType::BuiltInNumeric(CppBuiltInNumericType::Float),
+            CppConstantValue::FloatingPoint("1.5".to_string())
+        ))
+    );
+    assert_eq!(
+        parse(&["\"a\\tb\"", "\"c\""]),
+        Some((
+            CppType::new_pointer(true, CppType::BuiltInNumeric(CppBuiltInNumericType::Char)),
+            CppConstantValue::String("a\tbc".to_string())
+        ))
+    );
+    assert_eq!(parse(&["A", "|", "B"]), None);
+    assert_eq!(parse(&["OTHER_MACRO"]), None);
+}
+
+#[test]
+fn parse_integer_literal_works() {
+    assert_eq!(parse_integer_literal("4"), Some(4));
+    assert_eq!(parse_integer_literal(" 42 "), Some(42));
+    assert_eq!(parse_integer_literal("-1"), Some(-1));
+    assert_eq!(parse_integer_literal("0"), Some(0));
+    assert_eq!(parse_integer_literal("0x1F"), Some(31));
+    assert_eq!(parse_integer_literal("010"), Some(8));
+    assert_eq!(parse_integer_literal("16UL"), Some(16));
+    assert_eq!(parse_integer_literal("T"), None);
+    assert_eq!(parse_integer_literal("1.5"), None);
+}
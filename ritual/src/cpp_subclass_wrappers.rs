@@ -0,0 +1,284 @@
+//! Generates C++ subclasses that forward calls of virtual functions to callbacks,
+//! allowing to override virtual functions of the selected classes in Rust.
+
+use crate::cpp_data::{CppPath, CppVisibility};
+use crate::cpp_ffi_data::{
+    CppFfiItem, CppFlagContainer, CppSubclassWrapper, CppToFfiTypeConversion,
+    CppVirtualFunctionOverride,
+};
+use crate::cpp_ffi_generator::{ffi_type, FfiNameProvider};
+use crate::cpp_function::CppFunction;
+use crate::cpp_type::{CppFunctionPointerType, CppType, CppTypeRole};
+use crate::database::DatabaseClient;
+use crate::processor::ProcessorData;
+use itertools::Itertools;
+use log::{debug, trace};
+use ritual_common::errors::{bail, err_msg, Result};
+use ritual_common::utils::MapIfOk;
+use std::collections::HashSet;
+use std::iter::once;
+
+/// Returns `class_path` followed by all its direct and indirect base classes.
+fn class_and_bases(db: &DatabaseClient, class_path: &CppPath) -> Vec<CppPath> {
+    let mut result = vec![class_path.clone()];
+    let mut index = 0;
+    while index < result.len() {
+        let bases = db
+            .all_cpp_items()
+            .filter_map(|item| item.item.as_base_ref())
+            .filter(|base| base.derived_class_type == result[index])
+            .map(|base| base.base_class_type.clone())
+            .collect_vec();
+        for base in bases {
+            if !result.contains(&base) {
+                result.push(base);
+            }
+        }
+        index += 1;
+    }
+    result
+}
+
+/// Returns true if `function` can be redeclared in the subclass.
+fn is_supported(function: &CppFunction) -> bool {
+    !function.allows_variadic_arguments
+        && !function.is_operator()
+        && function.path.last().template_arguments.is_none()
+        && function
+            .all_involved_types()
+            .iter()
+            .all(|t| !t.is_function_pointer() && !t.is_or_contains_template_parameter())
+}
+
+/// Returns true if `function` has the same signature as `other`
+/// (i.e. one of them overrides or hides the other).
+fn is_same_signature(function: &CppFunction, other: &CppFunction) -> bool {
+    let is_const = |f: &CppFunction| f.member.as_ref().map_or(false, |m| m.is_const);
+    function.path.last().name == other.path.last().name
+        && function.argument_types_equal(other)
+        && is_const(function) == is_const(other)
+}
+
+/// Returns member functions of `classes` matching `filter`. If a function is redeclared
+/// in a derived class, only the most derived declaration is returned.
+fn member_functions<'a>(
+    db: &'a DatabaseClient,
+    classes: &[CppPath],
+    filter: impl Fn(&CppFunction) -> bool,
+) -> Vec<&'a CppFunction> {
+    let mut result: Vec<&CppFunction> = Vec::new();
+    for class_path in classes {
+        let functions = db
+            .all_cpp_items()
+            .filter_map(|item| item.item.as_function_ref())
+            .filter(|f| f.class_path().ok().as_ref() == Some(class_path))
+            .filter(|f| filter(f))
+            .collect_vec();
+        for function in functions {
+            if !result.iter().any(|f| is_same_signature(f, function)) {
+                result.push(function);
+            }
+        }
+    }
+    result
+}
+
+/// Generates information required to override `function`.
+fn generate_override(
+    function: &CppFunction,
+    callback_name: String,
+    flag_containers: &[CppFlagContainer],
+) -> Result<CppVirtualFunctionOverride> {
+    let arguments = function.arguments.iter().map_if_ok(|arg| {
+        ffi_type(
+            &arg.argument_type,
+            CppTypeRole::NotReturnType,
+            flag_containers,
+        )
+    })?;
+    if arguments
+        .iter()
+        .any(|arg| arg.conversion() == &CppToFfiTypeConversion::FunctionToCallback)
+    {
+        bail!("std::function arguments are not supported in overridden functions");
+    }
+    let return_type = ffi_type(
+        &function.return_type,
+        CppTypeRole::ReturnType,
+        flag_containers,
+    )?;
+
+    let func_arguments = once(CppType::new_pointer(false, CppType::Void))
+        .chain(arguments.iter().map(|t| t.ffi_type().clone()))
+        .collect();
+    let function_type = CppFunctionPointerType {
+        return_type: Box::new(return_type.ffi_type().clone()),
+        arguments: func_arguments,
+        allows_variadic_arguments: false,
+    };
+
+    Ok(CppVirtualFunctionOverride {
+        function: function.clone(),
+        callback_name,
+        arguments,
+        return_type,
+        function_type,
+    })
+}
+
+/// Generates subclass wrapper for `base_class`.
+fn generate_subclass_wrapper(
+    db: &DatabaseClient,
+    base_class: &CppPath,
+    flag_containers: &[CppFlagContainer],
+    name_provider: &mut FfiNameProvider,
+) -> Result<CppSubclassWrapper> {
+    let classes = class_and_bases(db, base_class);
+
+    let mut constructors: Vec<CppFunction> = Vec::new();
+    let base_constructors = member_functions(db, &classes[..1], |f| {
+        f.is_constructor()
+            && !f.is_private()
+            && !f.is_copy_constructor()
+            && !f.is_move_constructor()
+            && is_supported(f)
+    });
+    for constructor in base_constructors {
+        // default values can't be forwarded, so a constructor is added
+        // for each number of passed arguments
+        let required_count = constructor
+            .arguments
+            .iter()
+            .take_while(|arg| !arg.has_default_value)
+            .count();
+        for count in required_count..=constructor.arguments.len() {
+            let mut variant = constructor.clone();
+            variant.arguments.truncate(count);
+            for arg in &mut variant.arguments {
+                arg.has_default_value = false;
+            }
+            if !constructors
+                .iter()
+                .any(|c| c.argument_types_equal(&variant))
+            {
+                constructors.push(variant);
+            }
+        }
+    }
+    if constructors.is_empty() {
+        bail!("no accessible constructors");
+    }
+
+    let virtual_functions = member_functions(db, &classes, |f| {
+        f.is_virtual() && !f.is_destructor() && !f.is_signal()
+    });
+    let mut overrides = Vec::new();
+    let mut callback_names = HashSet::new();
+    for function in virtual_functions {
+        let is_pure_virtual = function
+            .member
+            .as_ref()
+            .map_or(false, |m| m.is_pure_virtual);
+        if function.is_private() && !is_pure_virtual {
+            // the base implementation can't be called from the subclass
+            continue;
+        }
+        let name = &function.path.last().name;
+        let mut callback_name = name.clone();
+        let mut num = 1;
+        while callback_names.contains(&callback_name) {
+            callback_name = format!("{}_{}", name, num);
+            num += 1;
+        }
+
+        let result = if is_supported(function) {
+            generate_override(function, callback_name.clone(), flag_containers)
+        } else {
+            Err(err_msg("unsupported function signature"))
+        };
+        match result {
+            Ok(item) => {
+                callback_names.insert(callback_name);
+                overrides.push(item);
+            }
+            Err(err) => {
+                if is_pure_virtual {
+                    bail!(
+                        "can't override pure virtual function {}: {}",
+                        function.short_text(),
+                        err
+                    );
+                }
+                trace!("skipping override of {}: {}", function.short_text(), err);
+            }
+        }
+    }
+    if overrides.is_empty() {
+        bail!("no virtual functions to override");
+    }
+
+    let protected_functions = member_functions(db, &classes, |f| {
+        f.member.as_ref().map_or(false, |m| {
+            m.visibility == CppVisibility::Protected && !m.is_virtual && !m.is_signal
+        }) && !f.is_constructor()
+            && !f.is_destructor()
+            && is_supported(f)
+    })
+    .into_iter()
+    .cloned()
+    .collect();
+
+    Ok(CppSubclassWrapper {
+        base_class: base_class.clone(),
+        class_path: name_provider.create_path(&format!("{}_subclass", base_class.ascii_caption())),
+        constructors,
+        overrides,
+        protected_functions,
+    })
+}
+
+/// Adds subclass wrappers for classes requested in the config.
+pub fn run(data: &mut ProcessorData<'_>) -> Result<()> {
+    let mut name_provider = FfiNameProvider::new(data);
+    for base_class in data.config.subclass_wrappers() {
+        let found = data
+            .db
+            .all_ffi_items()
+            .filter_map(|item| item.item.as_subclass_wrapper_ref())
+            .any(|item| &item.base_class == base_class);
+        if found {
+            trace!(
+                "subclass wrapper already exists: {}",
+                base_class.to_cpp_pseudo_code()
+            );
+            continue;
+        }
+
+        match generate_subclass_wrapper(
+            data.db,
+            base_class,
+            data.config.flag_containers(),
+            &mut name_provider,
+        ) {
+            Ok(wrapper) => {
+                let id = data
+                    .db
+                    .add_ffi_item(None, CppFfiItem::SubclassWrapper(wrapper))?;
+                if id.is_some() {
+                    trace!(
+                        "adding subclass wrapper for {}",
+                        base_class.to_cpp_pseudo_code()
+                    );
+                }
+            }
+            Err(err) => {
+                debug!(
+                    "failed to add subclass wrapper for {}: {}",
+                    base_class.to_cpp_pseudo_code(),
+                    err
+                );
+            }
+        }
+    }
+    Ok(())
+}
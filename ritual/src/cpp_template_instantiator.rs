@@ -48,6 +48,7 @@ pub fn instantiate_function(
         new_method.arguments.push(CppFunctionArgument {
             name: arg.name.clone(),
             has_default_value: arg.has_default_value,
+            type_alias: arg.type_alias.clone(),
             argument_type: arg.argument_type.instantiate(nested_level, arguments)?,
         });
     }
@@ -58,6 +58,25 @@ pub struct CppFunctionPointerType {
     pub allows_variadic_arguments: bool,
 }
 
+impl CppFunctionPointerType {
+    /// Returns C++ code representing the function type (e.g. `int(bool)`)
+    /// corresponding to this function pointer type.
+    pub fn to_cpp_function_type_code(&self) -> Result<String> {
+        let mut arg_texts = Vec::new();
+        for arg in &self.arguments {
+            arg_texts.push(arg.to_cpp_code(None)?);
+        }
+        if self.allows_variadic_arguments {
+            arg_texts.push("...".to_string());
+        }
+        Ok(format!(
+            "{}({})",
+            self.return_type.to_cpp_code(None)?,
+            arg_texts.join(", ")
+        ))
+    }
+}
+
 /// Information about a numeric C++ type that is
 /// guaranteed to be the same on all platforms,
 /// e.g. `uint32_t`.
@@ -88,6 +107,46 @@ pub struct CppTemplateParameter {
     pub name: String,
 }
 
+/// Value of a non-type template argument, like `4` in `std::array<int, 4>`.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Serialize, Deserialize)]
+pub enum CppTemplateArgumentValue {
+    /// Integer constant
+    Integer(i64),
+    /// Boolean constant
+    Bool(bool),
+    /// Enum constant, like `Qt::AlignLeft`
+    EnumValue {
+        /// Path of the enumerator, including the enum's path
+        path: CppPath,
+    },
+}
+
+impl CppTemplateArgumentValue {
+    /// Returns C++ code representing this value.
+    pub fn to_cpp_code(&self) -> Result<String> {
+        match self {
+            CppTemplateArgumentValue::Integer(value) => Ok(value.to_string()),
+            CppTemplateArgumentValue::Bool(value) => Ok(value.to_string()),
+            CppTemplateArgumentValue::EnumValue { path } => path.to_cpp_code(),
+        }
+    }
+
+    /// Returns a string that can be used as part of an identifier.
+    pub fn ascii_caption(&self) -> String {
+        match self {
+            CppTemplateArgumentValue::Integer(value) => {
+                if *value < 0 {
+                    format!("minus_{}", value.unsigned_abs())
+                } else {
+                    value.to_string()
+                }
+            }
+            CppTemplateArgumentValue::Bool(value) => value.to_string(),
+            CppTemplateArgumentValue::EnumValue { path } => path.last().name.clone(),
+        }
+    }
+}
+
 /// Base C++ type. `CppType` can add indirection
 /// and constness to `CppTypeBase`, but otherwise
 /// this enum lists all supported types.
@@ -115,6 +174,9 @@ pub enum CppType {
     TemplateParameter(CppTemplateParameter),
     /// Function pointer type
     FunctionPointer(CppFunctionPointerType),
+    /// Value of a non-type template argument. This is not a real type
+    /// and can only appear in `CppPathItem::template_arguments`.
+    TemplateArgumentValue(CppTemplateArgumentValue),
     PointerLike {
         kind: CppPointerLikeTypeKind,
         is_const: bool,
@@ -222,6 +284,15 @@ impl CppType {
         matches!(self, CppType::FunctionPointer(..))
     }
 
+    /// Returns true if this is the `va_list` type.
+    pub fn is_va_list(&self) -> bool {
+        if let CppType::Class(path) = self {
+            path.to_templateless_string() == "va_list"
+        } else {
+            false
+        }
+    }
+
     pub fn is_pointer(&self) -> bool {
         match self {
             CppType::PointerLike { kind, .. } => *kind == CppPointerLikeTypeKind::Pointer,
@@ -296,18 +367,19 @@ impl CppType {
             CppType::TemplateParameter { .. } => {
                 bail!("template parameters are not allowed in C++ code generator");
             }
+            CppType::TemplateArgumentValue(value) => value.to_cpp_code(),
             CppType::FunctionPointer(CppFunctionPointerType {
                 return_type,
                 arguments,
                 allows_variadic_arguments,
             }) => {
-                if *allows_variadic_arguments {
-                    bail!("function pointers with variadic arguments are not supported");
-                }
                 let mut arg_texts = Vec::new();
                 for arg in arguments {
                     arg_texts.push(arg.to_cpp_code(None)?);
                 }
+                if *allows_variadic_arguments {
+                    arg_texts.push("...".to_string());
+                }
                 if let Some(function_pointer_inner_text) = function_pointer_inner_text {
                     Ok(format!(
                         "{} (*{})({})",
@@ -380,6 +452,7 @@ impl CppType {
             | CppType::Enum { path }
             | CppType::Class(path) => path.ascii_caption(),
             CppType::TemplateParameter(param) => param.name.to_string(),
+            CppType::TemplateArgumentValue(value) => value.ascii_caption(),
             CppType::FunctionPointer(_) => "fn".into(),
             CppType::PointerLike {
                 kind,
@@ -442,7 +515,87 @@ pub fn is_qflags(path: &CppPath) -> bool {
             .map_or(false, |args| args.len() == 1)
 }
 
+/// Returns true if `path` refers to `name` in the `std` namespace or
+/// in one of its inline namespaces (like `std::__1`).
+fn is_std_item(path: &CppPath, name: &str) -> bool {
+    let items = path.items();
+    items.len() >= 2
+        && items[0].name == "std"
+        && items[0].template_arguments.is_none()
+        && items[1..items.len() - 1]
+            .iter()
+            .all(|item| item.name.starts_with("__") && item.template_arguments.is_none())
+        && path.last().name == name
+}
+
+/// Returns the function type of `std::function<F>` if `path` is an instantiation
+/// of `std::function`. The function type is represented as a function pointer type.
+pub fn std_function_type(path: &CppPath) -> Option<&CppFunctionPointerType> {
+    if !is_std_item(path, "function") {
+        return None;
+    }
+    match path.last().template_arguments.as_ref()?.as_slice() {
+        [CppType::FunctionPointer(function_type)] => Some(function_type),
+        _ => None,
+    }
+}
+
+/// Kind of a standard library smart pointer
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Serialize, Deserialize)]
+pub enum CppSmartPointerKind {
+    /// `std::shared_ptr<T>`
+    Shared,
+    /// `std::unique_ptr<T>` with the default deleter
+    Unique,
+}
+
+impl CppSmartPointerKind {
+    /// Returns name of the class template in the `std` namespace.
+    pub fn template_name(self) -> &'static str {
+        match self {
+            CppSmartPointerKind::Shared => "shared_ptr",
+            CppSmartPointerKind::Unique => "unique_ptr",
+        }
+    }
+}
+
+/// Returns the kind and the target class type if `path` is an instantiation
+/// of `std::shared_ptr` or `std::unique_ptr` with a class type argument.
+/// `std::unique_ptr` is only recognized if it uses the default deleter.
+pub fn smart_pointer_type(path: &CppPath) -> Option<(CppSmartPointerKind, &CppType)> {
+    let kind = [CppSmartPointerKind::Shared, CppSmartPointerKind::Unique]
+        .iter()
+        .copied()
+        .find(|kind| is_std_item(path, kind.template_name()))?;
+    let target = match path.last().template_arguments.as_ref()?.as_slice() {
+        [target] => target,
+        [target, CppType::Class(deleter)] if kind == CppSmartPointerKind::Unique => {
+            let is_default_deleter = is_std_item(deleter, "default_delete")
+                && deleter.last().template_arguments.as_deref()
+                    == Some(std::slice::from_ref(target));
+            if !is_default_deleter {
+                return None;
+            }
+            target
+        }
+        _ => return None,
+    };
+    if !target.is_class() {
+        return None;
+    }
+    Some((kind, target))
+}
+
 impl CppType {
+    /// Returns true if this is a `std::unique_ptr` instantiation.
+    pub fn is_unique_ptr(&self) -> bool {
+        if let CppType::Class(path) = self {
+            smart_pointer_type(path).map_or(false, |(kind, _)| kind == CppSmartPointerKind::Unique)
+        } else {
+            false
+        }
+    }
+
     pub fn contains_reference(&self) -> bool {
         if let CppType::PointerLike { kind, target, .. } = self {
             match *kind {
@@ -354,8 +354,10 @@ impl IndexedDatabase {
                 .entry(item.source_id.clone())
                 .or_default()
                 .push(index);
-            if let Some(path) = item.item.as_rust_item().and_then(|item| item.path()) {
-                self.rust_path_to_index.insert(path.clone(), index);
+            if let Some(rust_item) = item.item.as_rust_item() {
+                for path in rust_item.reserved_paths() {
+                    self.rust_path_to_index.insert(path.clone(), index);
+                }
             }
             if let Some(path) = item.item.as_cpp_item().and_then(|item| item.path()) {
                 self.cpp_path_to_index
@@ -372,8 +374,10 @@ impl IndexedDatabase {
             .entry(item.source_id.clone())
             .or_default()
             .push(index);
-        if let Some(path) = item.item.as_rust_item().and_then(|item| item.path()) {
-            self.rust_path_to_index.insert(path.clone(), index);
+        if let Some(rust_item) = item.item.as_rust_item() {
+            for path in rust_item.reserved_paths() {
+                self.rust_path_to_index.insert(path.clone(), index);
+            }
         }
         if let Some(path) = item.item.as_cpp_item().and_then(|item| item.path()) {
             self.cpp_path_to_index
@@ -944,6 +948,10 @@ impl DatabaseClient {
         self.all_databases().flat_map(|d| d.db.ffi_items())
     }
 
+    pub fn all_rust_items(&self) -> impl Iterator<Item = DbItem<&RustItem>> {
+        self.all_databases().flat_map(|d| d.db.rust_items())
+    }
+
     pub fn find_rust_items_for_cpp_path(
         &self,
         cpp_path: &CppPath,
@@ -8,12 +8,15 @@ use crate::cpp_type::CppType;
 use crate::database::{DatabaseClient, DbItem, DocItem};
 use crate::rust_code_generator::rust_type_to_code;
 use crate::rust_info::{
-    RustEnumValue, RustFunction, RustFunctionKind, RustModule, RustModuleKind, RustQtReceiverType,
-    RustSpecialModuleKind, RustStruct, RustStructKind, RustWrapperTypeKind,
+    RustConstant, RustEnumValue, RustFunction, RustFunctionKind, RustModule, RustModuleKind,
+    RustQtReceiverType, RustReexport, RustSpecialModuleKind, RustStruct, RustStructKind,
+    RustTypeAlias, RustWrapperTypeKind,
 };
+use crate::rust_type::RustToFfiTypeConversion;
 use itertools::Itertools;
 use ritual_common::errors::{bail, err_msg, Result};
 use std::fmt::Write;
+use std::path::Path;
 
 pub fn wrap_inline_cpp_code(code: &str) -> String {
     format!("<span style='color: green;'>```{}```</span>", code)
@@ -55,6 +58,12 @@ pub fn module_doc(module: DbItem<&RustModule>, database: &DatabaseClient) -> Res
                     "Types with the same size and alignment as corresponding C++ types"
                 )?;
             }
+            RustSpecialModuleKind::Overloading => {
+                write!(
+                    output,
+                    "Traits that allow to call overloaded C++ functions by their original names"
+                )?;
+            }
         },
         RustModuleKind::CppNamespace { .. } => {
             let cpp_item = database
@@ -115,7 +124,7 @@ pub fn struct_doc(type1: DbItem<&RustStruct>, database: &DatabaseClient) -> Resu
                 .to_cpp_pseudo_code();
 
             match kind {
-                RustWrapperTypeKind::EnumWrapper => {
+                RustWrapperTypeKind::EnumWrapper { .. } => {
                     writeln!(
                         output,
                         "C++ enum: {}.\n",
@@ -168,7 +177,9 @@ pub fn struct_doc(type1: DbItem<&RustStruct>, database: &DatabaseClient) -> Resu
                     .item;
 
                 let cpp_signal_arguments = match ffi_item {
-                    CppFfiItem::Function(_) => bail!("invalid source ffi item type"),
+                    CppFfiItem::Function(_) | CppFfiItem::SubclassWrapper(_) => {
+                        bail!("invalid source ffi item type")
+                    }
                     CppFfiItem::QtSlotWrapper(w) => &w.signal_arguments,
                     CppFfiItem::QtSignalWrapper(w) => &w.signal_arguments,
                 };
@@ -214,6 +225,20 @@ pub fn struct_doc(type1: DbItem<&RustStruct>, database: &DatabaseClient) -> Resu
                     }
                 }
             }
+
+            if let Some(thread_safety) = &type1.item.thread_safety {
+                let traits = match (thread_safety.is_send, thread_safety.is_sync) {
+                    (true, true) => "`Send` and `Sync`",
+                    (true, false) => "`Send`",
+                    (false, true) => "`Sync`",
+                    (false, false) => "neither `Send` nor `Sync`",
+                };
+                writeln!(output, "This type implements {}.", traits)?;
+                if !thread_safety.rationale.is_empty() {
+                    writeln!(output, "{}", thread_safety.rationale)?;
+                }
+                writeln!(output)?;
+            }
         }
         RustStructKind::QtSlotWrapper(_) => {
             bail!("RustStructKind::QtSlotWrapper is deprecated");
@@ -250,6 +275,78 @@ pub fn enum_value_doc(value: DbItem<&RustEnumValue>, database: &DatabaseClient)
     Ok(doc)
 }
 
+pub fn type_alias_doc(alias: DbItem<&RustTypeAlias>, database: &DatabaseClient) -> Result<String> {
+    let cpp_item = database
+        .source_cpp_item(&alias.id)?
+        .ok_or_else(|| err_msg("source cpp item not found"))?
+        .item
+        .as_type_alias_ref()
+        .ok_or_else(|| err_msg("invalid source cpp item type"))?;
+
+    let mut output = format!(
+        "C++ type alias: {}.\n\n",
+        wrap_inline_cpp_code(&cpp_item.path.to_cpp_pseudo_code())
+    );
+    if let Some(doc_item) = database.find_doc_for(&alias.id)? {
+        write!(output, "{}", format_doc_item(doc_item.item))?;
+    }
+    Ok(output)
+}
+
+pub fn constant_doc(constant: DbItem<&RustConstant>, database: &DatabaseClient) -> Result<String> {
+    let cpp_item = database
+        .source_cpp_item(&constant.id)?
+        .ok_or_else(|| err_msg("source cpp item not found"))?
+        .item
+        .as_constant_ref()
+        .ok_or_else(|| err_msg("invalid source cpp item type"))?;
+
+    let file_name = Path::new(&cpp_item.origin_location.include_file_path)
+        .file_name()
+        .map_or_else(
+            || cpp_item.origin_location.include_file_path.clone(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+    let mut output = format!(
+        "C++ {}: {} (defined in `{}`, line {}).\n\n",
+        if cpp_item.is_macro {
+            "macro"
+        } else {
+            "constant"
+        },
+        wrap_inline_cpp_code(&cpp_item.path.to_cpp_pseudo_code()),
+        file_name,
+        cpp_item.origin_location.line
+    );
+    if let Some(doc_item) = database.find_doc_for(&constant.id)? {
+        write!(output, "{}", format_doc_item(doc_item.item))?;
+    }
+    Ok(output)
+}
+
+pub fn variadic_function_reexport_doc(
+    reexport: DbItem<&RustReexport>,
+    database: &DatabaseClient,
+) -> Result<String> {
+    let cpp_item = database
+        .source_cpp_item(&reexport.id)?
+        .ok_or_else(|| err_msg("source cpp item not found"))?
+        .item
+        .as_function_ref()
+        .ok_or_else(|| err_msg("invalid source cpp item type"))?;
+
+    let mut output = format!(
+        "Calls C++ function: {}.\n\n\
+         Variadic arguments are passed as is, so their types must match \
+         the types expected by the C++ function after default argument promotions.\n\n",
+        wrap_inline_cpp_code(&cpp_item.short_text())
+    );
+    if let Some(doc_item) = database.find_doc_for(&reexport.id)? {
+        write!(output, "{}", format_doc_item(doc_item.item))?;
+    }
+    Ok(output)
+}
+
 fn format_maybe_link(url: &Option<String>, text: &str) -> String {
     if let Some(url) = url {
         format!("<a href=\"{}\">{}</a>", url, text)
@@ -367,6 +464,21 @@ pub fn function_doc(function: DbItem<&RustFunction>, database: &DatabaseClient)
                 .as_function_ref()
                 .ok_or_else(|| err_msg("invalid source ffi item type"))?;
 
+            let upcast_self = function.item.arguments.iter().any(|arg| {
+                matches!(
+                    arg.argument_type.conversion(),
+                    RustToFfiTypeConversion::UpcastRefToPtr { .. }
+                )
+            });
+            if upcast_self {
+                write!(
+                    output,
+                    "This function is inherited from a base class that is not accessible \
+                     through `Deref`. `self` is converted to the base class using \
+                     `StaticUpcast`.\n\n"
+                )?;
+            }
+
             match &cpp_ffi_function.kind {
                 CppFfiFunctionKind::Function => {
                     let cpp_item = cpp_item
@@ -378,6 +490,67 @@ pub fn function_doc(function: DbItem<&RustFunction>, database: &DatabaseClient)
                         "Calls C++ function: {}.\n\n",
                         wrap_inline_cpp_code(&cpp_item.short_text())
                     )?;
+                    let return_type = &function.item.return_type;
+                    let return_conversion = if return_type.conversion().is_exception_result() {
+                        return_type.without_exception_result()?.conversion().clone()
+                    } else {
+                        return_type.conversion().clone()
+                    };
+                    let status_conversion =
+                        if let Some(conversion) = return_conversion.as_out_arguments_ref() {
+                            &conversion.return_conversion
+                        } else {
+                            &return_conversion
+                        };
+                    if let RustToFfiTypeConversion::StatusResult { success } = status_conversion {
+                        write!(
+                            output,
+                            "The value returned by the C++ function is a status. \
+                             Returns `Err` unless the status {}.\n\n",
+                            success.description()
+                        )?;
+                    }
+                    for arg in &function.item.arguments {
+                        if let Some(conversion) = arg.argument_type.conversion().as_callback_ref() {
+                            if conversion.is_leaked {
+                                write!(
+                                    output,
+                                    "The closure passed in `{}` is never dropped because \
+                                     the C++ function may store the callback.\n\n",
+                                    arg.name
+                                )?;
+                            } else if conversion.deleter_ffi_index.is_none() {
+                                write!(
+                                    output,
+                                    "The closure passed in `{}` is dropped when \
+                                     the function returns.\n\n",
+                                    arg.name
+                                )?;
+                            }
+                        }
+                    }
+                    if let Some(conversion) = return_conversion.as_out_arguments_ref() {
+                        let names = conversion
+                            .arguments
+                            .iter()
+                            .map(|arg| format!("`{}`", arg.name))
+                            .join(", ");
+                        if !names.is_empty() {
+                            write!(
+                                output,
+                                "Values written to output arguments ({}) \
+                                 are included in the returned value.\n\n",
+                                names
+                            )?;
+                        }
+                        if let Some(ok_flag) = &conversion.ok_flag {
+                            write!(
+                                output,
+                                "Returns `None` if the function sets `{}` to `false`.\n\n",
+                                ok_flag.name
+                            )?;
+                        }
+                    }
 
                     // TODO: detect omitted arguments using source_id
                     /*if let Some(arguments_before_omitting) =
@@ -416,6 +589,55 @@ pub fn function_doc(function: DbItem<&RustFunction>, database: &DatabaseClient)
                         }
                     };
                 }
+                CppFfiFunctionKind::VariableAccessor { accessor_type } => {
+                    let cpp_item = cpp_item
+                        .item
+                        .as_variable_ref()
+                        .ok_or_else(|| err_msg("invalid source cpp item type"))?;
+                    let variable_text = wrap_inline_cpp_code(&cpp_item.path.to_cpp_pseudo_code());
+                    match *accessor_type {
+                        CppFieldAccessorType::CopyGetter => {
+                            write!(output, "Returns the value of {}.", variable_text)?;
+                        }
+                        CppFieldAccessorType::ConstRefGetter => {
+                            write!(output, "Returns a reference to {}.", variable_text)?;
+                        }
+                        CppFieldAccessorType::MutRefGetter => {
+                            write!(output, "Returns a mutable reference to {}.", variable_text)?;
+                        }
+                        CppFieldAccessorType::Setter => {
+                            write!(output, "Sets the value of {}.", variable_text)?;
+                        }
+                    };
+                }
+                CppFfiFunctionKind::StreamToString => {
+                    let cpp_item = cpp_item
+                        .item
+                        .as_function_ref()
+                        .ok_or_else(|| err_msg("invalid source cpp item type"))?;
+                    write!(
+                        output,
+                        "Returns the text written by C++ function: {}.\n\n",
+                        wrap_inline_cpp_code(&cpp_item.short_text())
+                    )?;
+                }
+                CppFfiFunctionKind::SmartPointer { .. } => {
+                    bail!("smart pointer functions are not exposed in the public API");
+                }
+                CppFfiFunctionKind::StdHash => {
+                    let cpp_item = cpp_item
+                        .item
+                        .as_type_ref()
+                        .ok_or_else(|| err_msg("invalid source cpp item type"))?;
+                    write!(
+                        output,
+                        "Returns the hash of the value calculated by {}.\n\n",
+                        wrap_inline_cpp_code(&format!(
+                            "std::hash<{}>",
+                            cpp_item.path.to_cpp_pseudo_code()
+                        ))
+                    )?;
+                }
             }
         }
         RustFunctionKind::SignalOrSlotGetter(_) => {
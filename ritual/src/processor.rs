@@ -5,7 +5,7 @@ use crate::database::{DatabaseClient, ItemId};
 use crate::workspace::Workspace;
 use crate::{
     cpp_casts, cpp_checker, cpp_ffi_generator, cpp_implicit_methods, cpp_omitting_arguments,
-    cpp_parser, cpp_template_instantiator, crate_writer, rust_generator,
+    cpp_parser, cpp_subclass_wrappers, cpp_template_instantiator, crate_writer, rust_generator,
 };
 use itertools::Itertools;
 use log::debug;
@@ -105,6 +105,10 @@ impl Default for ProcessingSteps {
                 &format!("instantiate_templates{}", suffix),
                 cpp_template_instantiator::instantiate_templates,
             );
+            s.push(
+                &format!("add_std_function_call_operators{}", suffix),
+                cpp_implicit_methods::add_std_function_call_operators,
+            );
             s.push(
                 &format!("omitting_arguments{}", suffix),
                 cpp_omitting_arguments::run,
@@ -114,6 +118,10 @@ impl Default for ProcessingSteps {
                 &format!("cpp_ffi_generator{}", suffix),
                 cpp_ffi_generator::run,
             );
+            s.push(
+                &format!("cpp_subclass_wrappers{}", suffix),
+                cpp_subclass_wrappers::run,
+            );
             s.push(&format!("cpp_checker{}", suffix), |data| {
                 cpp_checker::run(data, false)
             });
@@ -1,31 +1,67 @@
 //! Types and functions used for Rust code generation.
 
 use crate::cpp_checks::Condition;
-use crate::cpp_ffi_data::{CppFfiArgumentMeaning, CppFfiItem};
+use crate::cpp_data::CppPath;
+use crate::cpp_ffi_data::{CppFfiArgumentMeaning, CppFfiItem, CppSmartPointerOperation};
 use crate::cpp_function::CppFunction;
+use crate::cpp_type::CppSmartPointerKind;
 use crate::database::{DatabaseClient, DbItem, ItemId};
 use crate::doc_formatter;
 use crate::rust_generator::qt_core_path;
 use crate::rust_info::{
-    RustEnumValue, RustExtraImpl, RustExtraImplKind, RustFfiWrapperData, RustFunction,
-    RustFunctionArgument, RustFunctionKind, RustItem, RustModule, RustModuleKind,
-    RustQtReceiverType, RustSpecialModuleKind, RustStruct, RustStructKind, RustTraitImpl,
-    RustWrapperTypeKind,
+    RustConstant, RustEnumValue, RustExtraImpl, RustExtraImplKind, RustFfiWrapperData,
+    RustFfiWrapperVariant, RustFunction, RustFunctionArgument, RustFunctionKind,
+    RustFunctionSelfArgKind, RustItem, RustModule, RustModuleKind, RustOverloadDispatchImpl,
+    RustQtReceiverType, RustReexportSource, RustSmartPointerTargetImpl, RustSpecialModuleKind,
+    RustStruct, RustStructKind, RustSubclassOverridesImpl, RustTemplateTraitImpl,
+    RustTemplateTraitMethod, RustTraitImpl, RustTypeAlias, RustWrapperTypeKind,
 };
 use crate::rust_type::{
     RustClosureToCallbackConversion, RustCommonType, RustFinalType, RustPath,
     RustPointerLikeTypeKind, RustToFfiTypeConversion, RustType,
 };
 use itertools::Itertools;
+use log::debug;
 use ritual_common::errors::{bail, err_msg, format_err, Result};
 use ritual_common::file_utils::{create_dir_all, create_file, file_to_string, File};
 use ritual_common::string_utils::trim_slice;
 use ritual_common::utils::MapIfOk;
+use std::borrow::Cow;
 use std::fmt::Write as _;
 use std::fs;
 use std::io::{self, BufWriter, Write};
+use std::iter::once;
 use std::path::{Path, PathBuf};
 
+/// Returns the range of values of the integer type `int_type`
+/// if it's the same on all platforms.
+fn integer_type_range(int_type: &RustType) -> Option<(i128, i128)> {
+    let name = match int_type {
+        RustType::Primitive(name) => name.as_str(),
+        RustType::Common(RustCommonType {
+            path,
+            generic_arguments: None,
+        }) if path.parent_parts().ok()? == ["std", "os", "raw"] => path.last(),
+        _ => return None,
+    };
+    let (bits, is_signed) = match name {
+        "i8" | "c_schar" => (8, true),
+        "u8" | "c_uchar" => (8, false),
+        "i16" | "c_short" => (16, true),
+        "u16" | "c_ushort" => (16, false),
+        "i32" | "c_int" => (32, true),
+        "u32" | "c_uint" => (32, false),
+        "i64" | "c_longlong" => (64, true),
+        "u64" | "c_ulonglong" => (64, false),
+        _ => return None,
+    };
+    Some(if is_signed {
+        (-(1_i128 << (bits - 1)), (1_i128 << (bits - 1)) - 1)
+    } else {
+        (0, (1_i128 << bits) - 1)
+    })
+}
+
 fn wrap_unsafe(in_unsafe_context: bool, content: &str) -> String {
     let (unsafe_start, unsafe_end) = if in_unsafe_context {
         ("", "")
@@ -96,6 +132,11 @@ pub fn rust_type_to_code(rust_type: &RustType, current_crate: Option<&str>) -> S
                 .arguments
                 .iter()
                 .map(|arg| rust_type_to_code(arg, current_crate))
+                .chain(if function.is_variadic {
+                    Some("...".to_string())
+                } else {
+                    None
+                })
                 .join(", "),
             if function.return_type.is_unit() {
                 String::new()
@@ -110,6 +151,7 @@ pub fn rust_type_to_code(rust_type: &RustType, current_crate: Option<&str>) -> S
             "impl {}",
             rust_common_type_to_code(trait_type, current_crate)
         ),
+        RustType::Slice(item_type) => format!("[{}]", rust_type_to_code(item_type, current_crate)),
     }
 }
 
@@ -281,28 +323,7 @@ impl Generator<'_> {
             }
         }
 
-        let ffi_item = self
-            .current_database
-            .source_ffi_item(&item_for_condition.id)?;
-
-        let mut condition_texts = ConditionTexts::default();
-
-        if let Some(ffi_item) = ffi_item {
-            let condition = self
-                .current_database
-                .cpp_checks(&ffi_item.id)?
-                .condition(self.current_database.environments());
-            if condition != Condition::True {
-                let expression = condition_expression(&condition);
-                condition_texts.attribute = format!(
-                    "#[cfg_attr(feature = \"ritual_rustdoc_nightly\", doc(cfg({})))]\n\
-                    #[cfg(any({}, feature = \"ritual_rustdoc\"))]\n",
-                    expression, expression
-                );
-                // condition_texts.doc_text =
-                // format!("\n\nThis item is available if `{}`.", expression);
-            }
-        }
+        let condition_texts = self.condition_texts(&item_for_condition.id)?;
 
         match &item.item {
             RustItem::Module(_) => self.generate_module(item.map(|i| i.as_module_ref().unwrap())),
@@ -327,15 +348,55 @@ impl Generator<'_> {
                 &condition_texts,
             ),
             RustItem::Reexport(reexport) => {
+                if reexport.source == RustReexportSource::VariadicFunction {
+                    let doc = doc_formatter::variadic_function_reexport_doc(
+                        item.clone().map(|i| i.as_reexport_ref().unwrap()),
+                        self.current_database,
+                    )?;
+                    write!(self, "{}", format_doc(&doc))?;
+                }
                 writeln!(
                     self,
-                    "pub use {} as {};",
+                    "{}pub use {} as {};",
+                    condition_texts.attribute,
                     self.rust_path_to_string(&reexport.target),
                     reexport.path.last()
                 )?;
                 Ok(())
             }
+            RustItem::TypeAlias(_) => {
+                self.generate_type_alias(item.map(|i| i.as_type_alias_ref().unwrap()))
+            }
+            RustItem::Constant(_) => {
+                self.generate_constant(item.map(|i| i.as_constant_ref().unwrap()))
+            }
+        }
+    }
+
+    /// Returns conditional compilation attribute for the Rust item `id`
+    /// based on the results of C++ checks of its source FFI item.
+    fn condition_texts(&self, id: &ItemId) -> Result<ConditionTexts> {
+        let ffi_item = self.current_database.source_ffi_item(id)?;
+
+        let mut condition_texts = ConditionTexts::default();
+
+        if let Some(ffi_item) = ffi_item {
+            let condition = self
+                .current_database
+                .cpp_checks(&ffi_item.id)?
+                .condition(self.current_database.environments());
+            if condition != Condition::True {
+                let expression = condition_expression(&condition);
+                condition_texts.attribute = format!(
+                    "#[cfg_attr(feature = \"ritual_rustdoc_nightly\", doc(cfg({})))]\n\
+                    #[cfg(any({}, feature = \"ritual_rustdoc\"))]\n",
+                    expression, expression
+                );
+                // condition_texts.doc_text =
+                // format!("\n\nThis item is available if `{}`.", expression);
+            }
         }
+        Ok(condition_texts)
     }
 
     fn rust_type_to_code(&self, rust_type: &RustType) -> String {
@@ -413,6 +474,7 @@ impl Generator<'_> {
             }
             RustModuleKind::Special(RustSpecialModuleKind::CrateRoot)
             | RustModuleKind::Special(RustSpecialModuleKind::Ops)
+            | RustModuleKind::Special(RustSpecialModuleKind::Overloading)
             | RustModuleKind::CppNamespace { .. }
             | RustModuleKind::CppNestedTypes { .. } => {
                 self.generate_children(&module.item.path, None)?;
@@ -467,28 +529,58 @@ impl Generator<'_> {
         };
         match &rust_struct.item.kind {
             RustStructKind::WrapperType(kind) => match kind {
-                RustWrapperTypeKind::EnumWrapper => {
+                RustWrapperTypeKind::EnumWrapper { underlying_type } => {
+                    let mut values = Vec::new();
+                    let mut variants = Vec::new();
+                    for item in self.current_database.rust_children(&rust_struct.item.path) {
+                        if let Some(value) = item.item.as_enum_value_ref() {
+                            if values.contains(&value.value) {
+                                continue;
+                            }
+                            values.push(value.value);
+                            let cpp_name = self
+                                .current_database
+                                .source_cpp_item(&item.id)?
+                                .and_then(|cpp_item| cpp_item.item.as_enum_value_ref())
+                                .map_or_else(
+                                    || value.path.last().to_string(),
+                                    |cpp_value| cpp_value.path.last().name.clone(),
+                                );
+                            variants.push(format!(
+                                "({name}::{value}, {cpp_name:?})",
+                                name = rust_struct.item.path.last(),
+                                value = value.path.last(),
+                                cpp_name = cpp_name,
+                            ));
+                        }
+                    }
                     writeln!(
                         self,
                         include_str!("../templates/crate/enum_wrapper.rs.in"),
                         vis = visibility,
-                        name = rust_struct.item.path.last()
+                        name = rust_struct.item.path.last(),
+                        int_type = self.rust_type_to_code(underlying_type),
+                        variants = variants.join(", "),
                     )?;
                 }
                 RustWrapperTypeKind::ImmovableClassWrapper => {
                     writeln!(self, "#[repr(C)]")?;
+                    // the marker makes the type `!Send` and `!Sync` unless
+                    // thread safety is declared in the config
                     writeln!(
                         self,
-                        "{}struct {} {{ _unused: u8, }}",
+                        "{}struct {} {{ _unused: u8, \
+                         _marker: ::std::marker::PhantomData<*mut ()>, }}",
                         visibility,
                         rust_struct.item.path.last()
                     )?;
                 }
                 RustWrapperTypeKind::MovableClassWrapper { sized_type_path } => {
                     writeln!(self, "#[repr(transparent)]")?;
+                    // see the comment for `ImmovableClassWrapper`
                     writeln!(
                         self,
-                        "{}struct {}({});",
+                        "{}struct {}({}, ::std::marker::PhantomData<*mut ()>);",
                         visibility,
                         rust_struct.item.path.last(),
                         self.rust_path_to_string(sized_type_path),
@@ -504,6 +596,24 @@ impl Generator<'_> {
             }
         }
 
+        if let Some(thread_safety) = &rust_struct.item.thread_safety {
+            if thread_safety.is_send {
+                writeln!(
+                    self,
+                    "unsafe impl Send for {} {{}}",
+                    rust_struct.item.path.last()
+                )?;
+            }
+            if thread_safety.is_sync {
+                writeln!(
+                    self,
+                    "unsafe impl Sync for {} {{}}",
+                    rust_struct.item.path.last()
+                )?;
+            }
+            writeln!(self)?;
+        }
+
         if self
             .current_database
             .rust_children(&rust_struct.item.path)
@@ -540,13 +650,75 @@ impl Generator<'_> {
                 .parent()
                 .expect("enum value must have parent"),
         );
+        let underlying_type = self
+            .current_database
+            .find_rust_item(&value.item.path.parent()?)
+            .and_then(|item| item.item.as_struct_ref())
+            .and_then(|item| match &item.kind {
+                RustStructKind::WrapperType(RustWrapperTypeKind::EnumWrapper {
+                    underlying_type,
+                }) => Some(underlying_type),
+                _ => None,
+            })
+            .ok_or_else(|| err_msg("enum value must be a child of an enum wrapper"))?;
         writeln!(self, "#[allow(non_upper_case_globals)]")?;
         writeln!(
             self,
             "pub const {value_name}: {struct_path} = {struct_path}({value});",
             value_name = value.item.path.last(),
             struct_path = struct_path,
-            value = value.item.value
+            value = self.integer_literal(value.item.value, underlying_type),
+        )?;
+        Ok(())
+    }
+
+    /// Returns code of a constant of type `int_type` having the same
+    /// binary representation as `value`.
+    fn integer_literal(&self, value: i64, int_type: &RustType) -> String {
+        let fits = match integer_type_range(int_type) {
+            Some((min, max)) => min <= i128::from(value) && i128::from(value) <= max,
+            None => false,
+        };
+        if fits {
+            value.to_string()
+        } else {
+            format!("{}_i64 as {}", value, self.rust_type_to_code(int_type))
+        }
+    }
+
+    fn generate_type_alias(&mut self, alias: DbItem<&RustTypeAlias>) -> Result<()> {
+        write!(
+            self,
+            "{}",
+            format_doc(&doc_formatter::type_alias_doc(
+                alias.clone(),
+                self.current_database
+            )?)
+        )?;
+        writeln!(
+            self,
+            "pub type {} = {};",
+            alias.item.path.last(),
+            self.rust_type_to_code(&alias.item.target)
+        )?;
+        Ok(())
+    }
+
+    fn generate_constant(&mut self, constant: DbItem<&RustConstant>) -> Result<()> {
+        write!(
+            self,
+            "{}",
+            format_doc(&doc_formatter::constant_doc(
+                constant.clone(),
+                self.current_database
+            )?)
+        )?;
+        writeln!(
+            self,
+            "pub const {}: {} = {};",
+            constant.item.path.last(),
+            self.rust_type_to_code(&constant.item.constant_type),
+            constant.item.value
         )?;
         Ok(())
     }
@@ -656,11 +828,11 @@ impl Generator<'_> {
                 );
                 wrap_unsafe(in_unsafe_context, &code)
             }
-            RustToFfiTypeConversion::QFlagsToUInt { .. } => {
-                let mut qflags_type = type1.api_type().clone();
+            RustToFfiTypeConversion::FlagsToInt { .. } => {
+                let mut flags_type = type1.api_type().clone();
                 if let RustType::Common(RustCommonType {
                     generic_arguments, ..
-                }) = &mut qflags_type
+                }) = &mut flags_type
                 {
                     *generic_arguments = None;
                 } else {
@@ -668,7 +840,7 @@ impl Generator<'_> {
                 }
                 format!(
                     "{}::from({})",
-                    self.rust_type_to_code(&qflags_type),
+                    self.rust_type_to_code(&flags_type),
                     source_expr
                 )
             }
@@ -690,9 +862,30 @@ impl Generator<'_> {
             RustToFfiTypeConversion::ImplCastInto(_) => {
                 bail!("ImplCastInto is not convertable from FFI type");
             }
+            RustToFfiTypeConversion::MovedCppBoxToPtr => {
+                bail!("MovedCppBoxToPtr is not convertable from FFI type");
+            }
+            RustToFfiTypeConversion::UpcastRefToPtr { .. } => {
+                bail!("UpcastRefToPtr is not convertable from FFI type");
+            }
             RustToFfiTypeConversion::ClosureToCallback { .. } => {
                 bail!("ClosureToCallback is not convertable from FFI type");
             }
+            RustToFfiTypeConversion::ExceptionResult(_) => {
+                bail!("ExceptionResult must be handled by generate_ffi_call");
+            }
+            RustToFfiTypeConversion::OutArguments(_) => {
+                bail!("OutArguments must be handled by generate_ffi_call");
+            }
+            RustToFfiTypeConversion::SliceToPtr { .. } => {
+                bail!("SliceToPtr is not convertable from FFI type");
+            }
+            RustToFfiTypeConversion::StatusResult { success } => format!(
+                "{{ let status = {}; if {} {{ Ok(()) }} else {{ \
+                 Err(::cpp_core::StatusError::new(status)) }} }}",
+                source_expr,
+                success.condition_code("status")
+            ),
         };
         Ok(code1 + &code2)
     }
@@ -716,6 +909,20 @@ impl Generator<'_> {
                     format!("{} as {}", expr, self.rust_type_to_code(type1.ffi_type()))
                 }
             }
+            RustToFfiTypeConversion::UpcastRefToPtr { derived_type, .. } => {
+                let upcast = format!(
+                    "<{} as ::cpp_core::StaticUpcast<{}>>::static_upcast(\
+                     ::cpp_core::Ptr::from_raw({}))",
+                    self.rust_type_to_code(derived_type),
+                    self.rust_type_to_code(&type1.ffi_type().pointer_like_to_target()?),
+                    expr
+                );
+                if type1.ffi_type().is_const_pointer_like()? {
+                    format!("{}.as_raw_ptr()", upcast)
+                } else {
+                    format!("{}.as_mut_raw_ptr()", upcast)
+                }
+            }
             RustToFfiTypeConversion::ValueToPtr => {
                 let is_const = type1.ffi_type().is_const_pointer_like()?;
                 format!(
@@ -728,6 +935,9 @@ impl Generator<'_> {
             RustToFfiTypeConversion::CppBoxToPtr | RustToFfiTypeConversion::QBoxToPtr => {
                 format!("{}.into_raw_ptr()", expr)
             }
+            // the box is dropped at the end of the wrapper function,
+            // deleting the moved-from object
+            RustToFfiTypeConversion::MovedCppBoxToPtr => format!("{}.as_mut_raw_ptr()", expr),
             RustToFfiTypeConversion::UtilsPtrToPtr { .. }
             | RustToFfiTypeConversion::UtilsRefToPtr { .. }
             | RustToFfiTypeConversion::QPtrToPtr { .. } => {
@@ -752,7 +962,7 @@ impl Generator<'_> {
             RustToFfiTypeConversion::OptionUtilsRefToPtr { .. } => {
                 bail!("OptionUtilsRefToPtr is not supported in argument position");
             }
-            RustToFfiTypeConversion::QFlagsToUInt { .. } => format!("{}.to_int()", expr),
+            RustToFfiTypeConversion::FlagsToInt { .. } => format!("{}.to_int()", expr),
             RustToFfiTypeConversion::UnitToAnything => {
                 bail!("UnitToAnything is not possible to use in argument position");
             }
@@ -780,8 +990,32 @@ impl Generator<'_> {
                 );
                 self.convert_type_to_ffi(&intermediate_expr, &intermediate)?
             }
+            // the user data and the deleter are passed separately by `generate_ffi_call`
             RustToFfiTypeConversion::ClosureToCallback { .. } => {
-                "Some(ffi_callback::<T>), Some(deleter::<T>), data".to_string()
+                "Some(ffi_callback::<T>)".to_string()
+            }
+            RustToFfiTypeConversion::ExceptionResult(_) => {
+                bail!("ExceptionResult is not possible to use in argument position");
+            }
+            RustToFfiTypeConversion::OutArguments(_) => {
+                bail!("OutArguments is not possible to use in argument position");
+            }
+            RustToFfiTypeConversion::StatusResult { .. } => {
+                bail!("StatusResult is not possible to use in argument position");
+            }
+            // the length is passed separately by `generate_ffi_call`
+            RustToFfiTypeConversion::SliceToPtr { is_str, .. } => {
+                if *is_str {
+                    format!(
+                        "{}.as_ptr() as {}",
+                        expr,
+                        self.rust_type_to_code(type1.ffi_type())
+                    )
+                } else if type1.ffi_type().is_const_pointer_like()? {
+                    format!("{}.as_ptr()", expr)
+                } else {
+                    format!("{}.as_mut_ptr()", expr)
+                }
             }
         };
         Ok(code)
@@ -801,53 +1035,122 @@ impl Generator<'_> {
             .iter()
             .map(|arg| self.rust_type_to_code(arg.api_type()))
             .join(", ");
+        // without a deleter, the closure is dropped when the FFI function returns
+        // (unless it's leaked)
+        let maybe_static = if conversion.deleter_ffi_index.is_some() || conversion.is_leaked {
+            " + 'static"
+        } else {
+            ""
+        };
 
-        format!("T: FnMut({}){} + 'static", args_text, return_type_text)
+        format!(
+            "T: FnMut({}){}{}",
+            args_text, return_type_text, maybe_static
+        )
     }
 
-    fn callback_glue_code(&self, conversion: &RustClosureToCallbackConversion) -> Result<String> {
-        let mut code = String::new();
-        writeln!(
-            code,
-            "extern \"C\" fn deleter<T>(data: *mut ::std::ffi::c_void) {{
-                unsafe {{
-                    let _ = Box::from_raw(data as *mut T);
-                }}
-            }}"
-        )?;
-
-        writeln!(
-            code,
-            "extern \"C\" fn ffi_callback<{}>(data: *mut ::std::ffi::c_void, {}) {{",
-            self.callback_bound_code(conversion),
-            conversion
-                .closure_arguments
-                .iter()
-                .enumerate()
-                .map(|(i, arg)| format!("arg{}: {}", i, self.rust_type_to_code(arg.ffi_type())))
-                .join(", ")
-        )?;
+    /// Generates the FFI callback calling the closure passed in `argument`,
+    /// the deleter of the closure (if it's needed) and the declaration of
+    /// the user data pointer named `data_var_name`. Panics are not allowed
+    /// to propagate to C++ and abort the process instead.
+    fn callback_glue_code(
+        &self,
+        argument: &RustFunctionArgument,
+        conversion: &RustClosureToCallbackConversion,
+        data_var_name: &str,
+    ) -> Result<String> {
+        let callback_type = argument
+            .argument_type
+            .ffi_type()
+            .as_common()?
+            .generic_arguments
+            .as_ref()
+            .and_then(|args| args.get(0))
+            .ok_or_else(|| err_msg("callback type must be an Option"))?;
+        let callback_type = match callback_type {
+            RustType::FunctionPointer(callback_type) => callback_type,
+            _ => bail!("callback type must be a function pointer"),
+        };
 
-        let func_args = conversion
-            .closure_arguments
-            .iter()
-            .enumerate()
-            .map_if_ok(|(num, t)| {
-                self.convert_type_from_ffi(t, format!("arg{}", num), true, false)
-            })?
-            .join(", ");
+        let mut code = String::new();
+        if conversion.deleter_ffi_index.is_some() {
+            writeln!(
+                code,
+                "extern \"C\" fn deleter<T>(data: *mut ::std::ffi::c_void) {{
+                    let result = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| unsafe {{
+                        let _ = Box::from_raw(data as *mut T);
+                    }}));
+                    if result.is_err() {{
+                        ::std::process::abort();
+                    }}
+                }}"
+            )?;
+        }
 
-        code.push_str(&wrap_unsafe(
-            false,
-            &format!("(*(data as *mut T))({})", func_args),
-        ));
+        let mut params = Vec::new();
+        let mut func_args = Vec::new();
+        let mut closure_arguments = conversion.closure_arguments.iter();
+        for (index, arg_type) in callback_type.arguments.iter().enumerate() {
+            let arg_type_text = self.rust_type_to_code(arg_type);
+            if index == conversion.callback_data_index {
+                params.push(format!("data: {}", arg_type_text));
+            } else {
+                let name = format!("arg{}", index);
+                let closure_argument = closure_arguments
+                    .next()
+                    .ok_or_else(|| err_msg("closure arguments don't match the callback"))?;
+                params.push(format!("{}: {}", name, arg_type_text));
+                func_args.push(self.convert_type_from_ffi(closure_argument, name, true, false)?);
+            }
+        }
 
-        writeln!(code, "}}")?;
+        let return_type = &conversion.closure_return_type;
+        let return_type_text = if callback_type.return_type.is_unit() {
+            String::new()
+        } else {
+            format!(" -> {}", self.rust_type_to_code(&callback_type.return_type))
+        };
+        let call = format!("(*(data as *mut T))({})", func_args.join(", "));
+        let body = if return_type.ffi_type().is_unit() {
+            call
+        } else {
+            format!(
+                "let result = {}; {}",
+                call,
+                self.convert_type_to_ffi("result", return_type)?
+            )
+        };
         writeln!(
             code,
-            "let data = Box::into_raw(Box::new(callback)) as *mut ::std::ffi::c_void;"
+            "extern \"C\" fn ffi_callback<{bound}>({params}){return_type} {{
+                let result = ::std::panic::catch_unwind(::std::panic::AssertUnwindSafe(|| {body}));
+                match result {{
+                    Ok(value) => value,
+                    Err(_) => ::std::process::abort(),
+                }}
+            }}",
+            bound = self.callback_bound_code(conversion),
+            params = params.join(", "),
+            return_type = return_type_text,
+            body = wrap_unsafe(false, &body),
         )?;
 
+        if conversion.deleter_ffi_index.is_some() || conversion.is_leaked {
+            writeln!(
+                code,
+                "let {} = Box::into_raw(Box::new({})) as *mut ::std::ffi::c_void;",
+                data_var_name, argument.name
+            )?;
+        } else {
+            writeln!(
+                code,
+                "let mut {name} = {name};\n\
+                 let {data} = &mut {name} as *mut T as *mut ::std::ffi::c_void;",
+                name = argument.name,
+                data = data_var_name
+            )?;
+        }
+
         Ok(code)
     }
 
@@ -862,21 +1165,148 @@ impl Generator<'_> {
         wrapper_data: &RustFfiWrapperData,
         in_unsafe_context: bool,
     ) -> Result<String> {
+        // if the FFI function reports C++ exceptions, the call is generated
+        // for the type of the successful result and wrapped in `Result` afterwards
+        let return_type = if return_type.conversion().is_exception_result() {
+            Cow::Owned(return_type.without_exception_result()?)
+        } else {
+            Cow::Borrowed(return_type)
+        };
+        // values of output arguments are stored in local variables
+        // and returned along with the FFI function's return value
+        let out_arguments = return_type.conversion().as_out_arguments_ref().cloned();
+        let return_type = if out_arguments.is_some() {
+            Cow::Owned(return_type.without_out_arguments()?)
+        } else {
+            return_type
+        };
+        // if the status is combined with output arguments, the `Result` is created
+        // after the output arguments are read
+        let status_success = match return_type.conversion() {
+            RustToFfiTypeConversion::StatusResult { success } if out_arguments.is_some() => {
+                Some(*success)
+            }
+            _ => None,
+        };
+        let return_type = if status_success.is_some() {
+            Cow::Owned(RustFinalType::new(
+                return_type.ffi_type().clone(),
+                RustToFfiTypeConversion::None,
+            )?)
+        } else {
+            return_type
+        };
+        let free_var_name = |name: &str| {
+            let mut var_name = name.to_string();
+            let mut ii = 1;
+            while arguments.iter().any(|x| x.name == var_name) {
+                ii += 1;
+                var_name = format!("{}{}", name, ii);
+            }
+            var_name
+        };
+
+        let callback_data_var_name = free_var_name("callback_data");
         let mut final_args = Vec::new();
         for arg in arguments {
             let code = self.convert_type_to_ffi(&arg.name, &arg.argument_type)?;
-            final_args.resize(arg.ffi_index + 1, None);
+            final_args.resize(final_args.len().max(arg.ffi_index + 1), None);
             final_args[arg.ffi_index] = Some(code);
+            if let Some(conversion) = arg.argument_type.conversion().as_callback_ref() {
+                final_args.resize(final_args.len().max(conversion.data_ffi_index + 1), None);
+                final_args[conversion.data_ffi_index] = Some(callback_data_var_name.clone());
+                if let Some(deleter_ffi_index) = conversion.deleter_ffi_index {
+                    final_args.resize(final_args.len().max(deleter_ffi_index + 1), None);
+                    final_args[deleter_ffi_index] = Some("Some(deleter::<T>)".to_string());
+                }
+            }
+            if let RustToFfiTypeConversion::SliceToPtr {
+                length_ffi_index,
+                length_type,
+                ..
+            } = arg.argument_type.conversion()
+            {
+                let length_type = self.rust_type_to_code(length_type);
+                final_args.resize(final_args.len().max(length_ffi_index + 1), None);
+                final_args[*length_ffi_index] = Some(format!(
+                    "<{t} as ::std::convert::TryFrom<usize>>::try_from({name}.len())\
+                     .expect(\"slice is too long\")",
+                    t = length_type,
+                    name = arg.name,
+                ));
+            }
         }
 
+        let mut out_declarations = String::new();
+        let mut out_var_names = Vec::new();
+        let mut ok_flag_var_name = None;
+        if let Some(out_arguments) = &out_arguments {
+            let all_out_arguments = out_arguments
+                .arguments
+                .iter()
+                .map(|arg| (arg, false))
+                .chain(out_arguments.ok_flag.iter().map(|arg| (arg, true)));
+            for (arg, is_ok_flag) in all_out_arguments {
+                let var_name = free_var_name(&arg.name);
+                writeln!(
+                    out_declarations,
+                    "let mut {}: {} = Default::default();",
+                    var_name,
+                    self.rust_type_to_code(&arg.value_type)
+                )?;
+                final_args.resize(final_args.len().max(arg.ffi_index + 1), None);
+                final_args[arg.ffi_index] = Some(format!("&mut {}", var_name));
+                if is_ok_flag {
+                    ok_flag_var_name = Some(var_name);
+                } else {
+                    out_var_names.push(var_name);
+                }
+            }
+        }
+        let with_out_values = |value: String| -> String {
+            if out_arguments.is_none() {
+                return value;
+            }
+            let mut values = out_var_names.clone();
+            let value = if return_type.api_type().is_unit() {
+                format!("{};\n", value.trim_end_matches(';'))
+            } else {
+                if status_success.is_none() {
+                    values.insert(0, "ffi_value".to_string());
+                }
+                format!("let ffi_value = {};\n", value)
+            };
+            let returned = if values.len() == 1 {
+                values.remove(0)
+            } else {
+                format!("({})", values.join(", "))
+            };
+            let returned = if let Some(ok_flag) = &ok_flag_var_name {
+                format!("if {} {{ Some({}) }} else {{ None }}", ok_flag, returned)
+            } else {
+                returned
+            };
+            let returned = if let Some(success) = status_success {
+                format!(
+                    "if {} {{ Ok({}) }} else {{ Err(::cpp_core::StatusError::new(ffi_value)) }}",
+                    success.condition_code("ffi_value"),
+                    returned
+                )
+            } else {
+                returned
+            };
+            format!("{{\n{}{}\n}}", value, returned)
+        };
+
         let mut result = Vec::new();
 
-        if let Some(conversion) = arguments
-            .iter()
-            .filter_map(|x| x.argument_type.conversion().as_callback_ref())
-            .next()
-        {
-            result.push(self.callback_glue_code(conversion)?);
+        if let Some((arg, conversion)) = arguments.iter().find_map(|arg| {
+            arg.argument_type
+                .conversion()
+                .as_callback_ref()
+                .map(|conversion| (arg, conversion))
+        }) {
+            result.push(self.callback_glue_code(arg, conversion, &callback_data_var_name)?);
         }
 
         let mut maybe_result_var_name = None;
@@ -897,12 +1327,7 @@ impl Generator<'_> {
             .map(|(index, _arg)| index);
 
         if let Some(i) = return_type_ffi_index {
-            let mut return_var_name = "object".to_string();
-            let mut ii = 1;
-            while arguments.iter().any(|x| x.name == return_var_name) {
-                ii += 1;
-                return_var_name = format!("object{}", ii);
-            }
+            let return_var_name = free_var_name("object");
             let struct_name = if return_type.conversion() == &RustToFfiTypeConversion::CppBoxToPtr {
                 if let RustType::Common(RustCommonType {
                     generic_arguments, ..
@@ -933,6 +1358,16 @@ impl Generator<'_> {
             final_args[i] = Some(format!("&mut {}", return_var_name));
             maybe_result_var_name = Some(return_var_name);
         }
+
+        let exception_ffi_index = ffi_item
+            .arguments
+            .iter()
+            .position(|arg| arg.meaning == CppFfiArgumentMeaning::Exception);
+        let exception_var_name = free_var_name("exception");
+        if let Some(i) = exception_ffi_index {
+            final_args.resize(i + 1, None);
+            final_args[i] = Some(format!("&mut {}", exception_var_name));
+        }
         let final_args = final_args
             .into_iter()
             .map_if_ok(|x| x.ok_or_else(|| err_msg("ffi argument is missing")))?;
@@ -954,13 +1389,75 @@ impl Generator<'_> {
             result.push(format!("{}\n}}", name));
         }
         let code = result.join("");
-        if maybe_result_var_name.is_none() {
-            self.convert_type_from_ffi(&return_type, code, in_unsafe_context, true)
+        if exception_ffi_index.is_some() {
+            // the returned value is meaningless if an exception was caught,
+            // so it must not be converted before the check
+            let value = if maybe_result_var_name.is_none() {
+                self.convert_type_from_ffi(
+                    &return_type,
+                    "ffi_result".to_string(),
+                    in_unsafe_context,
+                    false,
+                )?
+            } else {
+                "ffi_result".to_string()
+            };
+            let error = wrap_unsafe(
+                in_unsafe_context,
+                &format!(
+                    "::cpp_core::CppException::from_raw_message({})",
+                    exception_var_name
+                ),
+            );
+            Ok(format!(
+                "{out}let mut {var}: *mut ::std::os::raw::c_char = ::std::ptr::null_mut();\n\
+                 let ffi_result = {{ {code} }};\n\
+                 if {var}.is_null() {{ Ok({value}) }} else {{ Err({error}) }}",
+                out = out_declarations,
+                var = exception_var_name,
+                code = code,
+                value = with_out_values(value),
+                error = error,
+            ))
         } else {
-            Ok(code)
+            let value = if maybe_result_var_name.is_none() {
+                self.convert_type_from_ffi(&return_type, code, in_unsafe_context, true)?
+            } else {
+                code
+            };
+            if out_arguments.is_some() {
+                Ok(format!("{}{}", out_declarations, with_out_values(value)))
+            } else {
+                Ok(value)
+            }
         }
     }
 
+    /// Generates a call of `base_wrapper_path`, the wrapper of a base class method
+    /// declared in a dependency, for its copy forwarded to a derived class.
+    fn generate_base_wrapper_call(
+        &self,
+        arguments: &[RustFunctionArgument],
+        base_wrapper_path: &RustPath,
+        in_unsafe_context: bool,
+    ) -> Result<String> {
+        let args = arguments.iter().map_if_ok(|arg| -> Result<_> {
+            if let RustToFfiTypeConversion::UpcastRefToPtr { .. } = arg.argument_type.conversion() {
+                // the wrapper accepts a reference to the base class
+                let upcast = self.convert_type_to_ffi(&arg.name, &arg.argument_type)?;
+                Ok(format!("&*{}", upcast))
+            } else {
+                Ok(arg.name.clone())
+            }
+        })?;
+        let call = format!(
+            "{}({})",
+            self.rust_path_to_string(base_wrapper_path),
+            args.join(", ")
+        );
+        Ok(wrap_unsafe(in_unsafe_context, &call))
+    }
+
     /// Generates Rust code for declaring a function's arguments.
     #[allow(clippy::collapsible_match)]
     fn arg_texts(
@@ -1036,6 +1533,18 @@ impl Generator<'_> {
         let maybe_unsafe = if func.item.is_unsafe { "unsafe " } else { "" };
 
         let body = match &func.item.kind {
+            RustFunctionKind::FfiWrapper(RustFfiWrapperData {
+                variant:
+                    RustFfiWrapperVariant::Forwarded {
+                        base_wrapper_path: Some(base_wrapper_path),
+                        ..
+                    },
+                ..
+            }) => Some(self.generate_base_wrapper_call(
+                &func.item.arguments,
+                base_wrapper_path,
+                func.item.is_unsafe,
+            )?),
             RustFunctionKind::FfiWrapper(data) => Some(self.generate_ffi_call(
                 &func.id,
                 &func.item.arguments,
@@ -1122,6 +1631,12 @@ impl Generator<'_> {
             name = func.item.path.last(),
             args = self
                 .arg_texts(&func.item.arguments, None, self_type)?
+                .into_iter()
+                .chain(if func.item.is_variadic {
+                    Some("...".to_string())
+                } else {
+                    None
+                })
                 .join(", "),
             return_type = return_type_for_signature,
             maybe_body = maybe_body
@@ -1171,6 +1686,497 @@ impl Generator<'_> {
         Ok(())
     }
 
+    /// Finds the public Rust wrapper of the C++ function `cpp_path`.
+    fn find_api_function(&self, cpp_path: &CppPath) -> Result<Option<&RustFunction>> {
+        for item in self.current_database.rust_items() {
+            if let Some(function) = item.item.as_function_ref() {
+                if let RustFunctionKind::FfiWrapper(_) = &function.kind {
+                    let cpp_item = self.current_database.source_cpp_item(&item.id)?;
+                    if cpp_item.and_then(|cpp_item| cpp_item.item.path()) == Some(cpp_path) {
+                        return Ok(Some(function));
+                    }
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Generates the trait for overriding virtual functions of a subclass wrapper
+    /// and the method attaching its implementation to the wrapper object.
+    fn generate_subclass_overrides(
+        &mut self,
+        data: &RustSubclassOverridesImpl,
+        condition_texts: &ConditionTexts,
+    ) -> Result<()> {
+        let subclass_path = self.rust_path_to_string(&data.subclass_path);
+        let mut methods = Vec::new();
+        let mut setters = Vec::new();
+        for item in &data.overrides {
+            let setter = match self.find_api_function(&item.setter_cpp_path)? {
+                Some(function) => function,
+                None => {
+                    debug!(
+                        "callback setter of {} is not available, {} can't be overridden",
+                        self.rust_path_to_string(&data.subclass_path),
+                        item.cpp_declaration
+                    );
+                    continue;
+                }
+            };
+            let conversion = setter
+                .arguments
+                .iter()
+                .find_map(|arg| {
+                    if let RustToFfiTypeConversion::ClosureToCallback(conversion) =
+                        arg.argument_type.conversion()
+                    {
+                        Some(conversion)
+                    } else {
+                        None
+                    }
+                })
+                .ok_or_else(|| err_msg("callback setter must have a closure argument"))?;
+            let return_type = conversion.closure_return_type.api_type();
+
+            let base_function = match &item.base_function_cpp_path {
+                Some(path) => match self.find_api_function(path)? {
+                    Some(function) if function.return_type.api_type() == return_type => {
+                        Some(function)
+                    }
+                    // a base function with a different signature (e.g. returning `Result`)
+                    // can't be used as the default implementation,
+                    // so the method has to be implemented
+                    _ => {
+                        debug!(
+                            "base implementation of {} is not available for {}",
+                            item.cpp_declaration,
+                            self.rust_path_to_string(&data.trait_path)
+                        );
+                        None
+                    }
+                },
+                None => None,
+            };
+            let base_arguments = base_function.map(|function| {
+                function
+                    .arguments
+                    .iter()
+                    .filter(|arg| arg.name != "self")
+                    .collect_vec()
+            });
+            let arg_names = (0..conversion.closure_arguments.len())
+                .map(|i| {
+                    base_arguments
+                        .as_ref()
+                        .and_then(|args| args.get(i))
+                        .map_or_else(|| format!("arg{}", i), |arg| arg.name.clone())
+                })
+                .collect_vec();
+
+            let args_text =
+                once(format!("this: ::cpp_core::Ptr<{}>", subclass_path))
+                    .chain(conversion.closure_arguments.iter().zip(&arg_names).map(
+                        |(arg, name)| {
+                            format!("{}: {}", name, self.rust_type_to_code(arg.api_type()))
+                        },
+                    ))
+                    .join(", ");
+            let return_type_text = if return_type.is_unit() {
+                String::new()
+            } else {
+                format!(" -> {}", self.rust_type_to_code(return_type))
+            };
+
+            let mut method = format!("    /// Called instead of `{}`.\n", item.cpp_declaration);
+            if let Some(base_function) = base_function {
+                let call_args = conversion
+                    .closure_arguments
+                    .iter()
+                    .zip(&arg_names)
+                    .map(|(arg, name)| {
+                        let is_box = arg.api_type().as_common().ok().map_or(false, |t| {
+                            t.path == RustPath::from_good_str("cpp_core::CppBox")
+                        });
+                        if is_box {
+                            format!("&{}", name)
+                        } else {
+                            name.clone()
+                        }
+                    })
+                    .join(", ");
+                writeln!(
+                    method,
+                    "    ///\n    /// Calls the base class implementation by default."
+                )?;
+                write!(
+                    method,
+                    "    unsafe fn {}(&self, {}){} {{\n        this.{}({})\n    }}",
+                    item.method_name,
+                    args_text,
+                    return_type_text,
+                    base_function.path.last(),
+                    call_args
+                )?;
+            } else {
+                if item.base_function_cpp_path.is_some() {
+                    writeln!(
+                        method,
+                        "    ///\n    /// The base class implementation can't be called from Rust, \
+                         so this method must be implemented."
+                    )?;
+                }
+                write!(
+                    method,
+                    "    unsafe fn {}(&self, {}){};",
+                    item.method_name, args_text, return_type_text
+                )?;
+            }
+            methods.push(method);
+
+            setters.push(format!(
+                "        {{\n            let overrides = ::std::rc::Rc::clone(&overrides);\n            self.{}(move |{}| overrides.{}({}));\n        }}",
+                setter.path.last(),
+                arg_names.join(", "),
+                item.method_name,
+                once("this").chain(arg_names.iter().map(String::as_str)).join(", ")
+            ));
+        }
+
+        writeln!(
+            self,
+            include_str!("../templates/crate/subclass_overrides.rs"),
+            condition_attribute = condition_texts.attribute,
+            trait_name = data.trait_path.last(),
+            trait_path = self.rust_path_to_string(&data.trait_path),
+            subclass_path = subclass_path,
+            methods = methods.join("\n\n"),
+            setters = setters.join("\n"),
+        )?;
+        Ok(())
+    }
+
+    /// Generates the trait dispatching calls to overloaded functions,
+    /// its implementations for argument tuples and the generic function
+    /// calling the overload selected by the type of its argument.
+    fn generate_overload_dispatch(&mut self, data: &RustOverloadDispatchImpl) -> Result<()> {
+        let maybe_unsafe = if data.is_unsafe { "unsafe " } else { "" };
+        let self_type = data.self_type.as_ref().map(|t| self.rust_type_to_code(t));
+        let (exec_self_arg, self_arg) = match (data.self_arg_kind, &self_type) {
+            (RustFunctionSelfArgKind::None, None) => (String::new(), ""),
+            (RustFunctionSelfArgKind::ConstRef, Some(self_type)) => {
+                (format!("self_: &{}", self_type), "&self, ")
+            }
+            (RustFunctionSelfArgKind::MutRef, Some(self_type)) => {
+                (format!("self_: &mut {}", self_type), "&mut self, ")
+            }
+            _ => bail!("invalid self argument of overload dispatch: {:?}", data),
+        };
+        let trait_name = data.trait_path.last();
+        let function_name = data.function_path.last();
+
+        // The trait is parameterized by a tuple of the overload's argument types,
+        // so that implementations for generic tuples accepting `CastInto` arguments
+        // don't conflict with each other.
+        writeln!(
+            self,
+            "{doc}pub trait {trait_name}<Marker> {{\n\
+             type ReturnType;\n\
+             {maybe_unsafe}fn exec(self, {exec_self_arg}) -> Self::ReturnType;\n\
+             }}\n",
+            doc = format_doc(&format!(
+                "Argument tuples accepted by `{}`.",
+                self.rust_path_to_string(&data.function_path)
+            )),
+            trait_name = trait_name,
+            maybe_unsafe = maybe_unsafe,
+            exec_self_arg = exec_self_arg,
+        )?;
+
+        let mut function_doc =
+            "Calls one of the overloaded functions depending on the type of `args`:\n\n"
+                .to_string();
+        for variant in &data.variants {
+            let function = match self.current_database.find_rust_item(&variant.function_path) {
+                Some(function) => function,
+                None => continue,
+            };
+            let mut generic_params = Vec::new();
+            let mut element_types = Vec::new();
+            let mut marker_types = Vec::new();
+            for (index, argument_type) in variant.argument_types.iter().enumerate() {
+                if let Some(target) = argument_type.cast_into_target() {
+                    let param = format!("A{}", index);
+                    generic_params.push(format!(
+                        "{}: ::cpp_core::CastInto<{}>",
+                        param,
+                        self.rust_type_to_code(target)
+                    ));
+                    element_types.push(param);
+                    marker_types.push(self.rust_type_to_code(target));
+                } else {
+                    element_types.push(self.rust_type_to_code(argument_type));
+                    marker_types.push(self.rust_type_to_code(argument_type));
+                }
+            }
+            let tuple_code = |types: &[String]| {
+                if types.len() == 1 {
+                    format!("({},)", types[0])
+                } else {
+                    format!("({})", types.join(", "))
+                }
+            };
+            let impl_type = tuple_code(&element_types);
+            let marker_type = tuple_code(&marker_types);
+            let return_type = self.rust_type_to_code(&variant.return_type);
+            let function_path = self.rust_path_to_string(&variant.function_path);
+            let call_args = if self_type.is_some() {
+                Some("self_".to_string())
+            } else {
+                None
+            }
+            .into_iter()
+            .chain((0..variant.argument_types.len()).map(|i| format!("self.{}", i)))
+            .join(", ");
+
+            let condition_texts = self.condition_texts(&function.id)?;
+            write!(self, "{}", condition_texts.attribute)?;
+            writeln!(
+                self,
+                include_str!("../templates/crate/impl_overloading_trait.rs.in"),
+                generic_params = if generic_params.is_empty() {
+                    String::new()
+                } else {
+                    format!("<{}>", generic_params.join(", "))
+                },
+                trait_name = format!("{}<{}>", trait_name, marker_type),
+                impl_type = impl_type,
+                return_type_decl = format!("type ReturnType = {};", return_type),
+                maybe_unsafe = maybe_unsafe,
+                final_arg_list = exec_self_arg,
+                return_type_string = return_type,
+                body = format!("{}({})", function_path, call_args),
+            )?;
+            writeln!(
+                function_doc,
+                "- `{}`: calls `{}`.",
+                marker_type, function_path
+            )?;
+        }
+
+        let function_code = format!(
+            include_str!("../templates/crate/overloaded_function.rs.in"),
+            doc = format_doc(&function_doc),
+            maybe_pub = "pub ",
+            maybe_unsafe = maybe_unsafe,
+            name = function_name,
+            tpl_decl = "Args, Marker",
+            args = format!("{}args: Args", self_arg),
+            return_type_string = "Args::ReturnType",
+            tpl_type = "Args",
+            trait_path = format!("{}<Marker>", trait_name),
+            body = if self_type.is_some() {
+                "args.exec(self)"
+            } else {
+                "args.exec()"
+            },
+        );
+        if let Some(self_type) = &self_type {
+            writeln!(self, "impl {} {{\n{}\n}}\n", self_type, function_code)?;
+        } else {
+            writeln!(self, "{}\n", function_code)?;
+        }
+        Ok(())
+    }
+
+    /// Returns Rust code of the signature of a class template trait's method.
+    /// `substitutions` are applied to the types of the arguments and the return type.
+    fn template_trait_method_signature(
+        &self,
+        method: &RustTemplateTraitMethod,
+        substitutions: &[(RustType, RustType)],
+    ) -> String {
+        let substitute = |rust_type: &RustType| {
+            substitutions
+                .iter()
+                .fold(rust_type.clone(), |t, (from, to)| t.replace(from, to))
+        };
+        let mut lifetimes = Vec::new();
+        let mut args = Vec::new();
+        for arg in &method.arguments {
+            if let Some(lifetime) = arg.argument_type.lifetime() {
+                let lifetime = format!("'{}", lifetime);
+                if !lifetimes.contains(&lifetime) {
+                    lifetimes.push(lifetime);
+                }
+            }
+            if arg.name == "self" {
+                args.push(match &arg.argument_type {
+                    RustType::PointerLike {
+                        kind: RustPointerLikeTypeKind::Reference { lifetime },
+                        is_const,
+                        ..
+                    } => format!(
+                        "&{}{}self",
+                        lifetime
+                            .as_ref()
+                            .map_or_else(String::new, |lifetime| format!("'{} ", lifetime)),
+                        if *is_const { "" } else { "mut " }
+                    ),
+                    _ => "self".to_string(),
+                });
+            } else {
+                args.push(format!(
+                    "{}: {}",
+                    arg.name,
+                    self.rust_type_to_code(&substitute(&arg.argument_type))
+                ));
+            }
+        }
+        let return_type = substitute(&method.return_type);
+        format!(
+            "{maybe_unsafe}fn {name}{lifetimes}({args}){return_type}",
+            maybe_unsafe = if method.is_unsafe { "unsafe " } else { "" },
+            name = method.name,
+            lifetimes = if lifetimes.is_empty() {
+                String::new()
+            } else {
+                format!("<{}>", lifetimes.join(", "))
+            },
+            args = args.join(", "),
+            return_type = if return_type.is_unit() {
+                String::new()
+            } else {
+                format!(" -> {}", self.rust_type_to_code(&return_type))
+            },
+        )
+    }
+
+    /// Generates the trait containing methods common to all instantiations
+    /// of a class template and its implementations for the instantiations.
+    fn generate_smart_pointer_target(
+        &mut self,
+        data: &RustSmartPointerTargetImpl,
+        condition_texts: &ConditionTexts,
+    ) -> Result<()> {
+        let function = |operation: CppSmartPointerOperation| -> Result<String> {
+            let path = data
+                .ffi_functions
+                .iter()
+                .find(|(op, _)| *op == operation)
+                .map(|(_, path)| path)
+                .ok_or_else(|| format_err!("missing smart pointer function: {:?}", operation))?;
+            Ok(self.rust_path_to_string(path))
+        };
+        let target_type = self.rust_type_to_code(&data.target_type);
+        let code = match data.kind {
+            CppSmartPointerKind::Shared => format!(
+                include_str!("../templates/crate/cpp_shared_ptr_target_impl.rs.in"),
+                t = target_type,
+                delete = function(CppSmartPointerOperation::Delete)?,
+                copy = function(CppSmartPointerOperation::Copy)?,
+                get = function(CppSmartPointerOperation::Get)?,
+                use_count = function(CppSmartPointerOperation::UseCount)?,
+                from_raw = function(CppSmartPointerOperation::FromRaw)?,
+                to_owner = function(CppSmartPointerOperation::ToOwner)?,
+                alias = function(CppSmartPointerOperation::Alias)?,
+            ),
+            CppSmartPointerKind::Unique => format!(
+                include_str!("../templates/crate/cpp_unique_ptr_target_impl.rs.in"),
+                t = target_type,
+                delete = function(CppSmartPointerOperation::Delete)?,
+                get = function(CppSmartPointerOperation::Get)?,
+                from_raw = function(CppSmartPointerOperation::FromRaw)?,
+                release = function(CppSmartPointerOperation::Release)?,
+            ),
+        };
+        write!(self, "{}", condition_texts.attribute)?;
+        writeln!(self, "{}", code)?;
+        Ok(())
+    }
+
+    fn generate_template_trait(&mut self, data: &RustTemplateTraitImpl) -> Result<()> {
+        let trait_name = data.trait_path.last();
+        let instantiation_names = data
+            .instantiations
+            .iter()
+            .map(|instantiation| {
+                format!("`{}`", self.rust_type_to_code(&instantiation.target_type))
+            })
+            .join(", ");
+        let doc = format!(
+            "Methods common to all instantiations of C++ class template `{}`.\n\n\
+             Implemented for {}.",
+            data.cpp_template_path.to_cpp_pseudo_code(),
+            instantiation_names
+        );
+        let where_clause = if data.deletable_parameters.is_empty() {
+            String::new()
+        } else {
+            format!(
+                " where {}",
+                data.deletable_parameters
+                    .iter()
+                    .map(|name| format!("{}: ::cpp_core::CppDeletable", name))
+                    .join(", ")
+            )
+        };
+        let methods = data
+            .methods
+            .iter()
+            .map(|method| {
+                format!(
+                    "{}{};",
+                    format_doc(&format!("Calls C++ method `{}`.", method.cpp_name)),
+                    self.template_trait_method_signature(method, &[])
+                )
+            })
+            .join("\n\n");
+        writeln!(
+            self,
+            "{doc}pub trait {name}<{params}>: Sized{where_clause} {{\n{methods}\n}}\n",
+            doc = format_doc(&doc),
+            name = trait_name,
+            params = data.generic_parameters.join(", "),
+            where_clause = where_clause,
+            methods = methods,
+        )?;
+
+        for instantiation in &data.instantiations {
+            let substitutions = data
+                .generic_parameters
+                .iter()
+                .zip(&instantiation.generic_arguments)
+                .map(|(name, value)| (RustType::GenericParameter(name.clone()), value.clone()))
+                .collect_vec();
+            let functions = data
+                .methods
+                .iter()
+                .zip(&instantiation.method_paths)
+                .map(|(method, path)| {
+                    format!(
+                        "#[inline(always)]\n{signature} {{\n{path}({args})\n}}",
+                        signature = self.template_trait_method_signature(method, &substitutions),
+                        path = self.rust_path_to_string(path),
+                        args = method.arguments.iter().map(|arg| &arg.name).join(", "),
+                    )
+                })
+                .join("\n\n");
+            writeln!(
+                self,
+                "impl {name}<{args}> for {target} {{\n{functions}\n}}\n",
+                name = trait_name,
+                args = instantiation
+                    .generic_arguments
+                    .iter()
+                    .map(|t| self.rust_type_to_code(t))
+                    .join(", "),
+                target = self.rust_type_to_code(&instantiation.target_type),
+                functions = functions,
+            )?;
+        }
+        Ok(())
+    }
+
     fn generate_extra_impl(
         &mut self,
         item: DbItem<&RustExtraImpl>,
@@ -1188,6 +2194,33 @@ impl Generator<'_> {
                     qflags = qflags
                 )?;
             }
+            RustExtraImplKind::CppBoxEq(data) => {
+                write!(self, "{}", condition_texts.attribute)?;
+                writeln!(
+                    self,
+                    include_str!("../templates/crate/cpp_box_eq_impl.rs.in"),
+                    t = self.rust_type_to_code(&data.target_type),
+                )?;
+            }
+            RustExtraImplKind::CppDisplay(data) => {
+                let target_type = self.rust_type_to_code(&data.target_type);
+                let ffi_function = self.rust_path_to_string(&data.ffi_function_path);
+                write!(self, "{}", condition_texts.attribute)?;
+                writeln!(
+                    self,
+                    include_str!("../templates/crate/cpp_display_impl.rs.in"),
+                    t = target_type,
+                    f = ffi_function,
+                    cond = condition_texts.attribute,
+                )?;
+            }
+            RustExtraImplKind::BitmaskEnum(data) => {
+                writeln!(
+                    self,
+                    include_str!("../templates/crate/bitmask_enum_impl.rs.in"),
+                    e = self.rust_path_to_string(&data.enum_path),
+                )?;
+            }
             RustExtraImplKind::QtReceiverImpl(data) => {
                 let wrapper = &self
                     .current_database
@@ -1196,7 +2229,9 @@ impl Generator<'_> {
                     .item;
 
                 let receiver_id = match wrapper {
-                    CppFfiItem::Function(_) => bail!("invalid source ffi item type"),
+                    CppFfiItem::Function(_) | CppFfiItem::SubclassWrapper(_) => {
+                        bail!("invalid source ffi item type")
+                    }
                     CppFfiItem::QtSlotWrapper(w) => CppFunction::receiver_id_from_data(
                         RustQtReceiverType::Slot,
                         "slot_",
@@ -1231,6 +2266,19 @@ impl Generator<'_> {
                     )?;
                 }
             }
+            RustExtraImplKind::SubclassOverrides(data) => {
+                self.generate_subclass_overrides(data, condition_texts)?;
+            }
+            RustExtraImplKind::OverloadDispatch(data) => {
+                // each trait implementation has its own condition
+                self.generate_overload_dispatch(data)?;
+            }
+            RustExtraImplKind::TemplateTrait(data) => {
+                self.generate_template_trait(data)?;
+            }
+            RustExtraImplKind::SmartPointerTarget(data) => {
+                self.generate_smart_pointer_target(data, condition_texts)?;
+            }
         }
         Ok(())
     }
@@ -1,32 +1,38 @@
-use crate::config::CrateDependencyKind;
+use crate::config::{CallbackConvention, CrateDependencyKind, PointerAnnotation};
 use crate::cpp_checks::CppChecks;
 use crate::cpp_data::{
-    inherits, CppItem, CppPath, CppPathItem, CppTypeDeclaration, CppTypeDeclarationKind,
+    inherits, CppBaseSpecifier, CppConstant, CppConstantValue, CppItem, CppPath, CppPathItem,
+    CppTypeDeclaration, CppTypeDeclarationKind, CppVisibility,
 };
 use crate::cpp_ffi_data::{
     CppCast, CppFfiArgumentMeaning, CppFfiFunction, CppFfiFunctionKind, CppFfiItem, CppFfiType,
-    CppFieldAccessorType, CppToFfiTypeConversion,
+    CppFieldAccessorType, CppFlagContainer, CppSmartPointerOperation, CppToFfiTypeConversion,
 };
 use crate::cpp_ffi_generator::ffi_type;
 use crate::cpp_function::{CppFunction, CppOperator, ReturnValueAllocationPlace};
 use crate::cpp_type::{
-    is_qflags, CppBuiltInNumericType, CppFunctionPointerType, CppPointerLikeTypeKind,
-    CppSpecificNumericType, CppSpecificNumericTypeKind, CppType, CppTypeRole,
+    smart_pointer_type, CppBuiltInNumericType, CppFunctionPointerType, CppPointerLikeTypeKind,
+    CppSmartPointerKind, CppSpecificNumericType, CppSpecificNumericTypeKind, CppType, CppTypeRole,
 };
 use crate::database::{DbItem, ItemId, ItemWithSource};
 use crate::processor::ProcessorData;
 use crate::rust_info::{
-    NameType, RustEnumValue, RustExtraImpl, RustExtraImplKind, RustFfiWrapperData,
-    RustFlagEnumImpl, RustFunction, RustFunctionArgument, RustFunctionCaptionStrategy,
-    RustFunctionKind, RustFunctionSelfArgKind, RustItem, RustModule, RustModuleKind, RustPathScope,
-    RustQtReceiverData, RustQtReceiverImpl, RustQtReceiverType, RustReexport, RustReexportSource,
-    RustSignalOrSlotGetter, RustSizedType, RustSpecialModuleKind, RustStruct, RustStructKind,
-    RustTraitAssociatedType, RustTraitImpl, RustTraitImplExtraKind, RustTypeCaptionStrategy,
+    NameType, RustConstant, RustCppBoxEqImpl, RustCppDisplayImpl, RustEnumValue, RustExtraImpl,
+    RustExtraImplKind, RustFfiWrapperData, RustFfiWrapperVariant, RustFlagEnumImpl, RustFunction,
+    RustFunctionArgument, RustFunctionCaptionStrategy, RustFunctionKind, RustFunctionSelfArgKind,
+    RustItem, RustModule, RustModuleKind, RustOverloadDispatchImpl, RustOverloadVariant,
+    RustPathScope, RustQtReceiverData, RustQtReceiverImpl, RustQtReceiverType, RustReexport,
+    RustReexportSource, RustSignalOrSlotGetter, RustSizedType, RustSmartPointerTargetImpl,
+    RustSpecialModuleKind, RustStruct, RustStructKind, RustSubclassOverride,
+    RustSubclassOverridesImpl, RustTemplateTraitArgument, RustTemplateTraitImpl,
+    RustTemplateTraitInstantiation, RustTemplateTraitMethod, RustTraitAssociatedType,
+    RustTraitImpl, RustTraitImplExtraKind, RustTypeAlias, RustTypeCaptionStrategy,
     RustWrapperTypeKind, UnnamedRustFunction,
 };
 use crate::rust_type::{
     RustClosureToCallbackConversion, RustCommonType, RustFinalType, RustFunctionPointerType,
-    RustPath, RustPointerLikeTypeKind, RustToFfiTypeConversion, RustType,
+    RustOutArgument, RustPath, RustPointerLikeTypeKind, RustToFfiTypeConversion, RustType,
+    StatusSuccess,
 };
 use itertools::Itertools;
 use log::{debug, trace};
@@ -34,7 +40,7 @@ use ritual_common::errors::{bail, err_msg, format_err, print_trace, Result};
 use ritual_common::string_utils::CaseOperations;
 use ritual_common::utils::MapIfOk;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
-use std::iter::Iterator;
+use std::iter::{once, Iterator};
 use std::ops::Deref;
 
 pub fn qt_core_path(crate_name: &str) -> RustPath {
@@ -45,6 +51,25 @@ pub fn qt_core_path(crate_name: &str) -> RustPath {
     }
 }
 
+/// Returns Rust code of the value of `constant`.
+fn constant_value_to_rust_code(constant: &CppConstant) -> Result<String> {
+    Ok(match &constant.value {
+        CppConstantValue::Bool(value) => value.to_string(),
+        CppConstantValue::Integer(value) => {
+            if *value < 0 {
+                if let CppType::BuiltInNumeric(numeric) = &constant.constant_type {
+                    if numeric.is_unsigned_integer() {
+                        bail!("negative value of unsigned constant");
+                    }
+                }
+            }
+            value.to_string()
+        }
+        CppConstantValue::FloatingPoint(value) => value.clone(),
+        CppConstantValue::String(value) => format!("{:?}", value),
+    })
+}
+
 /// Adds "_" to a string if it is a reserved word in Rust
 fn sanitize_rust_identifier(name: &str, is_module: bool) -> String {
     match name {
@@ -72,12 +97,19 @@ fn sanitize_rust_identifier_test() {
     assert_eq!(&sanitize_rust_identifier("lib", true), "lib_");
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct FunctionWithDesiredPath {
     function: UnnamedRustFunction,
     desired_path: RustPath,
 }
 
+/// A class that receives forwarded methods of its base class.
+struct ForwardingTarget {
+    derived_class: CppPath,
+    /// Names of members of the base class that are hidden in the derived class
+    hidden_names: HashSet<String>,
+}
+
 enum ProcessedFfiItem {
     Item(RustItem),
     Function(FunctionWithDesiredPath),
@@ -87,6 +119,14 @@ enum ProcessedFfiItem {
 enum ReturnTypeConstraint {
     Bool,
     Usize,
+    U64,
+    /// The function must return an object owned by `CppBox`.
+    CppBox,
+    /// The returned reference or pointer is converted to a raw pointer
+    /// to its target, and the target is used as the `Output` type.
+    Pointer {
+        is_const: bool,
+    },
     #[allow(dead_code)]
     Unit,
     Any,
@@ -381,10 +421,94 @@ impl TraitImplInfo {
         })
     }
 
+    fn subscript(function: &CppFunction) -> Option<TraitImplInfo> {
+        let member = function.member.as_ref()?;
+        if member.is_static || function.arguments.len() != 1 {
+            return None;
+        }
+        let info = if member.is_const {
+            TraitImplInfo {
+                trait_path: "cpp_core::ops::Subscript",
+                function_name: "subscript",
+                is_unsafe: true,
+                is_inherent: true,
+                self_arg_kind: RustFunctionSelfArgKind::ConstRef,
+                has_output_associated_type: true,
+                trait_arg_is_second_arg_type: true,
+                second_arg_is_reference: false,
+                return_type_constraint: ReturnTypeConstraint::Pointer { is_const: true },
+                target_is_reference: false,
+            }
+        } else {
+            TraitImplInfo {
+                trait_path: "cpp_core::ops::SubscriptMut",
+                function_name: "subscript_mut",
+                is_unsafe: true,
+                is_inherent: true,
+                self_arg_kind: RustFunctionSelfArgKind::ConstRef,
+                has_output_associated_type: true,
+                trait_arg_is_second_arg_type: true,
+                second_arg_is_reference: false,
+                return_type_constraint: ReturnTypeConstraint::Pointer { is_const: false },
+                target_is_reference: false,
+            }
+        };
+        Some(info)
+    }
+
+    /// Returns information for implementing `cpp_core::CppHash`
+    /// using the hash function `function`.
+    fn hash(function: &CppFunction) -> Option<TraitImplInfo> {
+        // `qHash(const T&, uint seed = 0)` is also added with the default argument
+        // omitted (see `cpp_omitting_arguments`), and only that variant has
+        // the signature of `cpp_hash`
+        let has_single_argument = function.arguments.len() == 1
+            && function.arguments.iter().all(|arg| !arg.has_default_value);
+        if function.member.is_some() || !has_single_argument {
+            return None;
+        }
+        Some(Self::cpp_hash())
+    }
+
+    /// Returns information for implementing `cpp_core::CppHash` using a function
+    /// that accepts the hashed value as its only argument, like the `qHash` overloads
+    /// and the FFI functions calling `std::hash`.
+    fn cpp_hash() -> TraitImplInfo {
+        TraitImplInfo {
+            trait_path: "cpp_core::CppHash",
+            function_name: "cpp_hash",
+            is_unsafe: true,
+            is_inherent: true,
+            self_arg_kind: RustFunctionSelfArgKind::ConstRef,
+            has_output_associated_type: false,
+            trait_arg_is_second_arg_type: false,
+            second_arg_is_reference: false,
+            return_type_constraint: ReturnTypeConstraint::U64,
+            target_is_reference: false,
+        }
+    }
+
     fn new(function: &CppFunction) -> Option<TraitImplInfo> {
         if let Some(operator) = &function.operator {
+            if operator == &CppOperator::Subscript {
+                return Self::subscript(function);
+            }
             return Self::from_operator(operator);
         }
+        if function.is_copy_constructor() {
+            return Some(TraitImplInfo {
+                trait_path: "cpp_core::CppClone",
+                function_name: "cpp_clone",
+                is_unsafe: true,
+                is_inherent: true,
+                self_arg_kind: RustFunctionSelfArgKind::ConstRef,
+                has_output_associated_type: false,
+                trait_arg_is_second_arg_type: false,
+                second_arg_is_reference: false,
+                return_type_constraint: ReturnTypeConstraint::CppBox,
+                target_is_reference: false,
+            });
+        }
         if let Some(member) = &function.member {
             if !member.is_static
                 && function.arguments.is_empty()
@@ -644,6 +768,17 @@ impl State<'_, '_> {
                 let name = if *is_signed { "isize" } else { "usize" };
                 RustType::Primitive(name.into())
             }
+            CppType::Class(path) if smart_pointer_type(path).is_some() => {
+                let (kind, target) = smart_pointer_type(path).expect("checked above");
+                let path = match kind {
+                    CppSmartPointerKind::Shared => "cpp_core::CppSharedPtr",
+                    CppSmartPointerKind::Unique => "cpp_core::CppUniquePtr",
+                };
+                RustType::Common(RustCommonType {
+                    path: RustPath::from_good_str(path),
+                    generic_arguments: Some(vec![self.ffi_type_to_rust_ffi_type(target)?]),
+                })
+            }
             CppType::Enum { path } | CppType::Class(path) => {
                 let rust_item = self.find_wrapper_type(path)?;
                 let path = rust_item
@@ -662,9 +797,6 @@ impl State<'_, '_> {
                 arguments,
                 allows_variadic_arguments,
             }) => {
-                if *allows_variadic_arguments {
-                    bail!("function pointers with variadic arguments are not supported");
-                }
                 let rust_args = arguments
                     .iter()
                     .map_if_ok(|arg| self.ffi_type_to_rust_ffi_type(arg))?;
@@ -672,13 +804,16 @@ impl State<'_, '_> {
                 let pointer = RustType::FunctionPointer(RustFunctionPointerType {
                     arguments: rust_args,
                     return_type: Box::new(rust_return_type),
+                    is_variadic: *allows_variadic_arguments,
                 });
                 RustType::Common(RustCommonType {
                     path: RustPath::from_good_str("std::option::Option"),
                     generic_arguments: Some(vec![pointer]),
                 })
             }
-            CppType::TemplateParameter { .. } => bail!("invalid cpp type"),
+            CppType::TemplateParameter { .. } | CppType::TemplateArgumentValue(_) => {
+                bail!("invalid cpp type")
+            }
         };
 
         Ok(rust_type)
@@ -711,6 +846,9 @@ impl State<'_, '_> {
         } else {
             bail!("not a pointer to class");
         };
+        if let Some((kind, target)) = smart_pointer_type(class_path) {
+            return self.is_smart_pointer_deletable(kind, target, checks);
+        }
 
         let destructor = if let Some(r) = self
             .data
@@ -748,6 +886,36 @@ impl State<'_, '_> {
         Ok(is_deletable)
     }
 
+    /// Checks if the smart pointer of `kind` pointing to `target` can be deleted
+    /// using the generated smart pointer functions.
+    fn is_smart_pointer_deletable(
+        &self,
+        kind: CppSmartPointerKind,
+        target: &CppType,
+        checks: &CppChecks,
+    ) -> Result<bool> {
+        let delete_kind = CppFfiFunctionKind::SmartPointer {
+            kind,
+            operation: CppSmartPointerOperation::Delete,
+        };
+        for ffi_item in self.data.db.all_ffi_items() {
+            let function = match ffi_item.item.as_function_ref() {
+                Some(function) if function.kind == delete_kind => function,
+                _ => continue,
+            };
+            if smart_pointer_target(function)? != *target {
+                continue;
+            }
+            let delete_checks = self.data.db.cpp_checks(&ffi_item.id)?;
+            let is_deletable =
+                !delete_checks.is_empty() && delete_checks.is_always_success_for(checks);
+            debug!("    is_smart_pointer_deletable = {}", is_deletable);
+            return Ok(is_deletable);
+        }
+        debug!("    not deletable (smart pointer delete function not found)");
+        Ok(false)
+    }
+
     /// Generates `CompleteType` from `CppFfiType`, adding
     /// Rust API type, Rust FFI type and conversion between them.
     #[allow(clippy::collapsible_if)]
@@ -757,8 +925,26 @@ impl State<'_, '_> {
         argument_meaning: &CppFfiArgumentMeaning,
         allocation_place: ReturnValueAllocationPlace,
         checks: Option<&CppChecks>,
+        annotation: Option<PointerAnnotation>,
     ) -> Result<RustFinalType> {
         let rust_ffi_type = self.ffi_type_to_rust_ffi_type(cpp_ffi_type.ffi_type())?;
+        // a rule matches all overloads of the function, so the annotated argument
+        // is not necessarily a pointer to an object in every overload
+        let annotation = annotation.filter(|_| {
+            let is_object_pointer = cpp_ffi_type.conversion() == &CppToFfiTypeConversion::NoChange
+                && cpp_ffi_type.ffi_type().is_pointer()
+                && cpp_ffi_type
+                    .ffi_type()
+                    .pointer_like_to_target()
+                    .map_or(false, |target| target.is_class());
+            if !is_object_pointer {
+                debug!(
+                    "pointer annotation is only applicable to pointers to objects: {:?}",
+                    cpp_ffi_type
+                );
+            }
+            is_object_pointer
+        });
         let mut api_to_ffi_conversion = RustToFfiTypeConversion::None;
         if let RustType::PointerLike { .. } = &rust_ffi_type {
             let target = cpp_ffi_type.ffi_type().pointer_like_to_target()?;
@@ -800,6 +986,18 @@ impl State<'_, '_> {
                     api_to_ffi_conversion = RustToFfiTypeConversion::ImplCastInto(Box::new(
                         RustToFfiTypeConversion::UtilsRefToPtr {},
                     ));
+                    if target.is_unique_ptr() {
+                        let is_deletable = if let Some(checks) = checks {
+                            self.is_type_deletable(cpp_ffi_type.ffi_type(), checks)?
+                        } else {
+                            true
+                        };
+                        if is_deletable {
+                            // the object is moved out of `std::unique_ptr`,
+                            // so the box is consumed
+                            api_to_ffi_conversion = RustToFfiTypeConversion::MovedCppBoxToPtr;
+                        }
+                    }
                 }
             } else if argument_meaning == &CppFfiArgumentMeaning::This {
                 api_to_ffi_conversion = RustToFfiTypeConversion::RefToPtr { lifetime: None };
@@ -822,7 +1020,24 @@ impl State<'_, '_> {
                 // argument
                 if target.is_class() {
                     api_to_ffi_conversion =
-                        if let CppToFfiTypeConversion::ReferenceToPointer { .. } =
+                        if let CppToFfiTypeConversion::RValueReferenceToPointer =
+                            cpp_ffi_type.conversion()
+                        {
+                            let is_deletable = if let Some(checks) = checks {
+                                self.is_type_deletable(cpp_ffi_type.ffi_type(), checks)?
+                            } else {
+                                true
+                            };
+                            // an owned object is consumed if possible, otherwise
+                            // the object is moved from and left in a valid but unspecified state
+                            if is_deletable {
+                                RustToFfiTypeConversion::MovedCppBoxToPtr
+                            } else {
+                                RustToFfiTypeConversion::ImplCastInto(Box::new(
+                                    RustToFfiTypeConversion::UtilsRefToPtr {},
+                                ))
+                            }
+                        } else if let CppToFfiTypeConversion::ReferenceToPointer { .. } =
                             cpp_ffi_type.conversion()
                         {
                             RustToFfiTypeConversion::ImplCastInto(Box::new(
@@ -837,45 +1052,82 @@ impl State<'_, '_> {
                     api_to_ffi_conversion = RustToFfiTypeConversion::None;
                 }
             }
+
+            if let Some(annotation) = annotation {
+                // annotations override the default representation of raw pointers to objects
+                let is_return_value = argument_meaning == &CppFfiArgumentMeaning::ReturnValue;
+                api_to_ffi_conversion = match annotation {
+                    PointerAnnotation::NonNull => {
+                        if is_return_value {
+                            RustToFfiTypeConversion::UtilsRefToPtr {}
+                        } else {
+                            RustToFfiTypeConversion::ImplCastInto(Box::new(
+                                RustToFfiTypeConversion::UtilsRefToPtr {},
+                            ))
+                        }
+                    }
+                    PointerAnnotation::Nullable => {
+                        if is_return_value {
+                            RustToFfiTypeConversion::OptionUtilsRefToPtr {}
+                        } else {
+                            RustToFfiTypeConversion::ImplCastInto(Box::new(
+                                RustToFfiTypeConversion::UtilsPtrToPtr {},
+                            ))
+                        }
+                    }
+                    PointerAnnotation::Owned => {
+                        let is_deletable = if let Some(checks) = checks {
+                            self.is_type_deletable(cpp_ffi_type.ffi_type(), checks)?
+                        } else {
+                            true
+                        };
+                        if !is_deletable {
+                            bail!("owned object must be deletable: {:?}", cpp_ffi_type);
+                        }
+                        if inherits_qobject {
+                            RustToFfiTypeConversion::QBoxToPtr
+                        } else {
+                            RustToFfiTypeConversion::CppBoxToPtr
+                        }
+                    }
+                };
+            }
         }
-        if cpp_ffi_type.conversion() == &CppToFfiTypeConversion::QFlagsToInt {
-            let qflags_type = match cpp_ffi_type.original_type() {
+        if let CppToFfiTypeConversion::FlagsToInt { container } = cpp_ffi_type.conversion() {
+            let flags_type = match cpp_ffi_type.original_type() {
                 CppType::PointerLike {
                     kind,
                     is_const,
                     target,
                 } => {
                     if kind != &CppPointerLikeTypeKind::Reference {
-                        bail!(
-                            "unsupported indirection for QFlagsToUInt: {:?}",
-                            cpp_ffi_type
-                        );
+                        bail!("unsupported indirection for FlagsToInt: {:?}", cpp_ffi_type);
                     }
                     if !*is_const {
-                        bail!("unsupported is_const for QFlagsToUInt: {:?}", cpp_ffi_type);
+                        bail!("unsupported is_const for FlagsToInt: {:?}", cpp_ffi_type);
                     }
                     &*target
                 }
                 a => a,
             };
-            let enum_type = if let CppType::Class(path) = qflags_type {
+            let enum_type = if let CppType::Class(path) = flags_type {
                 let template_arguments = path
                     .last()
                     .template_arguments
                     .as_ref()
-                    .ok_or_else(|| err_msg("expected template arguments for QFlags"))?;
+                    .ok_or_else(|| err_msg("expected template arguments for flag container"))?;
                 if template_arguments.len() != 1 {
-                    bail!("QFlags type must have exactly 1 template argument");
+                    bail!("flag container type must have exactly 1 template argument");
                 }
                 &template_arguments[0]
             } else {
-                bail!("invalid original type for QFlagsToUInt: {:?}", cpp_ffi_type);
+                bail!("invalid original type for FlagsToInt: {:?}", cpp_ffi_type);
             };
 
             let enum_path = if let CppType::Enum { path } = &enum_type {
                 path
             } else {
-                bail!("invalid QFlags argument type: {:?}", enum_type);
+                bail!("invalid flag container argument type: {:?}", enum_type);
             };
 
             let rust_enum_type = self.find_wrapper_type(enum_path)?;
@@ -886,9 +1138,18 @@ impl State<'_, '_> {
                 )
             })?;
 
-            api_to_ffi_conversion = RustToFfiTypeConversion::QFlagsToUInt {
-                api_type: self.create_qflags(rust_enum_path),
+            let api_type = if container == &CppFlagContainer::qflags() {
+                self.create_qflags(rust_enum_path)
+            } else {
+                if !is_int_enum_wrapper(rust_enum_type.item) {
+                    bail!("flag containers are only supported for enums represented by c_int");
+                }
+                RustType::Common(RustCommonType {
+                    path: rust_enum_path.clone(),
+                    generic_arguments: None,
+                })
             };
+            api_to_ffi_conversion = RustToFfiTypeConversion::FlagsToInt { api_type };
         };
 
         RustFinalType::new(rust_ffi_type, api_to_ffi_conversion)
@@ -912,6 +1173,7 @@ impl State<'_, '_> {
             path: self.generate_rust_path(&data.path, NameType::FfiFunction)?,
             kind: RustFunctionKind::FfiFunction,
             arguments: args,
+            is_variadic: data.variadic_arguments.is_some(),
             is_unsafe: false,
         };
         Ok(function)
@@ -1000,16 +1262,6 @@ impl State<'_, '_> {
             bail!("self type is not Common");
         };
 
-        let associated_types = if operator_info.has_output_associated_type {
-            let output = RustTraitAssociatedType {
-                name: "Output".into(),
-                value: unnamed_function.return_type.api_type().clone(),
-            };
-            vec![output]
-        } else {
-            Vec::new()
-        };
-
         let mut function = unnamed_function.with_path(trait_path.join(operator_info.function_name));
         function.is_unsafe = operator_info.is_unsafe;
         function.arguments[0].argument_type = RustFinalType::new(
@@ -1049,13 +1301,74 @@ impl State<'_, '_> {
                     )?;
                 }
             }
+            ReturnTypeConstraint::U64 => {
+                if function.return_type.conversion() != &RustToFfiTypeConversion::None {
+                    bail!("return type is not a plain integer");
+                }
+                let ffi_type = function.return_type.ffi_type().clone();
+                if !is_integer_type(&ffi_type) {
+                    bail!("return type is not an integer: {:?}", ffi_type);
+                }
+                let api_type = RustType::Primitive("u64".into());
+                if ffi_type != api_type {
+                    function.return_type =
+                        RustFinalType::new(ffi_type, RustToFfiTypeConversion::AsCast { api_type })?;
+                }
+            }
             ReturnTypeConstraint::Bool => {
                 if function.return_type.api_type() != &RustType::bool() {
                     bail!("return type is not bool");
                 }
             }
+            ReturnTypeConstraint::CppBox => {
+                if function.return_type.conversion() != &RustToFfiTypeConversion::CppBoxToPtr {
+                    bail!("return type is not CppBox");
+                }
+            }
+            ReturnTypeConstraint::Pointer { is_const } => {
+                if function.return_type.conversion().is_exception_result() {
+                    bail!("functions throwing exceptions can't return a raw pointer");
+                }
+                let ffi_type = function.return_type.ffi_type().clone();
+                let target = match &ffi_type {
+                    RustType::PointerLike {
+                        kind: RustPointerLikeTypeKind::Pointer,
+                        is_const: ffi_is_const,
+                        target,
+                    } => {
+                        if *ffi_is_const && !is_const {
+                            bail!("return type is a const pointer");
+                        }
+                        (**target).clone()
+                    }
+                    _ => bail!("return type is not a pointer"),
+                };
+                let api_type = RustType::new_pointer(is_const, target);
+                let conversion = if api_type == ffi_type {
+                    RustToFfiTypeConversion::None
+                } else {
+                    RustToFfiTypeConversion::AsCast { api_type }
+                };
+                function.return_type = RustFinalType::new(ffi_type, conversion)?;
+            }
         }
 
+        let associated_types = if operator_info.has_output_associated_type {
+            let value = match operator_info.return_type_constraint {
+                ReturnTypeConstraint::Pointer { .. } => {
+                    function.return_type.api_type().pointer_like_to_target()?
+                }
+                _ => function.return_type.api_type().clone(),
+            };
+            let output = RustTraitAssociatedType {
+                name: "Output".into(),
+                value,
+            };
+            vec![output]
+        } else {
+            Vec::new()
+        };
+
         Ok(RustTraitImpl {
             target_type,
             parent_path,
@@ -1231,6 +1544,109 @@ impl State<'_, '_> {
         Ok(results)
     }
 
+    /// Generates `CppDefault` implementation for a default constructor.
+    fn process_default_constructor(
+        unnamed_function: UnnamedRustFunction,
+        crate_name: &str,
+        trait_types: &[TraitTypes],
+    ) -> Result<RustTraitImpl> {
+        if !unnamed_function.arguments.is_empty() {
+            bail!("no arguments expected");
+        }
+        if unnamed_function.return_type.conversion() != &RustToFfiTypeConversion::CppBoxToPtr {
+            bail!("return type is not CppBox");
+        }
+        let target_type = unnamed_function
+            .return_type
+            .ffi_type()
+            .pointer_like_to_target()?;
+        let parent_path = if let RustType::Common(RustCommonType { path, .. }) = &target_type {
+            if path.crate_name() != crate_name {
+                bail!("self type is outside current crate");
+            }
+            path.parent()?
+        } else {
+            bail!("self type is not Common");
+        };
+
+        let trait_path = RustPath::from_good_str("cpp_core::CppDefault");
+        let trait_type = RustCommonType {
+            path: trait_path.clone(),
+            generic_arguments: None,
+        };
+        check_trait_impl_uniqueness(trait_types, &target_type, &trait_type)?;
+
+        let mut function = unnamed_function.with_path(trait_path.join("cpp_default"));
+        function.is_unsafe = true;
+        Ok(RustTraitImpl {
+            target_type,
+            parent_path,
+            trait_type,
+            associated_types: Vec::new(),
+            functions: vec![function],
+            extra_kind: RustTraitImplExtraKind::Normal,
+        })
+    }
+
+    /// Generates `CastFrom` implementation for a conversion operator,
+    /// allowing to use it through `cast_into()`. Conversions to class types
+    /// produce a `CppBox` because the result is a new object, so they can't
+    /// directly produce `Ref` values accepted by `impl CastInto<Ref<T>>` arguments.
+    fn process_conversion_operator(
+        unnamed_function: UnnamedRustFunction,
+        crate_name: &str,
+        trait_types: &[TraitTypes],
+    ) -> Result<RustTraitImpl> {
+        if unnamed_function.arguments.len() != 1 {
+            bail!("1 argument expected");
+        }
+        let target_type = unnamed_function.return_type.api_type().clone();
+        match &target_type {
+            RustType::Primitive(_) | RustType::Common(_) => {}
+            _ => bail!("unsupported target type: {:?}", target_type),
+        }
+        if target_type.lifetime().is_some() {
+            bail!("target type can't have a lifetime");
+        }
+
+        let self_value_type = unnamed_function.arguments[0]
+            .argument_type
+            .ffi_type()
+            .pointer_like_to_target()?;
+        let parent_path = if let RustType::Common(RustCommonType { path, .. }) = &self_value_type {
+            if path.crate_name() != crate_name {
+                bail!("self type is outside current crate");
+            }
+            path.parent()?
+        } else {
+            bail!("self type is not Common");
+        };
+
+        let trait_path = RustPath::from_good_str("cpp_core::CastFrom");
+        let mut function = unnamed_function.with_path(trait_path.join("cast_from"));
+        function.is_unsafe = true;
+        function.arguments[0].name = "value".to_string();
+        function.arguments[0].argument_type = RustFinalType::new(
+            function.arguments[0].argument_type.ffi_type().clone(),
+            RustToFfiTypeConversion::RefToPtr { lifetime: None },
+        )?;
+
+        let trait_type = RustCommonType {
+            path: trait_path,
+            generic_arguments: Some(vec![function.arguments[0].argument_type.api_type().clone()]),
+        };
+        check_trait_impl_uniqueness(trait_types, &target_type, &trait_type)?;
+
+        Ok(RustTraitImpl {
+            target_type,
+            parent_path,
+            trait_type,
+            associated_types: Vec::new(),
+            functions: vec![function],
+            extra_kind: RustTraitImplExtraKind::Normal,
+        })
+    }
+
     fn convert_callbacks_to_closure(
         &self,
         id: &ItemId,
@@ -1241,16 +1657,37 @@ impl State<'_, '_> {
             return Ok(());
         };
 
-        let wrapper = self
+        let source_ffi_item = self
             .data
             .db
             .source_ffi_item(id)?
             .ok_or_else(|| err_msg("source ffi item not found"))?
-            .item
-            .as_slot_wrapper_ref()
-            .ok_or_else(|| err_msg("invalid source ffi item type"))?;
+            .item;
+
+        let (arguments, return_type) = match source_ffi_item {
+            CppFfiItem::QtSlotWrapper(wrapper) => (&wrapper.arguments, None),
+            CppFfiItem::SubclassWrapper(wrapper) => {
+                let setter_name = &self
+                    .data
+                    .db
+                    .source_cpp_item(id)?
+                    .ok_or_else(|| err_msg("source cpp item not found"))?
+                    .item
+                    .path()
+                    .ok_or_else(|| err_msg("source cpp item must have a path"))?
+                    .last()
+                    .name;
+                let item = wrapper
+                    .overrides
+                    .iter()
+                    .find(|item| &item.setter_name() == setter_name)
+                    .ok_or_else(|| err_msg("virtual function override not found"))?;
+                (&item.arguments, Some(&item.return_type))
+            }
+            _ => bail!("invalid source ffi item type"),
+        };
 
-        let closure_arguments = wrapper.arguments.iter().map_if_ok(|arg| {
+        let closure_arguments = arguments.iter().map_if_ok(|arg| {
             self.rust_final_type(
                 arg,
                 // closure argument should be handled in the same way
@@ -1258,16 +1695,24 @@ impl State<'_, '_> {
                 &CppFfiArgumentMeaning::ReturnValue,
                 ReturnValueAllocationPlace::NotApplicable,
                 Some(&checks),
+                None,
             )
         })?;
-        let closure_return_type = self.rust_final_type(
-            &CppFfiType::void(),
-            // TODO: not sure about the meaning.
-            &CppFfiArgumentMeaning::Argument(0),
-            ReturnValueAllocationPlace::NotApplicable,
-            Some(&checks),
-        )?;
+        let closure_return_type = if let Some(return_type) = return_type {
+            self.closure_return_type(return_type, checks)?
+        } else {
+            self.rust_final_type(
+                &CppFfiType::void(),
+                // TODO: not sure about the meaning.
+                &CppFfiArgumentMeaning::Argument(0),
+                ReturnValueAllocationPlace::NotApplicable,
+                Some(&checks),
+                None,
+            )?
+        };
 
+        let deleter_ffi_index = function.arguments[function.arguments.len() - 2].ffi_index;
+        let data_ffi_index = function.arguments[function.arguments.len() - 1].ffi_index;
         function.arguments.drain(function.arguments.len() - 2..);
         let arg = function
             .arguments
@@ -1278,37 +1723,196 @@ impl State<'_, '_> {
             RustToFfiTypeConversion::ClosureToCallback(Box::new(RustClosureToCallbackConversion {
                 closure_arguments,
                 closure_return_type,
+                callback_data_index: 0,
+                data_ffi_index,
+                deleter_ffi_index: Some(deleter_ffi_index),
+                is_leaked: false,
             })),
         )?;
 
         Ok(())
     }
 
-    /// Converts one function to a `RustSingleMethod`.
-    fn process_rust_function(
+    /// Returns type of the value returned by a closure to C++.
+    fn closure_return_type(
         &self,
-        item: DbItem<&CppFfiFunction>,
+        return_type: &CppFfiType,
         checks: &CppChecks,
-        trait_types: &[TraitTypes],
-    ) -> Result<Vec<ProcessedFfiItem>> {
-        let function = item.item;
-        let rust_ffi_function = self.generate_ffi_function(&function)?;
-        let ffi_function_path = rust_ffi_function.path.clone();
-        let mut results = vec![ProcessedFfiItem::Item(RustItem::Function(
-            rust_ffi_function,
-        ))];
-
-        let mut arguments = Vec::new();
-        for (arg_index, arg) in function.arguments.iter().enumerate() {
-            if arg.meaning != CppFfiArgumentMeaning::ReturnValue {
-                let arg_type = self.rust_final_type(
-                    &arg.argument_type,
-                    &arg.meaning,
-                    function.allocation_place,
-                    Some(checks),
-                )?;
-                arguments.push(RustFunctionArgument {
-                    ffi_index: arg_index,
+    ) -> Result<RustFinalType> {
+        // closure return value should be handled in the same way
+        // as an argument (value is consumed behind FFI)
+        let final_type = self.rust_final_type(
+            return_type,
+            &CppFfiArgumentMeaning::Argument(0),
+            ReturnValueAllocationPlace::NotApplicable,
+            Some(checks),
+            None,
+        )?;
+        if let CppToFfiTypeConversion::ValueToPointer { .. } = return_type.conversion() {
+            // the object is deleted by C++ after it's copied
+            return RustFinalType::new(
+                final_type.ffi_type().clone(),
+                RustToFfiTypeConversion::CppBoxToPtr,
+            );
+        }
+        // closure must return a concrete type
+        if let RustToFfiTypeConversion::ImplCastInto(conversion) = final_type.conversion() {
+            return RustFinalType::new(final_type.ffi_type().clone(), (**conversion).clone());
+        }
+        Ok(final_type)
+    }
+
+    /// Returns type of the Rust constant corresponding to `constant`.
+    fn constant_rust_type(&self, constant: &CppConstant) -> Result<RustType> {
+        if let CppConstantValue::String(_) = &constant.value {
+            Ok(RustType::PointerLike {
+                kind: RustPointerLikeTypeKind::Reference {
+                    lifetime: Some("static".into()),
+                },
+                is_const: true,
+                target: Box::new(RustType::Primitive("str".into())),
+            })
+        } else {
+            self.ffi_type_to_rust_ffi_type(&constant.constant_type)
+        }
+    }
+
+    /// Returns Rust type alias generated for C++ typedef `cpp_path`, if any.
+    fn find_type_alias(&self, cpp_path: &CppPath) -> Option<RustTypeAlias> {
+        self.data
+            .db
+            .find_rust_items_for_cpp_path(cpp_path, true)
+            .ok()?
+            .find_map(|item| item.item.as_type_alias_ref())
+            .cloned()
+    }
+
+    /// Replaces types in the public API of `rust_function` with Rust type aliases
+    /// where the C++ declaration used the corresponding typedefs.
+    fn apply_type_aliases(
+        &self,
+        rust_function: &mut UnnamedRustFunction,
+        ffi_function: &CppFfiFunction,
+        cpp_function: &CppFunction,
+    ) -> Result<()> {
+        let apply = |final_type: &RustFinalType, alias_path: Option<&CppPath>| {
+            if let Some(alias) = alias_path.and_then(|path| self.find_type_alias(path)) {
+                let alias_type = RustType::Common(RustCommonType {
+                    path: alias.path,
+                    generic_arguments: None,
+                });
+                final_type.with_type_alias(&alias.target, &alias_type)
+            } else {
+                final_type.clone()
+            }
+        };
+
+        for arg in &mut rust_function.arguments {
+            let ffi_arg = ffi_function
+                .arguments
+                .get(arg.ffi_index)
+                .ok_or_else(|| err_msg("invalid ffi argument index"))?;
+            if let CppFfiArgumentMeaning::Argument(index) = ffi_arg.meaning {
+                let alias_path = cpp_function
+                    .arguments
+                    .get(index)
+                    .and_then(|arg| arg.type_alias.as_ref());
+                arg.argument_type = apply(&arg.argument_type, alias_path);
+            }
+        }
+        rust_function.return_type = apply(
+            &rust_function.return_type,
+            cpp_function.return_type_alias.as_ref(),
+        );
+        Ok(())
+    }
+
+    /// Converts one function to a `RustSingleMethod`.
+    fn process_rust_function(
+        &self,
+        item: DbItem<&CppFfiFunction>,
+        checks: &CppChecks,
+        trait_types: &[TraitTypes],
+    ) -> Result<Vec<ProcessedFfiItem>> {
+        let function = item.item;
+        let rust_ffi_function = self.generate_ffi_function(&function)?;
+        let ffi_function_path = rust_ffi_function.path.clone();
+        let mut results = vec![ProcessedFfiItem::Item(RustItem::Function(
+            rust_ffi_function,
+        ))];
+
+        if function.variadic_arguments.is_some() {
+            // variadic functions can't be defined in Rust,
+            // so the FFI function is exposed in the public API
+            let cpp_item = self
+                .data
+                .db
+                .source_cpp_item(&item.id)?
+                .ok_or_else(|| err_msg("source cpp item not found"))?
+                .item;
+            let cpp_path = cpp_item
+                .path()
+                .ok_or_else(|| err_msg("cpp function expected to have a path"))?;
+            let path = self.generate_rust_path(cpp_path, NameType::ApiFunction(item.clone()))?;
+            results.push(ProcessedFfiItem::Item(RustItem::Reexport(RustReexport {
+                path: self.data.db.make_unique_rust_path(&path),
+                target: ffi_function_path,
+                source: RustReexportSource::VariadicFunction,
+            })));
+            return Ok(results);
+        }
+
+        if function.kind.is_smart_pointer() {
+            // the FFI function is only used by the smart pointer trait implementation
+            // generated in `generate_smart_pointer_impls`
+            return Ok(results);
+        }
+
+        if function.kind == CppFfiFunctionKind::StreamToString {
+            // the FFI function is only used by the `Display` implementation
+            if let Some(extra_impl) = self.process_stream_to_string(function, ffi_function_path)? {
+                results.push(ProcessedFfiItem::Item(RustItem::ExtraImpl(extra_impl)));
+            }
+            return Ok(results);
+        }
+
+        let source_cpp_function = self
+            .data
+            .db
+            .source_cpp_item(&item.id)?
+            .and_then(|cpp_item| cpp_item.item.as_function_ref());
+
+        let mut arguments = Vec::new();
+        for (arg_index, arg) in function.arguments.iter().enumerate() {
+            let is_callback_extra_argument = matches!(
+                arg.meaning,
+                CppFfiArgumentMeaning::CallbackData(_) | CppFfiArgumentMeaning::CallbackDeleter(_)
+            );
+            if arg.meaning != CppFfiArgumentMeaning::ReturnValue
+                && arg.meaning != CppFfiArgumentMeaning::Exception
+                && !is_callback_extra_argument
+            {
+                let arg_type = if arg.argument_type.conversion()
+                    == &CppToFfiTypeConversion::FunctionToCallback
+                {
+                    self.std_function_closure_type(function, arg_index, checks)?
+                } else {
+                    let annotation = match (&arg.meaning, source_cpp_function) {
+                        (CppFfiArgumentMeaning::Argument(index), Some(cpp_function)) => {
+                            self.pointer_annotation(cpp_function, Some(*index))?
+                        }
+                        _ => None,
+                    };
+                    self.rust_final_type(
+                        &arg.argument_type,
+                        &arg.meaning,
+                        function.allocation_place,
+                        Some(checks),
+                        annotation,
+                    )?
+                };
+                arguments.push(RustFunctionArgument {
+                    ffi_index: arg_index,
                     argument_type: arg_type,
                     name: if arg.meaning == CppFfiArgumentMeaning::This {
                         "self".to_string()
@@ -1332,15 +1936,21 @@ impl State<'_, '_> {
                 &arg.meaning,
                 function.allocation_place,
                 Some(checks),
+                None,
             )?
         } else {
             // none of the arguments has return value meaning,
             // so FFI return value must be used
+            let annotation = match source_cpp_function {
+                Some(cpp_function) => self.pointer_annotation(cpp_function, None)?,
+                None => None,
+            };
             self.rust_final_type(
                 &function.return_type,
                 &CppFfiArgumentMeaning::ReturnValue,
                 function.allocation_place,
                 Some(checks),
+                annotation,
             )?
         };
         if return_type.api_type().is_ref() && return_type.api_type().lifetime().is_none() {
@@ -1378,11 +1988,37 @@ impl State<'_, '_> {
             }
         }
 
+        if let Some(cpp_function) = source_cpp_function {
+            if let Some(success) = self.status_success(cpp_function)? {
+                let is_supported = return_type.conversion() == &RustToFfiTypeConversion::None
+                    && if success == StatusSuccess::True {
+                        return_type.ffi_type() == &RustType::bool()
+                    } else {
+                        is_integer_type(return_type.ffi_type())
+                    };
+                if is_supported {
+                    return_type = RustFinalType::new(
+                        return_type.ffi_type().clone(),
+                        RustToFfiTypeConversion::StatusResult { success },
+                    )?;
+                } else {
+                    // the wrapper is still useful without the conversion
+                    debug!(
+                        "unsupported return type of a function returning status: {:?}",
+                        return_type.ffi_type()
+                    );
+                }
+            }
+        }
+
         let mut unnamed_function = UnnamedRustFunction {
             is_public: true,
             arguments,
             return_type,
-            kind: RustFunctionKind::FfiWrapper(RustFfiWrapperData { ffi_function_path }),
+            kind: RustFunctionKind::FfiWrapper(RustFfiWrapperData {
+                ffi_function_path,
+                variant: RustFfiWrapperVariant::Main,
+            }),
             is_unsafe: true,
         };
         self.convert_callbacks_to_closure(&item.id, &mut unnamed_function, checks)?;
@@ -1394,6 +2030,45 @@ impl State<'_, '_> {
             .ok_or_else(|| err_msg("source cpp item not found"))?
             .item;
 
+        if let Some(cpp_function) = cpp_item.as_function_ref() {
+            self.apply_type_aliases(&mut unnamed_function, function, cpp_function)?;
+        }
+
+        if function
+            .arguments
+            .iter()
+            .any(|arg| arg.meaning == CppFfiArgumentMeaning::Exception)
+        {
+            unnamed_function.return_type = unnamed_function.return_type.with_exception_result();
+        }
+
+        if function.kind == CppFfiFunctionKind::StdHash {
+            // the FFI function is only used by the `CppHash` implementation
+            match State::process_operator_as_trait_impl(
+                unnamed_function,
+                TraitImplInfo::cpp_hash(),
+                self.data.db.crate_name(),
+                trait_types,
+            ) {
+                Ok(item) => {
+                    // a registered hash function takes precedence over `std::hash`
+                    let already_exists = self.data.db.rust_items().any(|other| {
+                        other.item.as_trait_impl_ref().map_or(false, |other| {
+                            other.trait_type.path == item.trait_type.path
+                                && other.target_type == item.target_type
+                        })
+                    });
+                    if !already_exists {
+                        results.push(ProcessedFfiItem::Item(RustItem::TraitImpl(item)));
+                    }
+                }
+                Err(err) => {
+                    debug!("failed to implement CppHash using std::hash: {}", err);
+                }
+            }
+            return Ok(results);
+        }
+
         if let CppFfiFunctionKind::Function = &function.kind {
             let cpp_function = cpp_item
                 .as_function_ref()
@@ -1416,7 +2091,42 @@ impl State<'_, '_> {
             if cpp_function.operator.as_ref() == Some(&CppOperator::NotEqualTo) {
                 bail!("NotEqualTo is not needed in public API because PartialEq is used");
             }
-            if let Some(operator_info) = TraitImplInfo::new(cpp_function) {
+            if let Some(CppOperator::Conversion(_)) = &cpp_function.operator {
+                match State::process_conversion_operator(
+                    unnamed_function.clone(),
+                    self.data.db.crate_name(),
+                    trait_types,
+                ) {
+                    Ok(item) => {
+                        results.push(ProcessedFfiItem::Item(RustItem::TraitImpl(item)));
+                    }
+                    Err(err) => {
+                        debug!("failed to convert conversion operator to trait: {}", err);
+                    }
+                }
+            }
+            if cpp_function.is_constructor() && cpp_function.arguments.is_empty() {
+                match State::process_default_constructor(
+                    unnamed_function.clone(),
+                    self.data.db.crate_name(),
+                    trait_types,
+                ) {
+                    Ok(item) => {
+                        results.push(ProcessedFfiItem::Item(RustItem::TraitImpl(item)));
+                    }
+                    Err(err) => {
+                        debug!("failed to implement CppDefault: {}", err);
+                    }
+                }
+            }
+            let operator_info = TraitImplInfo::new(cpp_function).or_else(|| {
+                if self.is_hash_function(cpp_function) {
+                    TraitImplInfo::hash(cpp_function)
+                } else {
+                    None
+                }
+            });
+            if let Some(operator_info) = operator_info {
                 match State::process_operator_as_trait_impl(
                     unnamed_function.clone(),
                     operator_info,
@@ -1424,6 +2134,9 @@ impl State<'_, '_> {
                     trait_types,
                 ) {
                     Ok(item) => {
+                        if let Some(extra_impl) = cpp_box_eq_impl(&item) {
+                            results.push(ProcessedFfiItem::Item(RustItem::ExtraImpl(extra_impl)));
+                        }
                         results.push(ProcessedFfiItem::Item(RustItem::TraitImpl(item)));
                         if !operator_info.is_inherent {
                             return Ok(results);
@@ -1438,9 +2151,9 @@ impl State<'_, '_> {
             }
         }
 
-        let cpp_path = cpp_item
-            .path()
-            .ok_or_else(|| err_msg("cpp item (function or field) expected to have a path"))?;
+        let cpp_path = cpp_item.path().ok_or_else(|| {
+            err_msg("cpp item (function, field or variable) expected to have a path")
+        })?;
 
         if let CppFfiFunctionKind::Function = &function.kind {
             let cpp_function = cpp_item
@@ -1479,6 +2192,55 @@ impl State<'_, '_> {
         }
 
         let desired_path = self.generate_rust_path(cpp_path, NameType::ApiFunction(item))?;
+        if let Some(cpp_function) = cpp_item.as_function_ref() {
+            if let Some(out_function) =
+                self.out_arguments_function(&unnamed_function, function, cpp_function)?
+            {
+                let suffix = if out_function
+                    .return_type
+                    .conversion()
+                    .as_out_arguments_ref()
+                    .map_or(false, |conversion| conversion.ok_flag.is_some())
+                {
+                    "checked"
+                } else {
+                    "out"
+                };
+                let mut out_desired_path = desired_path.clone();
+                *out_desired_path.last_mut() = format!("{}_{}", desired_path.last(), suffix);
+                results.push(ProcessedFfiItem::Function(FunctionWithDesiredPath {
+                    function: out_function,
+                    desired_path: out_desired_path,
+                }));
+            }
+            if let Some(closure_function) =
+                self.closure_function(&unnamed_function, function, cpp_function, checks)?
+            {
+                let mut closure_desired_path = desired_path.clone();
+                *closure_desired_path.last_mut() = format!("{}_closure", desired_path.last());
+                results.push(ProcessedFfiItem::Function(FunctionWithDesiredPath {
+                    function: closure_function,
+                    desired_path: closure_desired_path,
+                }));
+            }
+            if let Some(slice_function) =
+                self.slice_arguments_function(&unnamed_function, function, cpp_function)?
+            {
+                let is_str = slice_function.arguments.iter().all(|arg| {
+                    match arg.argument_type.conversion() {
+                        RustToFfiTypeConversion::SliceToPtr { is_str, .. } => *is_str,
+                        _ => true,
+                    }
+                });
+                let suffix = if is_str { "str" } else { "slice" };
+                let mut slice_desired_path = desired_path.clone();
+                *slice_desired_path.last_mut() = format!("{}_{}", desired_path.last(), suffix);
+                results.push(ProcessedFfiItem::Function(FunctionWithDesiredPath {
+                    function: slice_function,
+                    desired_path: slice_desired_path,
+                }));
+            }
+        }
         results.push(ProcessedFfiItem::Function(FunctionWithDesiredPath {
             function: unnamed_function,
             desired_path,
@@ -1486,72 +2248,648 @@ impl State<'_, '_> {
         Ok(results)
     }
 
-    fn find_wrapper_type(&self, cpp_path: &CppPath) -> Result<DbItem<&RustItem>> {
-        self.data
-            .db
-            .find_rust_items_for_cpp_path(cpp_path, true)?
-            .find(|item| item.item.is_wrapper_type())
-            .ok_or_else(|| {
-                format_err!("no Rust type wrapper for {}", cpp_path.to_cpp_pseudo_code())
+    /// Generates a variant of `function` that accepts a closure in place of a callback
+    /// function pointer and its user data (and deleter, if any). Returns `None` if
+    /// the function doesn't accept a callback.
+    fn closure_function(
+        &self,
+        function: &UnnamedRustFunction,
+        ffi_function: &CppFfiFunction,
+        cpp_function: &CppFunction,
+        checks: &CppChecks,
+    ) -> Result<Option<UnnamedRustFunction>> {
+        if function
+            .arguments
+            .iter()
+            .any(|arg| arg.argument_type.conversion().as_callback_ref().is_some())
+        {
+            // only one closure argument is supported
+            return Ok(None);
+        }
+        let hook_conventions = if let Some(hook) = self.data.config.callback_convention_hook() {
+            hook(cpp_function)?
+        } else {
+            None
+        };
+        // a detected convention without a deleter doesn't guarantee that the callback
+        // is not stored by the C++ function, so the closure must be leaked in this case
+        let is_detected = hook_conventions.is_none();
+        let conventions =
+            hook_conventions.unwrap_or_else(|| detect_callback_conventions(cpp_function));
+
+        let rust_index = |cpp_index: usize| {
+            function.arguments.iter().position(|arg| {
+                ffi_function.arguments[arg.ffi_index].meaning
+                    == CppFfiArgumentMeaning::Argument(cpp_index)
             })
+        };
+        for convention in conventions {
+            let mut indexes = vec![convention.callback_argument, convention.data_argument];
+            indexes.extend(convention.deleter_argument);
+            let rust_indexes = match indexes
+                .iter()
+                .map(|i| rust_index(*i))
+                .collect::<Option<Vec<_>>>()
+            {
+                Some(rust_indexes) => rust_indexes,
+                // some of the arguments are omitted in this variant of the function
+                None => continue,
+            };
+            let conversion =
+                match self.closure_to_callback_conversion(cpp_function, &convention, checks) {
+                    Ok(conversion) => RustClosureToCallbackConversion {
+                        data_ffi_index: function.arguments[rust_indexes[1]].ffi_index,
+                        deleter_ffi_index: rust_indexes
+                            .get(2)
+                            .map(|index| function.arguments[*index].ffi_index),
+                        is_leaked: is_detected && convention.deleter_argument.is_none(),
+                        ..conversion
+                    },
+                    Err(err) => {
+                        debug!(
+                            "invalid callback convention {:?} of {}: {}",
+                            convention,
+                            cpp_function.short_text(),
+                            err
+                        );
+                        continue;
+                    }
+                };
+
+            let mut arguments = function.arguments.clone();
+            let callback_argument = &mut arguments[rust_indexes[0]];
+            callback_argument.argument_type = RustFinalType::new(
+                callback_argument.argument_type.ffi_type().clone(),
+                RustToFfiTypeConversion::ClosureToCallback(Box::new(conversion)),
+            )?;
+            let mut removed_indexes = rust_indexes[1..].to_vec();
+            removed_indexes.sort();
+            for index in removed_indexes.into_iter().rev() {
+                arguments.remove(index);
+            }
+            return Ok(Some(UnnamedRustFunction {
+                is_public: function.is_public,
+                is_unsafe: function.is_unsafe,
+                kind: function
+                    .kind
+                    .with_ffi_wrapper_variant(RustFfiWrapperVariant::Closure)?,
+                arguments,
+                return_type: function.return_type.clone(),
+            }));
+        }
+        Ok(None)
     }
 
-    fn get_path_scope(
+    /// Returns the conversion of a closure to the callback of `cpp_function`
+    /// described by `convention`. FFI indexes of the data and deleter arguments
+    /// are not filled.
+    fn closure_to_callback_conversion(
         &self,
-        parent_path: &CppPath,
-        name_type: NameType<'_>,
-    ) -> Result<RustPathScope> {
-        trace!("get_path_scope({:?}, {:?})", parent_path, name_type);
-        if let Some(hook) = self.data.config.rust_path_scope_hook() {
-            if let Some(strategy) = hook(parent_path)? {
-                return Ok(strategy);
-            }
+        cpp_function: &CppFunction,
+        convention: &CallbackConvention,
+        checks: &CppChecks,
+    ) -> Result<RustClosureToCallbackConversion> {
+        check_callback_convention(cpp_function, convention)?;
+        let callback_type =
+            match &cpp_function.arguments[convention.callback_argument].argument_type {
+                CppType::FunctionPointer(callback_type) => callback_type,
+                _ => bail!("callback argument must be a function pointer"),
+            };
+        self.callback_type_conversion(callback_type, convention.callback_data_argument, checks)
+    }
+
+    /// Returns the type of the closure accepted in place of the `std::function`
+    /// argument of `function` at `ffi_index`.
+    fn std_function_closure_type(
+        &self,
+        function: &CppFfiFunction,
+        ffi_index: usize,
+        checks: &CppChecks,
+    ) -> Result<RustFinalType> {
+        let callback_count = function
+            .arguments
+            .iter()
+            .filter(|arg| {
+                arg.argument_type.conversion() == &CppToFfiTypeConversion::FunctionToCallback
+            })
+            .count();
+        if callback_count > 1 {
+            bail!("only one std::function argument is supported");
         }
+        let arg = &function.arguments[ffi_index];
+        let cpp_index = match arg.meaning {
+            CppFfiArgumentMeaning::Argument(index) => index,
+            _ => bail!(
+                "unexpected meaning of std::function argument: {:?}",
+                arg.meaning
+            ),
+        };
+        let find_ffi_index = |meaning: CppFfiArgumentMeaning| {
+            function
+                .arguments
+                .iter()
+                .position(|arg| arg.meaning == meaning)
+                .ok_or_else(|| format_err!("argument not found: {:?}", meaning))
+        };
+        let callback_type = arg
+            .argument_type
+            .ffi_type()
+            .as_function_pointer()
+            .ok_or_else(|| err_msg("callback type must be a function pointer"))?;
+        let conversion = RustClosureToCallbackConversion {
+            data_ffi_index: find_ffi_index(CppFfiArgumentMeaning::CallbackData(cpp_index))?,
+            deleter_ffi_index: Some(find_ffi_index(CppFfiArgumentMeaning::CallbackDeleter(
+                cpp_index,
+            ))?),
+            ..self.callback_type_conversion(callback_type, 0, checks)?
+        };
+        RustFinalType::new(
+            self.ffi_type_to_rust_ffi_type(arg.argument_type.ffi_type())?,
+            RustToFfiTypeConversion::ClosureToCallback(Box::new(conversion)),
+        )
+    }
 
-        let allow_module_for_nested;
-        let allow_wrapper_type;
-        match name_type {
-            NameType::Type { .. } | NameType::Module { .. } => {
-                allow_module_for_nested = true;
-                allow_wrapper_type = false;
+    /// Returns the conversion of a closure to a callback of `callback_type`
+    /// that receives the user data in the argument at `callback_data_index`.
+    /// FFI indexes of the data and deleter arguments are not filled.
+    fn callback_type_conversion(
+        &self,
+        callback_type: &CppFunctionPointerType,
+        callback_data_index: usize,
+        checks: &CppChecks,
+    ) -> Result<RustClosureToCallbackConversion> {
+        let mut closure_arguments = Vec::new();
+        for (index, arg) in callback_type.arguments.iter().enumerate() {
+            if index == callback_data_index {
+                continue;
             }
-            _ => {
-                allow_module_for_nested = false;
-                allow_wrapper_type = true;
+            let ffi_type = ffi_type(
+                arg,
+                CppTypeRole::NotReturnType,
+                self.data.config.flag_containers(),
+            )?;
+            if ffi_type.conversion() != &CppToFfiTypeConversion::NoChange {
+                bail!("unsupported type of callback argument: {:?}", arg);
             }
-        };
+            // closure argument should be handled in the same way
+            // as return type (value is produced behind FFI)
+            closure_arguments.push(self.rust_final_type(
+                &ffi_type,
+                &CppFfiArgumentMeaning::ReturnValue,
+                ReturnValueAllocationPlace::NotApplicable,
+                Some(checks),
+                None,
+            )?);
+        }
+        let return_ffi_type = ffi_type(
+            &callback_type.return_type,
+            CppTypeRole::ReturnType,
+            self.data.config.flag_containers(),
+        )?;
+        if return_ffi_type.conversion() != &CppToFfiTypeConversion::NoChange {
+            bail!(
+                "unsupported return type of callback: {:?}",
+                callback_type.return_type
+            );
+        }
+        Ok(RustClosureToCallbackConversion {
+            closure_arguments,
+            closure_return_type: self.closure_return_type(&return_ffi_type, checks)?,
+            callback_data_index,
+            data_ffi_index: 0,
+            deleter_ffi_index: None,
+            is_leaked: false,
+        })
+    }
 
-        let mut rust_items = match self
-            .data
-            .db
-            .find_rust_items_for_cpp_path(parent_path, false)
-        {
-            Ok(r) => r,
-            Err(err) => match name_type {
-                NameType::Type {
-                    is_from_other_crate,
-                }
-                | NameType::Module {
-                    is_from_other_crate,
-                } if is_from_other_crate => {
-                    return Ok(self.default_path_scope());
-                }
-                _ => return Err(err),
-            },
+    /// Generates a variant of `function` that accepts slices in place of pairs of
+    /// pointer and length arguments. Returns `None` if the function doesn't have such pairs.
+    fn slice_arguments_function(
+        &self,
+        function: &UnnamedRustFunction,
+        ffi_function: &CppFfiFunction,
+        cpp_function: &CppFunction,
+    ) -> Result<Option<UnnamedRustFunction>> {
+        let hook_pairs = if let Some(hook) = self.data.config.slice_arguments_hook() {
+            hook(&cpp_function.path)?
+        } else {
+            None
         };
+        let pairs = hook_pairs.unwrap_or_else(|| detect_slice_arguments(cpp_function));
 
-        let rust_item = rust_items
-            .find(|item| {
-                (allow_wrapper_type && item.item.is_wrapper_type())
-                    || (item.item.is_module() && !item.item.is_module_for_nested())
-                    || (allow_module_for_nested && item.item.is_module_for_nested())
+        let rust_index = |cpp_index: usize| {
+            function.arguments.iter().position(|arg| {
+                ffi_function.arguments[arg.ffi_index].meaning
+                    == CppFfiArgumentMeaning::Argument(cpp_index)
             })
-            .ok_or_else(|| {
-                format_err!(
-                    "no Rust type wrapper for {}",
-                    parent_path.to_cpp_pseudo_code()
-                )
-            })?;
+        };
+        let mut arguments = function.arguments.clone();
+        let mut removed_indexes = Vec::new();
+        for (pointer_index, length_index) in pairs {
+            let (rust_pointer_index, rust_length_index) =
+                match (rust_index(pointer_index), rust_index(length_index)) {
+                    (Some(a), Some(b)) => (a, b),
+                    // at least one of the arguments is omitted in this variant of the function
+                    _ => continue,
+                };
+            let pointer_type = &cpp_function.arguments[pointer_index].argument_type;
+            let length_type = &cpp_function.arguments[length_index].argument_type;
+            if !is_slice_pointer_type(pointer_type)
+                || !is_integer_cpp_type(length_type)
+                || removed_indexes.contains(&rust_pointer_index)
+                || removed_indexes.contains(&rust_length_index)
+                || arguments[rust_pointer_index]
+                    .argument_type
+                    .conversion()
+                    .is_slice_to_ptr()
+            {
+                debug!(
+                    "invalid slice arguments ({}, {}) of {}",
+                    pointer_index,
+                    length_index,
+                    cpp_function.short_text()
+                );
+                continue;
+            }
+            let is_str = pointer_type
+                == &CppType::new_pointer(
+                    true,
+                    CppType::BuiltInNumeric(CppBuiltInNumericType::Char),
+                );
+            let length_argument = &function.arguments[rust_length_index];
+            let pointer_argument = &mut arguments[rust_pointer_index];
+            pointer_argument.argument_type = RustFinalType::new(
+                pointer_argument.argument_type.ffi_type().clone(),
+                RustToFfiTypeConversion::SliceToPtr {
+                    length_ffi_index: length_argument.ffi_index,
+                    length_type: length_argument.argument_type.ffi_type().clone(),
+                    is_str,
+                },
+            )?;
+            removed_indexes.push(rust_length_index);
+        }
+        if removed_indexes.is_empty() {
+            return Ok(None);
+        }
+        removed_indexes.sort();
+        for index in removed_indexes.into_iter().rev() {
+            arguments.remove(index);
+        }
+        Ok(Some(UnnamedRustFunction {
+            is_public: function.is_public,
+            is_unsafe: function.is_unsafe,
+            kind: function
+                .kind
+                .with_ffi_wrapper_variant(RustFfiWrapperVariant::Slice)?,
+            arguments,
+            return_type: function.return_type.clone(),
+        }))
+    }
+
+    /// Returns names of output arguments of `cpp_function` if they are
+    /// explicitly specified by the config's hook.
+    fn out_argument_names_from_hook(
+        &self,
+        cpp_function: &CppFunction,
+    ) -> Result<Option<Vec<String>>> {
+        if let Some(hook) = self.data.config.out_arguments_hook() {
+            hook(&cpp_function.path)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Generates a variant of `function` that returns values of output arguments
+    /// along with the return value, instead of accepting pointers for them.
+    /// Returns `None` if the function doesn't have output arguments.
+    fn out_arguments_function(
+        &self,
+        function: &UnnamedRustFunction,
+        ffi_function: &CppFfiFunction,
+        cpp_function: &CppFunction,
+    ) -> Result<Option<UnnamedRustFunction>> {
+        let config = self.data.config;
+        let hook_names = self.out_argument_names_from_hook(cpp_function)?;
+
+        // indexes of Rust arguments that can be output arguments
+        let mut candidates = Vec::new();
+        for (index, arg) in function.arguments.iter().enumerate() {
+            if let CppFfiArgumentMeaning::Argument(cpp_index) =
+                ffi_function.arguments[arg.ffi_index].meaning
+            {
+                let cpp_arg = &cpp_function.arguments[cpp_index];
+                if is_primitive_out_type(&cpp_arg.argument_type) {
+                    candidates.push((index, cpp_arg.name.as_str()));
+                }
+            }
+        }
+
+        let mut out_indexes = Vec::new();
+        if let Some(names) = &hook_names {
+            out_indexes.extend(
+                candidates
+                    .iter()
+                    .filter(|(_, name)| names.iter().any(|n| n == name))
+                    .map(|(index, _)| *index),
+            );
+        } else {
+            out_indexes.extend(
+                candidates
+                    .iter()
+                    .filter(|(_, name)| {
+                        config.out_argument_names().iter().any(|n| n == name)
+                            || config.ok_flag_argument_names().iter().any(|n| n == name)
+                    })
+                    .map(|(index, _)| *index),
+            );
+            if config.trailing_out_arguments() {
+                let mut index = function.arguments.len();
+                while index > 0 && candidates.iter().any(|(i, _)| *i == index - 1) {
+                    index -= 1;
+                    if !out_indexes.contains(&index) {
+                        out_indexes.push(index);
+                    }
+                }
+            }
+        }
+        if out_indexes.is_empty() {
+            return Ok(None);
+        }
+        out_indexes.sort();
+
+        let mut arguments = Vec::new();
+        let mut out_arguments = Vec::new();
+        let mut ok_flag = None;
+        for (index, arg) in function.arguments.iter().enumerate() {
+            let cpp_name = match candidates.iter().find(|(i, _)| *i == index) {
+                Some((_, cpp_name)) if out_indexes.contains(&index) => *cpp_name,
+                _ => {
+                    arguments.push(arg.clone());
+                    continue;
+                }
+            };
+            let out_argument = RustOutArgument {
+                name: arg.name.clone(),
+                ffi_index: arg.ffi_index,
+                value_type: arg.argument_type.ffi_type().pointer_like_to_target()?,
+            };
+            let is_ok_flag = out_argument.value_type == RustType::bool()
+                && config
+                    .ok_flag_argument_names()
+                    .iter()
+                    .any(|n| n == cpp_name);
+            if is_ok_flag && ok_flag.is_none() {
+                ok_flag = Some(out_argument);
+            } else {
+                out_arguments.push(out_argument);
+            }
+        }
+
+        let return_type = if function.return_type.conversion().is_exception_result() {
+            function
+                .return_type
+                .without_exception_result()?
+                .with_out_arguments(out_arguments, ok_flag)?
+                .with_exception_result()
+        } else {
+            function
+                .return_type
+                .with_out_arguments(out_arguments, ok_flag)?
+        };
+        Ok(Some(UnnamedRustFunction {
+            is_public: function.is_public,
+            is_unsafe: function.is_unsafe,
+            kind: function
+                .kind
+                .with_ffi_wrapper_variant(RustFfiWrapperVariant::OutArguments)?,
+            arguments,
+            return_type,
+        }))
+    }
+
+    /// Generates `Display` and `Debug` implementations based on a C++ output stream operator.
+    /// Returns `None` if the type is declared in another crate or already has
+    /// the implementations.
+    fn process_stream_to_string(
+        &self,
+        function: &CppFfiFunction,
+        ffi_function_path: RustPath,
+    ) -> Result<Option<RustExtraImpl>> {
+        let arg = function
+            .arguments
+            .get(0)
+            .ok_or_else(|| err_msg("no arguments"))?;
+        let target_type = self
+            .ffi_type_to_rust_ffi_type(arg.argument_type.ffi_type())?
+            .pointer_like_to_target()?;
+        let target_path = match &target_type {
+            RustType::Common(common) => common.path.clone(),
+            _ => bail!(
+                "unexpected type of stream operator argument: {:?}",
+                target_type
+            ),
+        };
+        if target_path.crate_name() != self.data.db.crate_name() {
+            return Ok(None);
+        }
+        let already_exists = self.data.db.rust_items().any(|item| {
+            item.item.as_extra_impl_ref().map_or(false, |extra_impl| {
+                if let RustExtraImplKind::CppDisplay(data) = &extra_impl.kind {
+                    data.target_type == target_type
+                } else {
+                    false
+                }
+            })
+        });
+        if already_exists {
+            return Ok(None);
+        }
+        Ok(Some(RustExtraImpl {
+            parent_path: target_path.parent()?,
+            kind: RustExtraImplKind::CppDisplay(RustCppDisplayImpl {
+                target_type,
+                ffi_function_path,
+            }),
+        }))
+    }
+
+    fn find_wrapper_type(&self, cpp_path: &CppPath) -> Result<DbItem<&RustItem>> {
+        self.data
+            .db
+            .find_rust_items_for_cpp_path(cpp_path, true)?
+            .find(|item| item.item.is_wrapper_type())
+            .ok_or_else(|| {
+                format_err!("no Rust type wrapper for {}", cpp_path.to_cpp_pseudo_code())
+            })
+    }
+
+    fn wrapper_type_path(&self, cpp_path: &CppPath) -> Result<RustPath> {
+        Ok(self
+            .find_wrapper_type(cpp_path)?
+            .item
+            .path()
+            .ok_or_else(|| err_msg("wrapper type must have a path"))?
+            .clone())
+    }
+
+    /// Returns the annotation of the pointer returned by `function` (if `argument_index`
+    /// is `None`) or accepted as its argument.
+    fn pointer_annotation(
+        &self,
+        function: &CppFunction,
+        argument_index: Option<usize>,
+    ) -> Result<Option<PointerAnnotation>> {
+        if let Some(hook) = self.data.config.pointer_annotation_hook() {
+            if let Some(annotation) = hook(function, argument_index)? {
+                return Ok(Some(annotation));
+            }
+        }
+        let argument_name = match argument_index {
+            Some(index) => Some(
+                function
+                    .arguments
+                    .get(index)
+                    .ok_or_else(|| err_msg("invalid argument index"))?
+                    .name
+                    .as_str(),
+            ),
+            None => None,
+        };
+        let mut path = function.path.clone();
+        path.last_mut().template_arguments = None;
+        Ok(self
+            .data
+            .config
+            .pointer_annotation_rules()
+            .iter()
+            .find(|rule| rule.function == path && rule.argument.as_deref() == argument_name)
+            .map(|rule| rule.annotation))
+    }
+
+    /// Returns the success condition if the value returned by `function` is a status.
+    fn status_success(&self, function: &CppFunction) -> Result<Option<StatusSuccess>> {
+        if let Some(hook) = self.data.config.status_hook() {
+            if let Some(success) = hook(function)? {
+                return Ok(Some(success));
+            }
+        }
+        let mut path = function.path.clone();
+        path.last_mut().template_arguments = None;
+        Ok(self
+            .data
+            .config
+            .status_functions()
+            .iter()
+            .find(|(status_path, _)| status_path == &path)
+            .map(|(_, success)| *success))
+    }
+
+    fn is_hash_function(&self, function: &CppFunction) -> bool {
+        let mut path = function.path.clone();
+        path.last_mut().template_arguments = None;
+        self.data.config.hash_functions().contains(&path)
+    }
+
+    fn is_bitmask_enum(&self, cpp_path: &CppPath) -> Result<bool> {
+        if let Some(hook) = self.data.config.bitmask_enum_hook() {
+            hook(cpp_path)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Returns true if `QFlags<E>` is declared for the enum `enum_path`. Bit operations
+    /// of such enums produce `QFlags`, so bitmask operations can't be added to them.
+    fn has_qflags_container(&self, enum_path: &CppPath) -> bool {
+        let qflags = CppFlagContainer::qflags();
+        if !self.data.config.flag_containers().contains(&qflags) {
+            return false;
+        }
+        let enum_type = CppType::Enum {
+            path: enum_path.clone(),
+        };
+        self.data
+            .db
+            .all_cpp_items()
+            .filter_map(|item| item.item.as_type_ref())
+            .any(|declaration| {
+                qflags.matches(&declaration.path)
+                    && declaration.path.last().template_arguments.as_ref().unwrap()[0] == enum_type
+            })
+    }
+
+    /// Returns true if bit operations can't be added to the enum wrapper `enum_type`
+    /// because it's already done or the wrapper belongs to another crate.
+    fn skip_bitmask_impl(&self, enum_type: &DbItem<&RustItem>) -> bool {
+        let enum_path = enum_type.item.path();
+        enum_type.id.crate_name() != self.data.db.crate_name()
+            || self.data.db.rust_items().any(|item| {
+                item.item.as_extra_impl_ref().map_or(false, |extra_impl| {
+                    if let RustExtraImplKind::BitmaskEnum(data) = &extra_impl.kind {
+                        Some(&data.enum_path) == enum_path
+                    } else {
+                        false
+                    }
+                })
+            })
+    }
+
+    fn get_path_scope(
+        &self,
+        parent_path: &CppPath,
+        name_type: NameType<'_>,
+    ) -> Result<RustPathScope> {
+        trace!("get_path_scope({:?}, {:?})", parent_path, name_type);
+        if let Some(hook) = self.data.config.rust_path_scope_hook() {
+            if let Some(strategy) = hook(parent_path)? {
+                return Ok(strategy);
+            }
+        }
+
+        let allow_module_for_nested;
+        let allow_wrapper_type;
+        match name_type {
+            NameType::Type { .. } | NameType::Module { .. } => {
+                allow_module_for_nested = true;
+                allow_wrapper_type = false;
+            }
+            _ => {
+                allow_module_for_nested = false;
+                allow_wrapper_type = true;
+            }
+        };
+
+        let mut rust_items = match self
+            .data
+            .db
+            .find_rust_items_for_cpp_path(parent_path, false)
+        {
+            Ok(r) => r,
+            Err(err) => match name_type {
+                NameType::Type {
+                    is_from_other_crate,
+                }
+                | NameType::Module {
+                    is_from_other_crate,
+                } if is_from_other_crate => {
+                    return Ok(self.default_path_scope());
+                }
+                _ => return Err(err),
+            },
+        };
+
+        let rust_item = rust_items
+            .find(|item| {
+                (allow_wrapper_type && item.item.is_wrapper_type())
+                    || (item.item.is_module() && !item.item.is_module_for_nested())
+                    || (allow_module_for_nested && item.item.is_module_for_nested())
+            })
+            .ok_or_else(|| {
+                format_err!(
+                    "no Rust type wrapper for {}",
+                    parent_path.to_cpp_pseudo_code()
+                )
+            })?;
 
         let rust_path = rust_item.item.path().ok_or_else(|| {
             format_err!(
@@ -1578,11 +2916,20 @@ impl State<'_, '_> {
     fn type_list_caption(&self, types: &[CppType], context: &RustPath) -> Result<String> {
         let mut captions = Vec::new();
         for arg in types {
+            if let CppType::TemplateArgumentValue(value) = arg {
+                captions.push(value.ascii_caption());
+                continue;
+            }
             let rust_type = self.rust_final_type(
-                &ffi_type(arg, CppTypeRole::NotReturnType)?,
+                &ffi_type(
+                    arg,
+                    CppTypeRole::NotReturnType,
+                    self.data.config.flag_containers(),
+                )?,
                 &CppFfiArgumentMeaning::Argument(0),
                 ReturnValueAllocationPlace::NotApplicable,
                 None,
+                None,
             )?;
             captions.push(
                 rust_type
@@ -1618,6 +2965,8 @@ impl State<'_, '_> {
                 if cpp_function.is_constructor() {
                     if cpp_function.is_copy_constructor() {
                         Some("new_copy".to_string())
+                    } else if cpp_function.is_move_constructor() {
+                        Some("new_move".to_string())
                     } else {
                         Some("new".to_string())
                     }
@@ -1625,10 +2974,15 @@ impl State<'_, '_> {
                     match operator {
                         CppOperator::Conversion(type1) => {
                             let rust_type = self.rust_final_type(
-                                &ffi_type(type1, CppTypeRole::ReturnType)?,
+                                &ffi_type(
+                                    type1,
+                                    CppTypeRole::ReturnType,
+                                    self.data.config.flag_containers(),
+                                )?,
                                 &CppFfiArgumentMeaning::ReturnValue,
                                 function.allocation_place,
                                 None,
+                                None,
                             )?;
                             Some(format!(
                                 "to_{}",
@@ -1637,7 +2991,13 @@ impl State<'_, '_> {
                                     .caption(context, RustTypeCaptionStrategy::LastName)?
                             ))
                         }
-                        CppOperator::Assignment => Some("copy_from".to_string()),
+                        CppOperator::Assignment => {
+                            if cpp_function.is_move_assignment_operator() {
+                                Some("move_from".to_string())
+                            } else {
+                                Some("copy_from".to_string())
+                            }
+                        }
                         _ => Some(operator_function_name(operator)?.to_string()),
                     }
                 } else {
@@ -1659,6 +3019,24 @@ impl State<'_, '_> {
                 };
                 Some(function_name)
             }
+            CppFfiFunctionKind::VariableAccessor { accessor_type } => {
+                let variable = cpp_item
+                    .as_variable_ref()
+                    .ok_or_else(|| err_msg("invalid source cpp item type"))?;
+
+                let name = &variable.path.last().name;
+                let function_name = match accessor_type {
+                    CppFieldAccessorType::CopyGetter | CppFieldAccessorType::ConstRefGetter => {
+                        name.to_string()
+                    }
+                    CppFieldAccessorType::MutRefGetter => format!("{}_mut", name),
+                    CppFieldAccessorType::Setter => format!("set_{}", name),
+                };
+                Some(function_name)
+            }
+            CppFfiFunctionKind::StreamToString
+            | CppFfiFunctionKind::SmartPointer { .. }
+            | CppFfiFunctionKind::StdHash => None,
         };
 
         Ok(r)
@@ -1701,9 +3079,17 @@ impl State<'_, '_> {
                 // crate root
                 self.default_path_scope()
             }
+            NameType::SubclassWrapper { base_class } => {
+                // next to the base class
+                RustPathScope {
+                    path: self.wrapper_type_path(base_class)?.parent()?,
+                    prefix: None,
+                }
+            }
             NameType::Type { .. }
             | NameType::Module { .. }
             | NameType::EnumValue
+            | NameType::Constant
             | NameType::ApiFunction { .. }
             | NameType::ReceiverFunction { .. } => {
                 if let Ok(parent) = cpp_path.parent() {
@@ -1783,6 +3169,14 @@ impl State<'_, '_> {
             NameType::Module { .. } => self
                 .cpp_path_item_to_name(&cpp_path.last(), &scope.path, &name_type)?
                 .to_snake_case(),
+            NameType::Constant => {
+                let name = &cpp_path.last().name;
+                if name.contains(char::is_lowercase) {
+                    name.to_upper_case_words()
+                } else {
+                    name.clone()
+                }
+            }
             NameType::FfiFunction => cpp_path.last().name.clone(),
             NameType::QtSlotWrapper { signal_arguments } => {
                 if signal_arguments.is_empty() {
@@ -1800,6 +3194,9 @@ impl State<'_, '_> {
                     format!("SignalOf_{}", captions).to_class_case()
                 }
             }
+            NameType::SubclassWrapper { base_class } => {
+                format!("{}Subclass", self.wrapper_type_path(base_class)?.last())
+            }
         };
 
         if name_type == NameType::FfiFunction {
@@ -1838,6 +3235,9 @@ impl State<'_, '_> {
             CppFfiItem::QtSignalWrapper(_) => {
                 bail!("signal wrappers do not need to be processed here");
             }
+            CppFfiItem::SubclassWrapper(_) => {
+                bail!("subclass wrappers do not need to be processed here");
+            }
         }
     }
 
@@ -1845,9 +3245,18 @@ impl State<'_, '_> {
     fn process_cpp_class(&self, item: DbItem<&CppTypeDeclaration>) -> Result<Vec<RustItem>> {
         trace!("process_cpp_class: {:?}", item);
         let data = item.item;
+        if smart_pointer_type(&data.path).is_some() {
+            bail!("smart pointers are represented by cpp_core types");
+        }
 
         // TODO: do something about `QUrlTwoFlags<T1, T2>`
-        if is_qflags(&data.path) {
+        let flag_container = self
+            .data
+            .config
+            .flag_containers()
+            .iter()
+            .find(|container| container.matches(&data.path));
+        if let Some(flag_container) = flag_container {
             let argument = &data.path.last().template_arguments.as_ref().unwrap()[0];
             if !argument.is_template_parameter() {
                 if let CppType::Enum { path } = &argument {
@@ -1856,11 +3265,25 @@ impl State<'_, '_> {
                         .item
                         .path()
                         .expect("enum rust item must have path");
+                    if !is_int_enum_wrapper(rust_type.item) {
+                        bail!("flag containers are only supported for enums represented by c_int");
+                    }
+                    let data = RustFlagEnumImpl {
+                        enum_path: rust_type_path.clone(),
+                    };
+                    let kind = if flag_container == &CppFlagContainer::qflags() {
+                        RustExtraImplKind::FlagEnum(data)
+                    } else if self.is_bitmask_enum(path)?
+                        || self.has_qflags_container(path)
+                        || self.skip_bitmask_impl(&rust_type)
+                    {
+                        return Ok(Vec::new());
+                    } else {
+                        RustExtraImplKind::BitmaskEnum(data)
+                    };
                     let rust_item = RustItem::ExtraImpl(RustExtraImpl {
                         parent_path: rust_type_path.parent()?,
-                        kind: RustExtraImplKind::FlagEnum(RustFlagEnumImpl {
-                            enum_path: rust_type_path.clone(),
-                        }),
+                        kind,
                     });
                     return Ok(vec![rust_item]);
                 }
@@ -1869,14 +3292,20 @@ impl State<'_, '_> {
 
         let mut qt_slot_wrapper = None;
         let mut qt_signal_wrapper = None;
+        let mut subclass_wrapper = None;
         if let Some(source_ffi_item) = self.data.db.source_ffi_item(&item.id)? {
             if let Some(item) = source_ffi_item
                 .clone()
                 .filter_map(|i| i.as_slot_wrapper_ref())
             {
                 qt_slot_wrapper = Some(item);
-            } else if let Some(item) = source_ffi_item.filter_map(|i| i.as_signal_wrapper_ref()) {
+            } else if let Some(item) = source_ffi_item
+                .clone()
+                .filter_map(|i| i.as_signal_wrapper_ref())
+            {
                 qt_signal_wrapper = Some(item);
+            } else if let Some(item) = source_ffi_item.filter_map(|i| i.as_subclass_wrapper_ref()) {
+                subclass_wrapper = Some(item);
             }
         }
 
@@ -1892,6 +3321,10 @@ impl State<'_, '_> {
             NameType::QtSignalWrapper {
                 signal_arguments: &wrapper.item.signal_arguments,
             }
+        } else if let Some(wrapper) = &subclass_wrapper {
+            NameType::SubclassWrapper {
+                base_class: &wrapper.item.base_class,
+            }
         } else {
             NameType::Type {
                 is_from_other_crate,
@@ -1922,6 +3355,7 @@ impl State<'_, '_> {
                 }),
                 is_public: true,
                 qt_receiver_data: None,
+                thread_safety: None,
             });
 
             rust_items.push(internal_rust_item);
@@ -1974,7 +3408,13 @@ impl State<'_, '_> {
                 .item
                 .signal_arguments
                 .iter()
-                .map_if_ok(|t| ffi_type(&t, CppTypeRole::NotReturnType))?
+                .map_if_ok(|t| {
+                    ffi_type(
+                        &t,
+                        CppTypeRole::NotReturnType,
+                        self.data.config.flag_containers(),
+                    )
+                })?
                 .map_if_ok(|t| self.ffi_type_to_rust_ffi_type(t.ffi_type()))?;
 
             let impl_item = RustItem::ExtraImpl(RustExtraImpl {
@@ -1995,11 +3435,60 @@ impl State<'_, '_> {
             qt_receiver_data = None;
         }
 
+        if let Some(wrapper) = subclass_wrapper {
+            let overrides = wrapper
+                .item
+                .overrides
+                .iter()
+                .map(|item| RustSubclassOverride {
+                    method_name: sanitize_rust_identifier(
+                        &item.callback_name.to_snake_case(),
+                        false,
+                    ),
+                    setter_cpp_path: data
+                        .path
+                        .join(CppPathItem::from_good_str(&item.setter_name())),
+                    base_function_cpp_path: if item.has_base_function() {
+                        Some(
+                            data.path
+                                .join(CppPathItem::from_good_str(&item.base_function_name())),
+                        )
+                    } else {
+                        None
+                    },
+                    cpp_declaration: item.function.pseudo_declaration(),
+                })
+                .collect();
+
+            let trait_name = format!(
+                "{}Overrides",
+                public_path.last().trim_end_matches("Subclass")
+            );
+            let impl_item = RustItem::ExtraImpl(RustExtraImpl {
+                parent_path: public_path.parent()?,
+                kind: RustExtraImplKind::SubclassOverrides(RustSubclassOverridesImpl {
+                    trait_path: self
+                        .data
+                        .db
+                        .make_unique_rust_path(&public_path.parent()?.join(trait_name)),
+                    subclass_path: public_path.clone(),
+                    overrides,
+                }),
+            });
+            rust_items.push(impl_item);
+        }
+
+        let thread_safety = match self.data.config.thread_safety_hook() {
+            Some(hook) => hook(&data.path)?,
+            None => None,
+        };
+
         let public_rust_item = RustItem::Struct(RustStruct {
             path: public_path,
             kind: RustStructKind::WrapperType(wrapper_kind),
             is_public: true,
             qt_receiver_data,
+            thread_safety,
         });
         rust_items.push(public_rust_item);
         Ok(rust_items)
@@ -2027,25 +3516,46 @@ impl State<'_, '_> {
                 });
                 Ok(vec![rust_item])
             }
-            CppItem::Type(data) => match data.kind {
+            CppItem::Type(data) => match &data.kind {
                 CppTypeDeclarationKind::Class { .. } => {
                     self.process_cpp_class(cpp_item.map(|v| v.as_type_ref().unwrap()))
                 }
-                CppTypeDeclarationKind::Enum => {
+                CppTypeDeclarationKind::Enum { underlying_type } => {
                     let rust_path = self.generate_rust_path(
                         &data.path,
                         NameType::Type {
                             is_from_other_crate: false,
                         },
                     )?;
+                    let underlying_type = if underlying_type
+                        == &CppType::BuiltInNumeric(CppBuiltInNumericType::UInt)
+                    {
+                        // `unsigned int` enums are represented by `c_int`, like `int` enums,
+                        // for compatibility with `QFlags`
+                        CppType::BuiltInNumeric(CppBuiltInNumericType::Int)
+                    } else {
+                        underlying_type.clone()
+                    };
                     let rust_item = RustItem::Struct(RustStruct {
-                        path: rust_path,
-                        kind: RustStructKind::WrapperType(RustWrapperTypeKind::EnumWrapper),
+                        path: rust_path.clone(),
+                        kind: RustStructKind::WrapperType(RustWrapperTypeKind::EnumWrapper {
+                            underlying_type: self.ffi_type_to_rust_ffi_type(&underlying_type)?,
+                        }),
                         is_public: true,
                         qt_receiver_data: None,
+                        thread_safety: None,
                     });
-
-                    Ok(vec![rust_item])
+                    let mut rust_items = vec![rust_item];
+
+                    if self.is_bitmask_enum(&data.path)? && !self.has_qflags_container(&data.path) {
+                        rust_items.push(RustItem::ExtraImpl(RustExtraImpl {
+                            parent_path: rust_path.parent()?,
+                            kind: RustExtraImplKind::BitmaskEnum(RustFlagEnumImpl {
+                                enum_path: rust_path,
+                            }),
+                        }));
+                    }
+                    Ok(rust_items)
                 }
             },
             CppItem::EnumValue(value) => {
@@ -2127,7 +3637,11 @@ impl State<'_, '_> {
                     .iter()
                     .map_if_ok(|arg| -> Result<_> {
                         // TODO: rust generator shouldn't know about cpp ffi types
-                        let ffi_type = ffi_type(&arg.argument_type, CppTypeRole::NotReturnType)?;
+                        let ffi_type = ffi_type(
+                            &arg.argument_type,
+                            CppTypeRole::NotReturnType,
+                            self.data.config.flag_containers(),
+                        )?;
                         self.ffi_type_to_rust_ffi_type(ffi_type.ffi_type())
                     })?;
 
@@ -2148,11 +3662,34 @@ impl State<'_, '_> {
                         name: "self".to_string(),
                         ffi_index: 42,
                     }],
+                    is_variadic: false,
                     return_type,
                 };
                 Ok(vec![RustItem::Function(rust_function)])
             }
-            CppItem::ClassField(_) | CppItem::ClassBase(_) => {
+            CppItem::TypeAlias(alias) => {
+                let target = self.ffi_type_to_rust_ffi_type(&alias.target)?;
+                let rust_path = self.generate_rust_path(
+                    &alias.path,
+                    NameType::Type {
+                        is_from_other_crate: false,
+                    },
+                )?;
+                let rust_item = RustItem::TypeAlias(RustTypeAlias {
+                    path: rust_path,
+                    target,
+                });
+                Ok(vec![rust_item])
+            }
+            CppItem::Constant(constant) => {
+                let rust_item = RustItem::Constant(RustConstant {
+                    path: self.generate_rust_path(&constant.path, NameType::Constant)?,
+                    constant_type: self.constant_rust_type(constant)?,
+                    value: constant_value_to_rust_code(constant)?,
+                });
+                Ok(vec![rust_item])
+            }
+            CppItem::ClassField(_) | CppItem::ClassBase(_) | CppItem::Variable(_) => {
                 // only need to process FFI items
                 Ok(Vec::new())
             }
@@ -2207,12 +3744,15 @@ impl State<'_, '_> {
             RustSpecialModuleKind::Ffi => vec![crate_name, "__ffi".to_string()],
             RustSpecialModuleKind::Ops => vec![crate_name, "ops".to_string()],
             RustSpecialModuleKind::SizedTypes => vec![crate_name, "__sized_types".to_string()],
+            RustSpecialModuleKind::Overloading => vec![crate_name, "overloading".to_string()],
         };
         let rust_path = RustPath::from_parts(rust_path_parts);
 
         let rust_item = RustItem::Module(RustModule {
             is_public: match kind {
-                RustSpecialModuleKind::CrateRoot | RustSpecialModuleKind::Ops => true,
+                RustSpecialModuleKind::CrateRoot
+                | RustSpecialModuleKind::Ops
+                | RustSpecialModuleKind::Overloading => true,
                 RustSpecialModuleKind::Ffi | RustSpecialModuleKind::SizedTypes => false,
             },
             path: rust_path.clone(),
@@ -2277,6 +3817,7 @@ impl State<'_, '_> {
         &mut self,
     ) -> Result<BTreeMap<RustPath, Vec<ItemWithSource<FunctionWithDesiredPath>>>> {
         let mut grouped_functions = BTreeMap::<_, Vec<_>>::new();
+        let forwarded_base_classes = self.forwarded_base_classes();
         let mut trait_types = self
             .data
             .db
@@ -2308,10 +3849,27 @@ impl State<'_, '_> {
                                 self.add_rust_item(Some(ffi_item_id.clone()), rust_item)?;
                             }
                             ProcessedFfiItem::Function(function) => {
-                                let entry = grouped_functions
-                                    .entry(function.desired_path.clone())
-                                    .or_default();
-                                entry.push(ItemWithSource::new(&ffi_item_id, function));
+                                let forwarded = self
+                                    .forwarded_functions(
+                                        &ffi_item_id,
+                                        &function,
+                                        &forwarded_base_classes,
+                                        None,
+                                    )
+                                    .unwrap_or_else(|err| {
+                                        debug!(
+                                            "failed to forward function to derived classes: \
+                                             {:?}: {}",
+                                            function.desired_path, err
+                                        );
+                                        Vec::new()
+                                    });
+                                for function in once(function).chain(forwarded) {
+                                    let entry = grouped_functions
+                                        .entry(function.desired_path.clone())
+                                        .or_default();
+                                    entry.push(ItemWithSource::new(&ffi_item_id, function));
+                                }
                             }
                         }
                     }
@@ -2327,13 +3885,325 @@ impl State<'_, '_> {
                 }
             }
         }
-        Ok(grouped_functions)
+
+        for (source_id, function) in self.dependency_forwarded_functions(&forwarded_base_classes)? {
+            let entry = grouped_functions
+                .entry(function.desired_path.clone())
+                .or_default();
+            entry.push(ItemWithSource::new(&source_id, function));
+        }
+        self.separate_rvalue_reference_overloads(grouped_functions)
     }
 
-    fn try_caption_strategy(
+    /// Returns copies of public wrappers of base class methods declared in dependencies
+    /// for the classes of the current crate that inherit them through a base other than
+    /// the first one. Each copy is returned along with the ID of the original wrapper.
+    fn dependency_forwarded_functions(
         &self,
-        functions: &[ItemWithSource<FunctionWithDesiredPath>],
-        strategy: &RustFunctionCaptionStrategy,
+        forwarded_base_classes: &HashMap<CppPath, Vec<ForwardingTarget>>,
+    ) -> Result<Vec<(ItemId, FunctionWithDesiredPath)>> {
+        let mut results = Vec::new();
+        for item in self.data.db.all_rust_items() {
+            if item.id.crate_name() == self.data.db.crate_name() {
+                continue;
+            }
+            let function = match item.item.as_function_ref() {
+                Some(function) if function.is_public => function,
+                _ => continue,
+            };
+            match &function.kind {
+                RustFunctionKind::FfiWrapper(data) => {
+                    // forwarded wrappers of the dependency don't belong to the base class
+                    if let RustFfiWrapperVariant::Forwarded { .. } = &data.variant {
+                        continue;
+                    }
+                }
+                _ => continue,
+            }
+            let ffi_item = match self.data.db.source_ffi_item(&item.id)? {
+                Some(ffi_item) => ffi_item,
+                None => continue,
+            };
+            let function = FunctionWithDesiredPath {
+                function: UnnamedRustFunction {
+                    is_public: function.is_public,
+                    is_unsafe: function.is_unsafe,
+                    kind: function.kind.clone(),
+                    arguments: function.arguments.clone(),
+                    return_type: function.return_type.clone(),
+                },
+                desired_path: function.path.clone(),
+            };
+            let forwarded = self
+                .forwarded_functions(
+                    &ffi_item.id,
+                    &function,
+                    forwarded_base_classes,
+                    Some(&function.desired_path),
+                )
+                .unwrap_or_else(|err| {
+                    debug!(
+                        "failed to forward function to derived classes: {:?}: {}",
+                        function.desired_path, err
+                    );
+                    Vec::new()
+                });
+            results.extend(forwarded.into_iter().map(|f| (item.id.clone(), f)));
+        }
+        Ok(results)
+    }
+
+    /// Moves overloads accepting rvalue references that have a counterpart accepting
+    /// const references to separate groups with a `_move` suffix. Otherwise the overloads
+    /// would get the same caption, and the names of the existing const reference overloads
+    /// would change.
+    fn separate_rvalue_reference_overloads(
+        &self,
+        grouped_functions: BTreeMap<RustPath, Vec<ItemWithSource<FunctionWithDesiredPath>>>,
+    ) -> Result<BTreeMap<RustPath, Vec<ItemWithSource<FunctionWithDesiredPath>>>> {
+        let mut result = BTreeMap::<_, Vec<_>>::new();
+        for (group_path, functions) in grouped_functions {
+            if functions.len() < 2 {
+                result.entry(group_path).or_default().extend(functions);
+                continue;
+            }
+            let cpp_functions = functions.iter().map_if_ok(|function| {
+                self.data
+                    .db
+                    .source_cpp_item(&function.source_id)
+                    .map(|item| item.and_then(|item| item.item.as_function_ref().cloned()))
+            })?;
+            for (mut function, cpp_function) in functions.into_iter().zip(&cpp_functions) {
+                let is_move_overload = cpp_function.as_ref().map_or(false, |cpp_function| {
+                    cpp_functions
+                        .iter()
+                        .flatten()
+                        .any(|other| cpp_function.is_rvalue_reference_overload_of(other))
+                });
+                if is_move_overload {
+                    let name = format!("{}_move", function.item.desired_path.last());
+                    *function.item.desired_path.last_mut() = name;
+                    result
+                        .entry(function.item.desired_path.clone())
+                        .or_default()
+                        .push(function);
+                } else {
+                    result.entry(group_path.clone()).or_default().push(function);
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Returns classes of the current crate that have more than one base class,
+    /// grouped by their ancestors that are not reachable through the `Deref`
+    /// implementation for the first base class. Methods of these ancestors
+    /// are forwarded to the derived classes.
+    fn forwarded_base_classes(&self) -> HashMap<CppPath, Vec<ForwardingTarget>> {
+        let public_bases = self
+            .data
+            .db
+            .all_cpp_items()
+            .filter_map(|item| item.item.as_base_ref())
+            .filter(|base| base.visibility == CppVisibility::Public)
+            .collect_vec();
+
+        let derived_classes = self
+            .data
+            .db
+            .cpp_items()
+            .filter_map(|item| item.item.as_base_ref())
+            .filter(|base| base.base_index > 0)
+            .map(|base| base.derived_class_type.clone())
+            .unique()
+            .collect_vec();
+
+        // (base class, derived class, classes that can hide members of the base class)
+        let mut targets = Vec::new();
+        for derived_class in derived_classes {
+            let mut all_ancestors = HashMap::new();
+            count_ancestors(&derived_class, &public_bases, &mut all_ancestors);
+            let mut deref_ancestors = HashMap::new();
+            let mut other_ancestors = HashMap::new();
+            for base in &public_bases {
+                if base.derived_class_type != derived_class {
+                    continue;
+                }
+                let ancestors = if base.base_index == 0 {
+                    &mut deref_ancestors
+                } else {
+                    &mut other_ancestors
+                };
+                count_ancestors(&base.base_class_type, &public_bases, ancestors);
+            }
+            for (ancestor, paths_count) in other_ancestors {
+                if deref_ancestors.contains_key(&ancestor) {
+                    continue;
+                }
+                if paths_count > 1 {
+                    debug!(
+                        "not forwarding methods of ambiguous base {} to {}",
+                        ancestor.to_cpp_pseudo_code(),
+                        derived_class.to_cpp_pseudo_code()
+                    );
+                    continue;
+                }
+                // members of the ancestor are hidden by members with the same name
+                // declared in the derived class or its other ancestors, except
+                // the ancestor's own bases
+                let mut ancestor_bases = HashMap::new();
+                count_ancestors(&ancestor, &public_bases, &mut ancestor_bases);
+                let hiding_classes = all_ancestors
+                    .keys()
+                    .filter(|path| !ancestor_bases.contains_key(path))
+                    .cloned()
+                    .collect_vec();
+                targets.push((ancestor, derived_class.clone(), hiding_classes));
+            }
+        }
+
+        let all_hiding_classes = targets
+            .iter()
+            .flat_map(|(_, _, classes)| classes)
+            .collect::<HashSet<_>>();
+        let mut member_names = HashMap::<_, HashSet<_>>::new();
+        for item in self.data.db.all_cpp_items() {
+            let path = match &item.item {
+                CppItem::Function(function) if function.member.is_some() => &function.path,
+                CppItem::ClassField(field) => &field.path,
+                CppItem::Variable(variable) => &variable.path,
+                _ => continue,
+            };
+            if let Ok(class_path) = path.parent() {
+                if all_hiding_classes.contains(&class_path) {
+                    member_names
+                        .entry(class_path)
+                        .or_default()
+                        .insert(path.last().name.clone());
+                }
+            }
+        }
+
+        let mut result = HashMap::<_, Vec<_>>::new();
+        for (ancestor, derived_class, hiding_classes) in targets {
+            let hidden_names = hiding_classes
+                .iter()
+                .filter_map(|path| member_names.get(path))
+                .flatten()
+                .cloned()
+                .collect();
+            result.entry(ancestor).or_default().push(ForwardingTarget {
+                derived_class,
+                hidden_names,
+            });
+        }
+        result
+    }
+
+    /// Returns copies of `function` generated from FFI item `ffi_item_id` for the classes
+    /// that inherit the function's class through a base other than the first one.
+    /// The `self` argument of the copies is upcast to the function's class.
+    /// If the function is a wrapper from a dependency, `base_wrapper_path` is its path.
+    fn forwarded_functions(
+        &self,
+        ffi_item_id: &ItemId,
+        function: &FunctionWithDesiredPath,
+        forwarded_base_classes: &HashMap<CppPath, Vec<ForwardingTarget>>,
+        base_wrapper_path: Option<&RustPath>,
+    ) -> Result<Vec<FunctionWithDesiredPath>> {
+        let ffi_item = self.data.db.ffi_item(ffi_item_id)?;
+        let this_arg = ffi_item.item.as_function_ref().and_then(|function| {
+            function
+                .arguments
+                .iter()
+                .find(|arg| arg.meaning == CppFfiArgumentMeaning::This)
+        });
+        let this_arg = match this_arg {
+            Some(arg) => arg,
+            None => return Ok(Vec::new()),
+        };
+        let class_path = match this_arg.argument_type.ffi_type().pointer_like_to_target()? {
+            CppType::Class(path) => path,
+            _ => bail!("invalid type of this argument"),
+        };
+        let targets = match forwarded_base_classes.get(class_path) {
+            Some(targets) => targets,
+            None => return Ok(Vec::new()),
+        };
+        let cpp_item = self
+            .data
+            .db
+            .source_cpp_item(ffi_item_id)?
+            .ok_or_else(|| err_msg("source cpp item not found"))?;
+        if cpp_item
+            .item
+            .as_function_ref()
+            .map_or(false, |function| function.is_operator())
+        {
+            return Ok(Vec::new());
+        }
+        let cpp_name = &cpp_item
+            .item
+            .path()
+            .ok_or_else(|| err_msg("cpp item expected to have a path"))?
+            .last()
+            .name;
+        let self_index = function
+            .function
+            .arguments
+            .iter()
+            .position(|arg| arg.name == "self")
+            .ok_or_else(|| err_msg("self argument not found"))?;
+        let variant = match &function.function.kind {
+            RustFunctionKind::FfiWrapper(data) => data.variant.clone(),
+            _ => bail!("only FFI wrappers can be forwarded"),
+        };
+
+        let mut results = Vec::new();
+        for target in targets {
+            if target.hidden_names.contains(cpp_name) {
+                continue;
+            }
+            let derived_path = match self.wrapper_type_path(&target.derived_class) {
+                Ok(path) => path,
+                Err(err) => {
+                    debug!("not forwarding function: {}", err);
+                    continue;
+                }
+            };
+            let mut forwarded = function.clone();
+            forwarded.function.kind = forwarded.function.kind.with_ffi_wrapper_variant(
+                RustFfiWrapperVariant::Forwarded {
+                    derived_class: target.derived_class.clone(),
+                    variant: Box::new(variant.clone()),
+                    base_wrapper_path: base_wrapper_path.cloned(),
+                },
+            )?;
+            let self_arg = &mut forwarded.function.arguments[self_index];
+            let lifetime = match self_arg.argument_type.conversion() {
+                RustToFfiTypeConversion::RefToPtr { lifetime } => lifetime.clone(),
+                _ => bail!("unexpected conversion of self argument"),
+            };
+            self_arg.argument_type = RustFinalType::new(
+                self_arg.argument_type.ffi_type().clone(),
+                RustToFfiTypeConversion::UpcastRefToPtr {
+                    derived_type: RustType::Common(RustCommonType {
+                        path: derived_path.clone(),
+                        generic_arguments: None,
+                    }),
+                    lifetime,
+                },
+            )?;
+            forwarded.desired_path = derived_path.join(function.desired_path.last());
+            results.push(forwarded);
+        }
+        Ok(results)
+    }
+
+    fn try_caption_strategy(
+        &self,
+        functions: &[ItemWithSource<FunctionWithDesiredPath>],
+        strategy: &RustFunctionCaptionStrategy,
     ) -> Result<()> {
         let mut paths = BTreeSet::new();
         for function in functions {
@@ -2356,7 +4226,7 @@ impl State<'_, '_> {
     ) -> Result<()> {
         let all_strategies = RustFunctionCaptionStrategy::all();
 
-        for (_group_path, functions) in grouped_functions {
+        for (group_path, functions) in grouped_functions {
             let mut chosen_strategy = None;
             if functions.len() > 1 {
                 trace!("choosing caption strategy for:");
@@ -2387,19 +4257,616 @@ impl State<'_, '_> {
                 }
             }
 
+            let mut final_functions = Vec::new();
             for function in functions {
                 let path = if let Some(strategy) = &chosen_strategy {
                     function.item.apply_strategy(strategy).unwrap()
                 } else {
-                    function.item.desired_path
+                    function.item.desired_path.clone()
                 };
                 let final_path = self.data.db.make_unique_rust_path(&path);
-                let item = RustItem::Function(function.item.function.with_path(final_path));
-                self.add_rust_item(Some(function.source_id), item)?;
+                let item = RustItem::Function(function.item.function.with_path(final_path.clone()));
+                self.add_rust_item(Some(function.source_id.clone()), item)?;
+                final_functions.push((function, final_path));
+            }
+
+            if final_functions.len() > 1
+                && self.should_generate_overload_dispatch(&group_path, &final_functions)?
+            {
+                match self.generate_overload_dispatch(&group_path, &final_functions) {
+                    Ok(items) => {
+                        // the generated items are conditionally compiled
+                        // in the same way as their source item
+                        let environments = self.data.db.environments();
+                        let source_id = final_functions
+                            .iter()
+                            .map(|(function, _)| &function.source_id)
+                            .find(|id| {
+                                self.data
+                                    .db
+                                    .cpp_checks(id)
+                                    .map_or(false, |checks| checks.all_success(environments))
+                            })
+                            .unwrap_or(&final_functions[0].0.source_id)
+                            .clone();
+                        for item in items {
+                            self.add_rust_item(Some(source_id.clone()), item)?;
+                        }
+                    }
+                    Err(err) => {
+                        debug!(
+                            "failed to generate overload dispatch for {:?}: {}",
+                            group_path, err
+                        );
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns true if the generic function dispatching calls to the overloaded
+    /// functions with the desired path `path` should be generated.
+    /// Overloaded function call operators are dispatched by default, so that
+    /// all of them can be called through `call`.
+    fn should_generate_overload_dispatch(
+        &self,
+        path: &RustPath,
+        functions: &[(ItemWithSource<FunctionWithDesiredPath>, RustPath)],
+    ) -> Result<bool> {
+        if let Some(hook) = self.data.config.overload_dispatch_hook() {
+            if let Some(value) = hook(path)? {
+                return Ok(value);
+            }
+        }
+        if self.data.config.overload_dispatch() {
+            return Ok(true);
+        }
+        for (function, _) in functions {
+            let cpp_item = self.data.db.source_cpp_item(&function.source_id)?;
+            let is_function_call = cpp_item
+                .as_ref()
+                .and_then(|item| item.item.as_function_ref())
+                .map_or(false, |cpp_function| {
+                    cpp_function.operator == Some(CppOperator::FunctionCall)
+                });
+            if !is_function_call {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Generates the trait implemented for argument tuples of `functions`
+    /// and the generic function named `path` calling them.
+    /// `functions` must contain the overloads with their final paths.
+    fn generate_overload_dispatch(
+        &self,
+        path: &RustPath,
+        functions: &[(ItemWithSource<FunctionWithDesiredPath>, RustPath)],
+    ) -> Result<Vec<RustItem>> {
+        if self.data.db.find_rust_item(path).is_some() {
+            bail!("path is already taken: {:?}", path);
+        }
+        let first_function = &functions[0].0.item.function;
+        let self_arg_kind = first_function.self_arg_kind()?;
+        let self_type = match self_arg_kind {
+            RustFunctionSelfArgKind::None => None,
+            RustFunctionSelfArgKind::ConstRef | RustFunctionSelfArgKind::MutRef => Some(
+                first_function.arguments[0]
+                    .argument_type
+                    .api_type()
+                    .pointer_like_to_target()?,
+            ),
+            RustFunctionSelfArgKind::Value => bail!("self argument by value is not supported"),
+        };
+
+        // overloads are distinguished by the target types of `CastInto` arguments
+        let marker_types = |types: &[RustType]| {
+            types
+                .iter()
+                .map(|t| t.cast_into_target().unwrap_or(t).clone())
+                .collect_vec()
+        };
+        let mut variants = Vec::<RustOverloadVariant>::new();
+        for (function, final_path) in functions {
+            let function = &function.item.function;
+            if function.self_arg_kind()? != self_arg_kind {
+                bail!("overloads have different self argument kinds");
+            }
+            if self_arg_kind != RustFunctionSelfArgKind::None
+                && function.arguments[0]
+                    .argument_type
+                    .api_type()
+                    .pointer_like_to_target()?
+                    != *self_type.as_ref().unwrap()
+            {
+                bail!("overloads have different self argument types");
+            }
+            let argument_types = function
+                .arguments
+                .iter()
+                .filter(|arg| arg.name != "self")
+                .map_if_ok(|arg| overload_argument_type(&arg.argument_type))?;
+            let return_type = function.return_type.api_type();
+            if return_type.lifetime().is_some() {
+                bail!("return types with lifetimes are not supported");
+            }
+            if variants
+                .iter()
+                .any(|v| marker_types(&v.argument_types) == marker_types(&argument_types))
+            {
+                bail!(
+                    "overloads have identical argument types: {:?}",
+                    argument_types
+                );
+            }
+            variants.push(RustOverloadVariant {
+                function_path: final_path.clone(),
+                argument_types,
+                return_type: return_type.clone(),
+            });
+        }
+
+        let overloading_module = &self.special_module_paths[&RustSpecialModuleKind::Overloading];
+        let trait_name = path
+            .parts()
+            .iter()
+            .skip(1)
+            .map(|part| part.to_class_case())
+            .join("");
+        // the generated paths are reserved in the database when the item is added,
+        // so the paths of functions and other dispatch items can't collide with them
+        let trait_path = self
+            .data
+            .db
+            .make_unique_rust_path(&overloading_module.join(trait_name));
+        let function_path = if self_type.is_some() {
+            path.clone()
+        } else {
+            self.data
+                .db
+                .make_unique_rust_path(&overloading_module.join(path.last()))
+        };
+
+        let mut items = vec![RustItem::ExtraImpl(RustExtraImpl {
+            parent_path: overloading_module.clone(),
+            kind: RustExtraImplKind::OverloadDispatch(RustOverloadDispatchImpl {
+                trait_path,
+                function_path: function_path.clone(),
+                self_type: self_type.clone(),
+                self_arg_kind,
+                is_unsafe: functions
+                    .iter()
+                    .any(|(function, _)| function.item.function.is_unsafe),
+                variants,
+            }),
+        })];
+        if self_type.is_none() {
+            // free functions can't be defined in another module,
+            // so the function from the `overloading` module is reexported
+            items.push(RustItem::Reexport(RustReexport {
+                path: path.clone(),
+                target: function_path,
+                source: RustReexportSource::OverloadDispatch,
+            }));
+        }
+        Ok(items)
+    }
+
+    /// Generates traits containing methods common to all instantiations
+    /// of each class template instantiated at least twice in the crate.
+    fn generate_template_traits(&mut self) -> Result<()> {
+        let mut templates = Vec::<(CppPath, Vec<(RustPath, Vec<CppType>)>)>::new();
+        for item in self.data.db.rust_items() {
+            let rust_struct = match item.item.as_struct_ref() {
+                Some(rust_struct)
+                    if rust_struct.kind.is_wrapper_type() && rust_struct.is_public =>
+                {
+                    rust_struct
+                }
+                _ => continue,
+            };
+            let cpp_item = match self.data.db.source_cpp_item(&item.id)? {
+                Some(cpp_item) => cpp_item,
+                None => continue,
+            };
+            let declaration = match cpp_item.item.as_type_ref() {
+                Some(declaration) if declaration.kind.is_class() => declaration,
+                _ => continue,
+            };
+            let template_arguments = match &declaration.path.last().template_arguments {
+                Some(arguments) if !arguments.iter().any(|arg| arg.is_template_parameter()) => {
+                    arguments.clone()
+                }
+                _ => continue,
+            };
+            let mut template_path = declaration.path.clone();
+            template_path.last_mut().template_arguments = None;
+            let instantiation = (rust_struct.path.clone(), template_arguments);
+            if let Some((_, instantiations)) = templates
+                .iter_mut()
+                .find(|(path, _)| path == &template_path)
+            {
+                instantiations.push(instantiation);
+            } else {
+                templates.push((template_path, vec![instantiation]));
+            }
+        }
+
+        let mut reserved_paths = HashSet::new();
+        for (template_path, instantiations) in templates {
+            if instantiations.len() < 2 {
+                continue;
+            }
+            match self.generate_template_trait(&template_path, &instantiations, &reserved_paths) {
+                Ok(Some(data)) => {
+                    reserved_paths.insert(data.trait_path.clone());
+                    let item = RustItem::ExtraImpl(RustExtraImpl {
+                        parent_path: data.trait_path.parent()?,
+                        kind: RustExtraImplKind::TemplateTrait(data),
+                    });
+                    self.add_rust_item(None, item)?;
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    debug!(
+                        "failed to generate trait for class template {}: {}",
+                        template_path.to_cpp_pseudo_code(),
+                        err
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Generates implementations of `cpp_core::CppSharedPtrTarget` and
+    /// `cpp_core::CppUniquePtrTarget` for class types of the current crate
+    /// based on the generated smart pointer FFI functions.
+    fn generate_smart_pointer_impls(&mut self) -> Result<()> {
+        // the implementation uses the checks of the `Delete` function as its condition
+        let mut targets = Vec::<(CppSmartPointerKind, CppType, Option<ItemId>, Vec<_>)>::new();
+        for item in self.data.db.rust_items() {
+            let rust_function = match item.item.as_function_ref() {
+                Some(function) if function.kind.is_ffi_function() => function,
+                _ => continue,
+            };
+            let ffi_function = match self
+                .data
+                .db
+                .source_ffi_item(&item.id)?
+                .and_then(|ffi_item| ffi_item.filter_map(|item| item.as_function_ref()))
+            {
+                Some(function) => function,
+                None => continue,
+            };
+            let (kind, operation) = match ffi_function.item.kind {
+                CppFfiFunctionKind::SmartPointer { kind, operation } => (kind, operation),
+                _ => continue,
+            };
+            let target = smart_pointer_target(ffi_function.item)?;
+            let function = (operation, rust_function.path.clone());
+            let delete_id = if operation == CppSmartPointerOperation::Delete {
+                Some(ffi_function.id.clone())
+            } else {
+                None
+            };
+            if let Some((_, _, source_id, functions)) = targets
+                .iter_mut()
+                .find(|(k, t, _, _)| *k == kind && *t == target)
+            {
+                functions.push(function);
+                if delete_id.is_some() {
+                    *source_id = delete_id;
+                }
+            } else {
+                targets.push((kind, target, delete_id, vec![function]));
+            }
+        }
+
+        for (kind, target, source_id, ffi_functions) in targets {
+            let is_complete = CppSmartPointerOperation::all(kind)
+                .iter()
+                .all(|operation| ffi_functions.iter().any(|(op, _)| op == operation));
+            if !is_complete {
+                debug!(
+                    "skipping smart pointer impl for {}: some of FFI functions are missing",
+                    target.to_cpp_pseudo_code()
+                );
+                continue;
+            }
+            let target_type = self.ffi_type_to_rust_ffi_type(&target)?;
+            let target_path = match &target_type {
+                RustType::Common(common) => common.path.clone(),
+                _ => bail!("unexpected smart pointer target type: {:?}", target_type),
+            };
+            if target_path.crate_name() != self.data.db.crate_name() {
+                continue;
             }
+            let item = RustItem::ExtraImpl(RustExtraImpl {
+                parent_path: target_path.parent()?,
+                kind: RustExtraImplKind::SmartPointerTarget(RustSmartPointerTargetImpl {
+                    kind,
+                    target_type,
+                    ffi_functions,
+                }),
+            });
+            self.add_rust_item(source_id, item)?;
         }
         Ok(())
     }
+
+    /// Returns names of generic parameters of the trait generated for
+    /// the class template `template_path` with `count` template parameters.
+    fn template_parameter_names(&self, template_path: &CppPath, count: usize) -> Vec<String> {
+        let declared_names = self
+            .data
+            .db
+            .all_cpp_items()
+            .filter_map(|item| item.item.as_type_ref())
+            .filter_map(|declaration| {
+                let arguments = declaration.path.last().template_arguments.as_ref()?;
+                let mut path = declaration.path.clone();
+                path.last_mut().template_arguments = None;
+                if &path != template_path {
+                    return None;
+                }
+                arguments
+                    .iter()
+                    .map(|arg| match arg {
+                        CppType::TemplateParameter(param) => Some(param.name.to_class_case()),
+                        _ => None,
+                    })
+                    .collect::<Option<Vec<_>>>()
+            })
+            .next();
+        if let Some(names) = declared_names {
+            let is_valid = names.len() == count
+                && names.iter().unique().count() == count
+                && names.iter().all(|name| {
+                    name != "Self"
+                        && name.starts_with(|c: char| c.is_ascii_alphabetic())
+                        && name.chars().all(|c| c.is_ascii_alphanumeric())
+                });
+            if is_valid {
+                return names;
+            }
+        }
+        if count == 1 {
+            vec!["T".to_string()]
+        } else {
+            (1..=count).map(|index| format!("T{}", index)).collect()
+        }
+    }
+
+    /// Generates the trait containing methods common to `instantiations`
+    /// of the class template `template_path`. Each instantiation is represented by
+    /// the path of its wrapper type and its template arguments.
+    /// Returns `None` if the instantiations don't have common methods.
+    fn generate_template_trait(
+        &self,
+        template_path: &CppPath,
+        instantiations: &[(RustPath, Vec<CppType>)],
+        reserved_paths: &HashSet<RustPath>,
+    ) -> Result<Option<RustTemplateTraitImpl>> {
+        let parameter_count = instantiations[0].1.len();
+        if instantiations
+            .iter()
+            .any(|(_, arguments)| arguments.len() != parameter_count)
+        {
+            bail!("instantiations have different numbers of template arguments");
+        }
+        let generic_parameters = self.template_parameter_names(template_path, parameter_count);
+
+        // generic parameters of the trait and their values for each instantiation
+        let mut parameters = vec![(
+            RustType::GenericParameter("Self".to_string()),
+            instantiations
+                .iter()
+                .map(|(path, _)| {
+                    RustType::Common(RustCommonType {
+                        path: path.clone(),
+                        generic_arguments: None,
+                    })
+                })
+                .collect_vec(),
+        )];
+        for (index, name) in generic_parameters.iter().enumerate() {
+            let values = instantiations
+                .iter()
+                .map_if_ok(|(_, arguments)| self.ffi_type_to_rust_ffi_type(&arguments[index]))?;
+            parameters.push((RustType::GenericParameter(name.clone()), values));
+        }
+
+        // public methods of each instantiation available in all environments
+        let environments = self.data.db.environments();
+        let mut all_methods = Vec::new();
+        for (path, _) in instantiations {
+            let mut methods = Vec::new();
+            for item in self.data.db.rust_children(path) {
+                let function = match item.item.as_function_ref() {
+                    Some(function) if function.is_public => function,
+                    _ => continue,
+                };
+                if !matches!(function.kind, RustFunctionKind::FfiWrapper(_))
+                    || function.arguments.iter().any(|arg| {
+                        matches!(
+                            arg.argument_type.conversion(),
+                            RustToFfiTypeConversion::ClosureToCallback(_)
+                        )
+                    })
+                {
+                    continue;
+                }
+                if let Some(ffi_item) = self.data.db.source_ffi_item(&item.id)? {
+                    if !self
+                        .data
+                        .db
+                        .cpp_checks(&ffi_item.id)?
+                        .all_success(environments)
+                    {
+                        continue;
+                    }
+                }
+                let cpp_name = match self
+                    .data
+                    .db
+                    .source_cpp_item(&item.id)?
+                    .and_then(|cpp_item| cpp_item.item.as_function_ref())
+                {
+                    Some(cpp_function) => cpp_function.path.last().name.clone(),
+                    None => continue,
+                };
+                methods.push((cpp_name, function));
+            }
+            all_methods.push(methods);
+        }
+
+        let function_types = |function: &RustFunction| {
+            function
+                .arguments
+                .iter()
+                .map(|arg| arg.argument_type.api_type().clone())
+                .chain(once(function.return_type.api_type().clone()))
+                .collect_vec()
+        };
+
+        let mut methods = Vec::<RustTemplateTraitMethod>::new();
+        let mut method_paths = vec![Vec::new(); instantiations.len()];
+        'methods: for (cpp_name, function) in &all_methods[0] {
+            let first_types = function_types(function);
+            let mut selected = vec![*function];
+            for (index, other_methods) in all_methods.iter().enumerate().skip(1) {
+                let pair_parameters = parameters
+                    .iter()
+                    .map(|(parameter, values)| {
+                        (
+                            parameter.clone(),
+                            vec![values[0].clone(), values[index].clone()],
+                        )
+                    })
+                    .collect_vec();
+                let other_function = other_methods.iter().find(|(other_name, other_function)| {
+                    other_name == cpp_name
+                        && generalize_types(
+                            &[&first_types[..], &function_types(other_function)[..]],
+                            &pair_parameters,
+                        )
+                        .is_some()
+                });
+                match other_function {
+                    Some((_, other_function)) => selected.push(*other_function),
+                    None => continue 'methods,
+                }
+            }
+
+            let all_types = selected.iter().map(|f| function_types(f)).collect_vec();
+            let generic_types = match generalize_types(
+                &all_types.iter().map(|types| &types[..]).collect_vec(),
+                &parameters,
+            ) {
+                Some(types) => types,
+                None => continue,
+            };
+            let (return_type, argument_types) = generic_types.split_last().unwrap();
+            let arguments = function
+                .arguments
+                .iter()
+                .zip(argument_types)
+                .map(|(arg, argument_type)| RustTemplateTraitArgument {
+                    name: arg.name.clone(),
+                    argument_type: argument_type.clone(),
+                })
+                .collect_vec();
+            if let Some(arg) = arguments.iter().find(|arg| arg.name == "self") {
+                let self_type = RustType::GenericParameter("Self".to_string());
+                let is_valid_self = arg.argument_type == self_type
+                    || (arg.argument_type.is_ref()
+                        && arg.argument_type.pointer_like_to_target()? == self_type);
+                if !is_valid_self {
+                    continue;
+                }
+            }
+
+            let name = if selected
+                .iter()
+                .all(|f| f.path.last() == function.path.last())
+            {
+                function.path.last().to_string()
+            } else {
+                cpp_name.to_snake_case()
+            };
+            if methods.iter().any(|method| method.name == name) {
+                trace!(
+                    "skipping method {} of {}: name is already taken",
+                    name,
+                    template_path.to_cpp_pseudo_code()
+                );
+                continue;
+            }
+            methods.push(RustTemplateTraitMethod {
+                name,
+                cpp_name: cpp_name.clone(),
+                is_unsafe: selected.iter().any(|f| f.is_unsafe),
+                arguments,
+                return_type: return_type.clone(),
+            });
+            for (paths, selected_function) in method_paths.iter_mut().zip(&selected) {
+                paths.push(selected_function.path.clone());
+            }
+        }
+        if methods.is_empty() {
+            return Ok(None);
+        }
+
+        let mut deletable_parameters = Vec::new();
+        for method in &methods {
+            for arg in &method.arguments {
+                collect_deletable_parameters(&arg.argument_type, &mut deletable_parameters);
+            }
+            collect_deletable_parameters(&method.return_type, &mut deletable_parameters);
+        }
+
+        let trait_name = format!("{}Ops", template_path.last().name.to_class_case());
+        let desired_path = instantiations[0].0.parent()?.join(trait_name);
+        let mut trait_path = desired_path.clone();
+        let mut number = 1;
+        while reserved_paths.contains(&trait_path)
+            || self.data.db.find_rust_item(&trait_path).is_some()
+        {
+            number += 1;
+            *trait_path.last_mut() = format!("{}{}", desired_path.last(), number);
+        }
+
+        let instantiations = instantiations
+            .iter()
+            .zip(method_paths)
+            .enumerate()
+            .map(
+                |(index, ((path, _), method_paths))| RustTemplateTraitInstantiation {
+                    target_type: RustType::Common(RustCommonType {
+                        path: path.clone(),
+                        generic_arguments: None,
+                    }),
+                    generic_arguments: parameters[1..]
+                        .iter()
+                        .map(|(_, values)| values[index].clone())
+                        .collect(),
+                    method_paths,
+                },
+            )
+            .collect();
+
+        Ok(Some(RustTemplateTraitImpl {
+            trait_path,
+            cpp_template_path: template_path.clone(),
+            generic_parameters,
+            deletable_parameters,
+            methods,
+            instantiations,
+        }))
+    }
 }
 
 pub fn run(data: &mut ProcessorData<'_>) -> Result<()> {
@@ -2412,6 +4879,7 @@ pub fn run(data: &mut ProcessorData<'_>) -> Result<()> {
         RustSpecialModuleKind::Ffi,
         RustSpecialModuleKind::Ops,
         RustSpecialModuleKind::SizedTypes,
+        RustSpecialModuleKind::Overloading,
     ] {
         state.generate_special_module(module)?;
     }
@@ -2431,10 +4899,531 @@ pub fn run(data: &mut ProcessorData<'_>) -> Result<()> {
     state.process_cpp_items()?;
     let grouped_functions = state.process_ffi_items()?;
     state.finalize_functions(grouped_functions)?;
+    state.generate_template_traits()?;
+    state.generate_smart_pointer_impls()?;
 
     Ok(())
 }
 
+/// Adds `path` and all its ancestors to `output`, counting the number
+/// of distinct inheritance paths leading to each of them.
+fn count_ancestors(
+    path: &CppPath,
+    bases: &[&CppBaseSpecifier],
+    output: &mut HashMap<CppPath, usize>,
+) {
+    *output.entry(path.clone()).or_default() += 1;
+    for base in bases {
+        if &base.derived_class_type == path {
+            count_ancestors(&base.base_class_type, bases, output);
+        }
+    }
+}
+
+/// Returns the target type of the smart pointer used by a smart pointer FFI `function`.
+fn smart_pointer_target(function: &CppFfiFunction) -> Result<CppType> {
+    let all_types = function
+        .arguments
+        .iter()
+        .map(|arg| arg.argument_type.ffi_type())
+        .chain(once(function.return_type.ffi_type()));
+    for ffi_type in all_types {
+        if let CppType::PointerLike { target, .. } = ffi_type {
+            if let CppType::Class(path) = &**target {
+                if let Some((_, target)) = smart_pointer_type(path) {
+                    return Ok(target.clone());
+                }
+            }
+        }
+    }
+    bail!("smart pointer type not found in smart pointer function");
+}
+
+/// Returns `PartialEq<CppBox<T>>` implementation for `T` if `trait_impl` is
+/// `PartialEq<Ref<T>>` implementation for `T`, allowing to compare `CppBox<T>` values.
+fn cpp_box_eq_impl(trait_impl: &RustTraitImpl) -> Option<RustExtraImpl> {
+    if trait_impl.trait_type.path != RustPath::from_good_str("std::cmp::PartialEq") {
+        return None;
+    }
+    let ref_type = RustType::Common(RustCommonType {
+        path: RustPath::from_good_str("cpp_core::Ref"),
+        generic_arguments: Some(vec![trait_impl.target_type.clone()]),
+    });
+    if trait_impl.trait_type.generic_arguments != Some(vec![ref_type]) {
+        return None;
+    }
+    Some(RustExtraImpl {
+        parent_path: trait_impl.parent_path.clone(),
+        kind: RustExtraImplKind::CppBoxEq(RustCppBoxEqImpl {
+            target_type: trait_impl.target_type.clone(),
+        }),
+    })
+}
+
+/// Finds a type that turns into each of `types` when generic parameters are replaced
+/// with their values. `parameters` contains generic parameters and their values
+/// corresponding to each of `types`. Returns `None` if there is no such type.
+fn generalize_type(
+    types: &[&RustType],
+    parameters: &[(RustType, Vec<RustType>)],
+) -> Option<RustType> {
+    let first = types[0];
+    if types.iter().all(|t| *t == first) {
+        return Some(first.clone());
+    }
+    if let Some((parameter, _)) = parameters
+        .iter()
+        .find(|(_, values)| types.iter().zip(values).all(|(t, value)| *t == value))
+    {
+        return Some(parameter.clone());
+    }
+    match first {
+        RustType::Tuple(_) => {
+            let lists = types
+                .iter()
+                .map(|t| match t {
+                    RustType::Tuple(items) => Some(&items[..]),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some(RustType::Tuple(generalize_types(&lists, parameters)?))
+        }
+        RustType::Common(_) => {
+            let common_types = types
+                .iter()
+                .map(|t| match t {
+                    RustType::Common(common) => Some(common),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some(RustType::Common(generalize_common_type(
+                &common_types,
+                parameters,
+            )?))
+        }
+        RustType::ImplTrait(_) => {
+            let common_types = types
+                .iter()
+                .map(|t| match t {
+                    RustType::ImplTrait(common) => Some(common),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some(RustType::ImplTrait(generalize_common_type(
+                &common_types,
+                parameters,
+            )?))
+        }
+        RustType::PointerLike { kind, is_const, .. } => {
+            let targets = types
+                .iter()
+                .map(|t| match t {
+                    RustType::PointerLike {
+                        kind: other_kind,
+                        is_const: other_is_const,
+                        target,
+                    } if other_kind == kind && other_is_const == is_const => Some(&**target),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some(RustType::PointerLike {
+                kind: kind.clone(),
+                is_const: *is_const,
+                target: Box::new(generalize_type(&targets, parameters)?),
+            })
+        }
+        RustType::Slice(_) => {
+            let item_types = types
+                .iter()
+                .map(|t| match t {
+                    RustType::Slice(item_type) => Some(&**item_type),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some(RustType::Slice(Box::new(generalize_type(
+                &item_types,
+                parameters,
+            )?)))
+        }
+        RustType::FunctionPointer(function) => {
+            let functions = types
+                .iter()
+                .map(|t| match t {
+                    RustType::FunctionPointer(other)
+                        if other.is_variadic == function.is_variadic =>
+                    {
+                        Some(other)
+                    }
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()?;
+            let arguments = generalize_types(
+                &functions.iter().map(|f| &f.arguments[..]).collect_vec(),
+                parameters,
+            )?;
+            let return_type = generalize_type(
+                &functions.iter().map(|f| &*f.return_type).collect_vec(),
+                parameters,
+            )?;
+            Some(RustType::FunctionPointer(RustFunctionPointerType {
+                arguments,
+                return_type: Box::new(return_type),
+                is_variadic: function.is_variadic,
+            }))
+        }
+        RustType::Primitive(_) | RustType::GenericParameter(_) => None,
+    }
+}
+
+/// Applies `generalize_type` to each position of `lists`.
+fn generalize_types(
+    lists: &[&[RustType]],
+    parameters: &[(RustType, Vec<RustType>)],
+) -> Option<Vec<RustType>> {
+    let len = lists[0].len();
+    if lists.iter().any(|list| list.len() != len) {
+        return None;
+    }
+    (0..len)
+        .map(|index| {
+            let types = lists.iter().map(|list| &list[index]).collect_vec();
+            generalize_type(&types, parameters)
+        })
+        .collect()
+}
+
+/// Implements `generalize_type` for common types with the same path.
+fn generalize_common_type(
+    types: &[&RustCommonType],
+    parameters: &[(RustType, Vec<RustType>)],
+) -> Option<RustCommonType> {
+    let path = &types[0].path;
+    if types.iter().any(|t| &t.path != path) {
+        return None;
+    }
+    let generic_arguments = if types.iter().all(|t| t.generic_arguments.is_none()) {
+        None
+    } else {
+        let lists = types
+            .iter()
+            .map(|t| t.generic_arguments.as_deref())
+            .collect::<Option<Vec<_>>>()?;
+        Some(generalize_types(&lists, parameters)?)
+    };
+    Some(RustCommonType {
+        path: path.clone(),
+        generic_arguments,
+    })
+}
+
+/// Adds names of generic parameters used as arguments of `CppBox` within `rust_type`
+/// to `names`.
+fn collect_deletable_parameters(rust_type: &RustType, names: &mut Vec<String>) {
+    match rust_type {
+        RustType::Common(common) | RustType::ImplTrait(common) => {
+            if let Some(arguments) = &common.generic_arguments {
+                if common.path == RustPath::from_good_str("cpp_core::CppBox") {
+                    if let Some(RustType::GenericParameter(name)) = arguments.get(0) {
+                        if !names.contains(name) {
+                            names.push(name.clone());
+                        }
+                    }
+                }
+                for argument in arguments {
+                    collect_deletable_parameters(argument, names);
+                }
+            }
+        }
+        RustType::Tuple(items) => {
+            for item in items {
+                collect_deletable_parameters(item, names);
+            }
+        }
+        RustType::FunctionPointer(function) => {
+            for argument in &function.arguments {
+                collect_deletable_parameters(argument, names);
+            }
+            collect_deletable_parameters(&function.return_type, names);
+        }
+        RustType::PointerLike { target, .. } | RustType::Slice(target) => {
+            collect_deletable_parameters(target, names);
+        }
+        RustType::Primitive(_) | RustType::GenericParameter(_) => {}
+    }
+}
+
+/// Returns true if `cpp_type` is a non-const pointer or reference to
+/// a numeric type (excluding character types).
+fn is_primitive_out_type(cpp_type: &CppType) -> bool {
+    if let CppType::PointerLike {
+        kind,
+        is_const: false,
+        target,
+    } = cpp_type
+    {
+        if kind == &CppPointerLikeTypeKind::RValueReference {
+            return false;
+        }
+        match &**target {
+            CppType::BuiltInNumeric(numeric) => ![
+                CppBuiltInNumericType::Char,
+                CppBuiltInNumericType::SChar,
+                CppBuiltInNumericType::UChar,
+                CppBuiltInNumericType::WChar,
+                CppBuiltInNumericType::Char16,
+                CppBuiltInNumericType::Char32,
+            ]
+            .contains(numeric),
+            CppType::SpecificNumeric(_) | CppType::PointerSizedInteger { .. } => true,
+            _ => false,
+        }
+    } else {
+        false
+    }
+}
+
+/// Returns true if `cpp_type` is `void*` or `const void*`.
+fn is_void_pointer(cpp_type: &CppType) -> bool {
+    if let CppType::PointerLike {
+        kind: CppPointerLikeTypeKind::Pointer,
+        target,
+        ..
+    } = cpp_type
+    {
+        **target == CppType::Void
+    } else {
+        false
+    }
+}
+
+/// Returns true if `cpp_type` is a pointer to a function that can delete user data
+/// (`void (*)(void*)`).
+fn is_deleter_type(cpp_type: &CppType) -> bool {
+    if let CppType::FunctionPointer(function) = cpp_type {
+        !function.allows_variadic_arguments
+            && *function.return_type == CppType::Void
+            && function.arguments.len() == 1
+            && is_void_pointer(&function.arguments[0])
+    } else {
+        false
+    }
+}
+
+/// Checks that argument types of `function` match `convention`.
+fn check_callback_convention(
+    function: &CppFunction,
+    convention: &CallbackConvention,
+) -> Result<()> {
+    let argument_type = |index: usize| {
+        function
+            .arguments
+            .get(index)
+            .map(|arg| &arg.argument_type)
+            .ok_or_else(|| format_err!("argument index is out of bounds: {}", index))
+    };
+    let mut indexes = vec![convention.callback_argument, convention.data_argument];
+    indexes.extend(convention.deleter_argument);
+    if indexes.iter().unique().count() != indexes.len() {
+        bail!("argument indexes are not unique");
+    }
+    match argument_type(convention.callback_argument)? {
+        CppType::FunctionPointer(callback_type) => {
+            if callback_type.allows_variadic_arguments {
+                bail!("variadic callbacks are not supported");
+            }
+            let data_type = callback_type
+                .arguments
+                .get(convention.callback_data_argument)
+                .ok_or_else(|| err_msg("callback data argument index is out of bounds"))?;
+            if !is_void_pointer(data_type) {
+                bail!("callback data argument must be a void pointer");
+            }
+        }
+        _ => bail!("callback argument must be a function pointer"),
+    }
+    if !is_void_pointer(argument_type(convention.data_argument)?) {
+        bail!("data argument must be a void pointer");
+    }
+    if let Some(index) = convention.deleter_argument {
+        if !is_deleter_type(argument_type(index)?) {
+            bail!("deleter argument must be a pointer to a function accepting void pointer");
+        }
+    }
+    Ok(())
+}
+
+/// Detects callback arguments of `function`: a pointer to a function receiving `void*`
+/// as the first or the last argument, accompanied by a single `void*` argument
+/// of `function` and, optionally, a deleter.
+fn detect_callback_conventions(function: &CppFunction) -> Vec<CallbackConvention> {
+    let data_arguments = function
+        .arguments
+        .iter()
+        .positions(|arg| is_void_pointer(&arg.argument_type))
+        .collect_vec();
+    if data_arguments.len() != 1 {
+        return Vec::new();
+    }
+    let function_pointers_count = function
+        .arguments
+        .iter()
+        .filter(|arg| matches!(arg.argument_type, CppType::FunctionPointer(_)))
+        .count();
+    // a single function pointer of the deleter's type is the callback
+    let deleter_argument = if function_pointers_count > 1 {
+        function
+            .arguments
+            .iter()
+            .rposition(|arg| is_deleter_type(&arg.argument_type))
+    } else {
+        None
+    };
+
+    let mut conventions = Vec::new();
+    for (index, arg) in function.arguments.iter().enumerate() {
+        if Some(index) == deleter_argument {
+            continue;
+        }
+        if let CppType::FunctionPointer(callback_type) = &arg.argument_type {
+            let callback_data_argument = if callback_type
+                .arguments
+                .first()
+                .map_or(false, is_void_pointer)
+            {
+                0
+            } else if callback_type
+                .arguments
+                .last()
+                .map_or(false, is_void_pointer)
+            {
+                callback_type.arguments.len() - 1
+            } else {
+                continue;
+            };
+            conventions.push(CallbackConvention {
+                callback_argument: index,
+                callback_data_argument,
+                data_argument: data_arguments[0],
+                deleter_argument,
+            });
+        }
+    }
+    conventions
+}
+
+/// Returns true if `cpp_type` is a pointer that can be replaced with a slice.
+fn is_slice_pointer_type(cpp_type: &CppType) -> bool {
+    if let CppType::PointerLike {
+        kind: CppPointerLikeTypeKind::Pointer,
+        target,
+        ..
+    } = cpp_type
+    {
+        matches!(
+            &**target,
+            CppType::BuiltInNumeric(_)
+                | CppType::SpecificNumeric(_)
+                | CppType::PointerSizedInteger { .. }
+        )
+    } else {
+        false
+    }
+}
+
+/// Returns true if `cpp_type` is an integer type that can hold the length of a slice.
+fn is_integer_cpp_type(cpp_type: &CppType) -> bool {
+    match cpp_type {
+        CppType::BuiltInNumeric(numeric) => {
+            numeric.is_signed_integer() || numeric.is_unsigned_integer()
+        }
+        CppType::SpecificNumeric(CppSpecificNumericType { kind, .. }) => {
+            matches!(kind, CppSpecificNumericTypeKind::Integer { .. })
+        }
+        CppType::PointerSizedInteger { .. } => true,
+        _ => false,
+    }
+}
+
+/// Detects pairs of pointer and length arguments of `function`: a pointer
+/// to a numeric type followed by an integer argument named like a length.
+fn detect_slice_arguments(function: &CppFunction) -> Vec<(usize, usize)> {
+    let length_suffixes = ["len", "length", "size", "count"];
+    let mut pairs = Vec::new();
+    for (index, pair) in function.arguments.windows(2).enumerate() {
+        let length_name = pair[1].name.to_lowercase();
+        if is_slice_pointer_type(&pair[0].argument_type)
+            && is_integer_cpp_type(&pair[1].argument_type)
+            && length_suffixes
+                .iter()
+                .any(|suffix| length_name.ends_with(suffix))
+        {
+            pairs.push((index, index + 1));
+        }
+    }
+    pairs
+}
+
+/// Returns true if `rust_type` is a built-in integer type.
+fn is_integer_type(rust_type: &RustType) -> bool {
+    match rust_type {
+        RustType::Primitive(name) => [
+            "i8", "i16", "i32", "i64", "isize", "u8", "u16", "u32", "u64", "usize",
+        ]
+        .contains(&name.as_str()),
+        RustType::Common(RustCommonType {
+            path,
+            generic_arguments: None,
+        }) => [
+            "c_char",
+            "c_schar",
+            "c_uchar",
+            "c_short",
+            "c_ushort",
+            "c_int",
+            "c_uint",
+            "c_long",
+            "c_ulong",
+            "c_longlong",
+            "c_ulonglong",
+        ]
+        .iter()
+        .any(|name| path == &RustPath::from_good_str("std::os::raw").join(*name)),
+        _ => false,
+    }
+}
+
+/// Returns true if `item` is an enum wrapper represented by `c_int`.
+fn is_int_enum_wrapper(item: &RustItem) -> bool {
+    item.as_struct_ref().map_or(false, |s| {
+        s.kind
+            == RustStructKind::WrapperType(RustWrapperTypeKind::EnumWrapper {
+                underlying_type: RustType::Common(RustCommonType {
+                    path: RustPath::from_good_str("std::os::raw::c_int"),
+                    generic_arguments: None,
+                }),
+            })
+    })
+}
+
+/// Returns type of the argument tuple element corresponding to an argument
+/// of an overloaded function.
+fn overload_argument_type(argument_type: &RustFinalType) -> Result<RustType> {
+    if argument_type.conversion().as_callback_ref().is_some() {
+        bail!("callback arguments are not supported");
+    }
+    let api_type = argument_type.api_type();
+    if api_type.lifetime().is_some() {
+        bail!("arguments with lifetimes are not supported");
+    }
+    if let RustType::ImplTrait(trait_type) = api_type {
+        // `impl CastInto<T>` becomes a generic tuple element of the trait implementation
+        if api_type.cast_into_target().is_none() {
+            bail!("unsupported impl trait argument: {:?}", trait_type);
+        }
+    }
+    Ok(api_type.clone())
+}
+
 fn detect_callback_function(function: &UnnamedRustFunction) -> Option<&RustFunctionPointerType> {
     if function.arguments.len() < 3 {
         return None;
@@ -2458,6 +5447,9 @@ fn detect_callback_function(function: &UnnamedRustFunction) -> Option<&RustFunct
         if let Some(args) = &common.generic_arguments {
             if args.len() == 1 {
                 if let RustType::FunctionPointer(t) = &args[0] {
+                    if t.is_variadic {
+                        return None;
+                    }
                     callback_type = t;
                 } else {
                     return None;
@@ -2477,6 +5469,7 @@ fn detect_callback_function(function: &UnnamedRustFunction) -> Option<&RustFunct
     let deleter_type = RustType::new_option(RustType::FunctionPointer(RustFunctionPointerType {
         arguments: vec![void_ptr.clone()],
         return_type: Box::new(RustType::unit()),
+        is_variadic: false,
     }));
     if args[1].argument_type.ffi_type() != &deleter_type {
         return None;
@@ -1,8 +1,9 @@
 //! Types holding information about generates Rust API.
 
+use crate::config::ThreadSafety;
 use crate::cpp_data::CppPath;
-use crate::cpp_ffi_data::CppFfiFunction;
-use crate::cpp_type::CppType;
+use crate::cpp_ffi_data::{CppFfiFunction, CppSmartPointerOperation};
+use crate::cpp_type::{CppSmartPointerKind, CppType};
 use crate::database::DbItem;
 use crate::rust_code_generator::{rust_common_type_to_code, rust_type_to_code};
 use crate::rust_type::{
@@ -29,9 +30,14 @@ pub struct RustQtSlotWrapper {
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub enum RustWrapperTypeKind {
-    EnumWrapper,
+    EnumWrapper {
+        /// Integer type used to represent values of the enum
+        underlying_type: RustType,
+    },
     ImmovableClassWrapper,
-    MovableClassWrapper { sized_type_path: RustPath },
+    MovableClassWrapper {
+        sized_type_path: RustPath,
+    },
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -87,6 +93,9 @@ pub struct RustStruct {
     pub is_public: bool,
 
     pub qt_receiver_data: Option<RustQtReceiverData>,
+    /// Thread safety of the C++ class declared in the config. If set,
+    /// `Send` and/or `Sync` are implemented for the type.
+    pub thread_safety: Option<ThreadSafety>,
 }
 
 /// Location of a Rust method.
@@ -118,9 +127,33 @@ pub enum RustQtReceiverType {
     Slot,
 }
 
+/// Variant of a Rust wrapper of an FFI function. Multiple wrappers with different
+/// variants can be generated for the same FFI function.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub enum RustFfiWrapperVariant {
+    /// The wrapper directly corresponding to the FFI function
+    Main,
+    /// The wrapper returning values of output arguments
+    OutArguments,
+    /// The wrapper accepting slices in place of pointer and length arguments
+    Slice,
+    /// The wrapper accepting a closure in place of a callback
+    Closure,
+    /// A copy of the wrapper `variant` of a base class method added to `derived_class`
+    Forwarded {
+        derived_class: CppPath,
+        variant: Box<RustFfiWrapperVariant>,
+        /// Path of the original wrapper if the base class belongs to a dependency.
+        /// FFI functions of other crates are private, so the original wrapper is called
+        /// instead of the FFI function.
+        base_wrapper_path: Option<RustPath>,
+    },
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct RustFfiWrapperData {
     pub ffi_function_path: RustPath,
+    pub variant: RustFfiWrapperVariant,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -149,6 +182,18 @@ impl RustFunctionKind {
         matches!(self, RustFunctionKind::FfiFunction)
     }
 
+    /// Returns a copy of this FFI wrapper kind with the variant replaced with `variant`.
+    pub fn with_ffi_wrapper_variant(&self, variant: RustFfiWrapperVariant) -> Result<Self> {
+        if let RustFunctionKind::FfiWrapper(data) = self {
+            Ok(RustFunctionKind::FfiWrapper(RustFfiWrapperData {
+                ffi_function_path: data.ffi_function_path.clone(),
+                variant,
+            }))
+        } else {
+            bail!("not an FFI wrapper");
+        }
+    }
+
     pub fn is_signal_or_slot_getter(&self) -> bool {
         matches!(self, RustFunctionKind::SignalOrSlotGetter(_))
     }
@@ -171,6 +216,7 @@ impl UnnamedRustFunction {
             is_unsafe: self.is_unsafe,
             kind: self.kind,
             arguments: self.arguments,
+            is_variadic: false,
             return_type: self.return_type,
         }
     }
@@ -298,6 +344,9 @@ pub struct RustFunction {
     /// There can also be arguments shared by all variants (typically the
     /// `self` argument), and they are not listed in this field.
     pub arguments: Vec<RustFunctionArgument>,
+    /// True if the function accepts C variadic arguments after `arguments`.
+    /// Only FFI functions can be variadic.
+    pub is_variadic: bool,
     /// C++ and Rust return types at all levels.
     pub return_type: RustFinalType,
 }
@@ -353,6 +402,7 @@ pub enum RustSpecialModuleKind {
     Ffi,
     Ops,
     SizedTypes,
+    Overloading,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
@@ -370,6 +420,7 @@ impl RustModuleKind {
                 RustSpecialModuleKind::Ffi => false,
                 RustSpecialModuleKind::Ops => true,
                 RustSpecialModuleKind::SizedTypes => false,
+                RustSpecialModuleKind::Overloading => true,
             },
             RustModuleKind::CppNamespace { .. } => true,
             RustModuleKind::CppNestedTypes { .. } => false,
@@ -479,10 +530,139 @@ pub struct RustFlagEnumImpl {
     pub enum_path: RustPath,
 }
 
+/// `PartialEq<CppBox<T>>` implementation for `T` based on
+/// its `PartialEq<Ref<T>>` implementation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RustCppBoxEqImpl {
+    pub target_type: RustType,
+}
+
+/// `Display` and `Debug` implementations for a type based on
+/// its C++ output stream operator
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RustCppDisplayImpl {
+    pub target_type: RustType,
+    /// Path of the FFI function that returns the text written by the operator
+    pub ffi_function_path: RustPath,
+}
+
+/// Virtual function that can be overridden by implementing
+/// the trait of a subclass wrapper
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RustSubclassOverride {
+    /// Name of the trait method
+    pub method_name: String,
+    /// C++ path of the wrapper method that sets the callback
+    pub setter_cpp_path: CppPath,
+    /// C++ path of the wrapper method that calls the base class implementation,
+    /// if it's available
+    pub base_function_cpp_path: Option<CppPath>,
+    /// C++ declaration of the virtual function (for documentation)
+    pub cpp_declaration: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RustSubclassOverridesImpl {
+    /// Path of the generated trait
+    pub trait_path: RustPath,
+    /// Path of the subclass wrapper type
+    pub subclass_path: RustPath,
+    pub overrides: Vec<RustSubclassOverride>,
+}
+
+/// One of the overloads callable through an overload dispatch trait
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RustOverloadVariant {
+    /// Path of the function implementing this overload
+    pub function_path: RustPath,
+    /// Types of arguments of the overload (excluding `self`). An `impl CastInto<T>`
+    /// argument accepts any tuple element implementing `CastInto<T>`.
+    pub argument_types: Vec<RustType>,
+    pub return_type: RustType,
+}
+
+/// Trait dispatching calls of a generic function to a group of overloaded functions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RustOverloadDispatchImpl {
+    /// Path of the generated trait
+    pub trait_path: RustPath,
+    /// Path of the generic function
+    pub function_path: RustPath,
+    /// Type of `self` for methods, or `None` for free functions
+    pub self_type: Option<RustType>,
+    pub self_arg_kind: RustFunctionSelfArgKind,
+    pub is_unsafe: bool,
+    pub variants: Vec<RustOverloadVariant>,
+}
+
+/// Argument of a method of a class template trait
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RustTemplateTraitArgument {
+    pub name: String,
+    /// Type of the argument. It may refer to generic parameters of the trait and `Self`.
+    pub argument_type: RustType,
+}
+
+/// Method of a trait implemented by all instantiations of a class template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RustTemplateTraitMethod {
+    pub name: String,
+    /// Name of the C++ method (for documentation)
+    pub cpp_name: String,
+    pub is_unsafe: bool,
+    /// Arguments of the method, including `self`
+    pub arguments: Vec<RustTemplateTraitArgument>,
+    /// Return type of the method. It may refer to generic parameters of the trait and `Self`.
+    pub return_type: RustType,
+}
+
+/// Implementation of a class template trait for one of the instantiations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RustTemplateTraitInstantiation {
+    pub target_type: RustType,
+    /// Values of generic parameters of the trait
+    pub generic_arguments: Vec<RustType>,
+    /// Paths of the methods of `target_type` implementing the methods of the trait
+    /// (in the same order)
+    pub method_paths: Vec<RustPath>,
+}
+
+/// Generic trait containing methods common to all instantiations of a class template
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RustTemplateTraitImpl {
+    /// Path of the generated trait
+    pub trait_path: RustPath,
+    /// C++ path of the class template
+    pub cpp_template_path: CppPath,
+    /// Generic parameters of the trait corresponding to template parameters
+    pub generic_parameters: Vec<String>,
+    /// Generic parameters (including `Self`) that must implement `CppDeletable`
+    pub deletable_parameters: Vec<String>,
+    pub methods: Vec<RustTemplateTraitMethod>,
+    pub instantiations: Vec<RustTemplateTraitInstantiation>,
+}
+
+/// Implementation of `cpp_core::CppSharedPtrTarget` or `cpp_core::CppUniquePtrTarget`
+/// for a class type
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RustSmartPointerTargetImpl {
+    pub kind: CppSmartPointerKind,
+    pub target_type: RustType,
+    /// Paths of the FFI functions performing the smart pointer operations
+    pub ffi_functions: Vec<(CppSmartPointerOperation, RustPath)>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum RustExtraImplKind {
     FlagEnum(RustFlagEnumImpl),
+    BitmaskEnum(RustFlagEnumImpl),
+    CppBoxEq(RustCppBoxEqImpl),
+    CppDisplay(RustCppDisplayImpl),
     QtReceiverImpl(RustQtReceiverImpl),
+    SubclassOverrides(RustSubclassOverridesImpl),
+    OverloadDispatch(RustOverloadDispatchImpl),
+    TemplateTrait(RustTemplateTraitImpl),
+    SmartPointerTarget(RustSmartPointerTargetImpl),
 }
 
 impl RustExtraImplKind {
@@ -491,9 +671,34 @@ impl RustExtraImplKind {
             RustExtraImplKind::FlagEnum(_) => {
                 matches!(other, RustExtraImplKind::FlagEnum(_))
             }
+            RustExtraImplKind::BitmaskEnum(_) => {
+                matches!(other, RustExtraImplKind::BitmaskEnum(_))
+            }
+            RustExtraImplKind::CppBoxEq(_) => {
+                matches!(other, RustExtraImplKind::CppBoxEq(_))
+            }
+            RustExtraImplKind::CppDisplay(_) => {
+                matches!(other, RustExtraImplKind::CppDisplay(_))
+            }
             RustExtraImplKind::QtReceiverImpl(_) => {
                 matches!(other, RustExtraImplKind::QtReceiverImpl(_))
             }
+            RustExtraImplKind::SubclassOverrides(_) => {
+                matches!(other, RustExtraImplKind::SubclassOverrides(_))
+            }
+            RustExtraImplKind::OverloadDispatch(_) => {
+                matches!(other, RustExtraImplKind::OverloadDispatch(_))
+            }
+            RustExtraImplKind::TemplateTrait(data) => {
+                if let RustExtraImplKind::TemplateTrait(other) = other {
+                    data.trait_path == other.trait_path
+                } else {
+                    false
+                }
+            }
+            RustExtraImplKind::SmartPointerTarget(_) => {
+                matches!(other, RustExtraImplKind::SmartPointerTarget(_))
+            }
         }
     }
 }
@@ -504,9 +709,32 @@ pub struct RustExtraImpl {
     pub kind: RustExtraImplKind,
 }
 
+/// Rust constant (`pub const`) generated from a C++ macro or constant variable
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RustConstant {
+    pub path: RustPath,
+    pub constant_type: RustType,
+    /// Rust code of the value
+    pub value: String,
+}
+
+/// Rust type alias (`pub type`) generated from a C++ typedef
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RustTypeAlias {
+    pub path: RustPath,
+    /// Type referred to by the alias
+    pub target: RustType,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum RustReexportSource {
-    DependencyCrate { crate_name: String },
+    DependencyCrate {
+        crate_name: String,
+    },
+    /// Variadic FFI function exposed in the public API as is.
+    VariadicFunction,
+    /// Generic function dispatching calls to overloaded free functions.
+    OverloadDispatch,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -526,6 +754,8 @@ pub enum RustItem {
     ExtraImpl(RustExtraImpl),
     Function(RustFunction),
     Reexport(RustReexport),
+    TypeAlias(RustTypeAlias),
+    Constant(RustConstant),
 }
 
 impl RustItem {
@@ -536,10 +766,24 @@ impl RustItem {
             RustItem::EnumValue(data) => Some(&data.path),
             RustItem::Function(data) => Some(&data.path),
             RustItem::Reexport(data) => Some(&data.path),
+            RustItem::TypeAlias(data) => Some(&data.path),
+            RustItem::Constant(data) => Some(&data.path),
             RustItem::TraitImpl(_) | RustItem::ExtraImpl(_) => None,
         }
     }
 
+    /// Returns paths that can't be used by other items because they are
+    /// taken by this item, including the items declared by extra impls.
+    pub fn reserved_paths(&self) -> Vec<&RustPath> {
+        match self {
+            RustItem::ExtraImpl(RustExtraImpl {
+                kind: RustExtraImplKind::OverloadDispatch(data),
+                ..
+            }) => vec![&data.trait_path, &data.function_path],
+            _ => self.path().into_iter().collect(),
+        }
+    }
+
     pub fn parent_path(&self) -> Result<RustPath> {
         match self {
             RustItem::TraitImpl(trait_impl) => Ok(trait_impl.parent_path.clone()),
@@ -594,6 +838,20 @@ impl RustItem {
             None
         }
     }
+    pub fn as_type_alias_ref(&self) -> Option<&RustTypeAlias> {
+        if let RustItem::TypeAlias(value) = self {
+            Some(value)
+        } else {
+            None
+        }
+    }
+    pub fn as_constant_ref(&self) -> Option<&RustConstant> {
+        if let RustItem::Constant(value) = self {
+            Some(value)
+        } else {
+            None
+        }
+    }
     pub fn as_function_ref(&self) -> Option<&RustFunction> {
         if let RustItem::Function(value) = self {
             Some(value)
@@ -650,9 +908,13 @@ impl RustItem {
                 }
             }
             RustItem::Function(data) => match &data.kind {
-                RustFunctionKind::FfiWrapper(_) => {
+                RustFunctionKind::FfiWrapper(data) => {
                     if let RustItem::Function(other) = other {
-                        matches!(&other.kind, RustFunctionKind::FfiWrapper(_))
+                        if let RustFunctionKind::FfiWrapper(other) = &other.kind {
+                            data.variant == other.variant
+                        } else {
+                            false
+                        }
                     } else {
                         false
                     }
@@ -679,6 +941,8 @@ impl RustItem {
                     false
                 }
             }
+            RustItem::TypeAlias(_) => matches!(other, RustItem::TypeAlias(_)),
+            RustItem::Constant(_) => matches!(other, RustItem::Constant(_)),
         }
     }
 
@@ -736,6 +1000,17 @@ impl RustItem {
                 data.path.full_name(None),
                 data.target.last()
             ),
+            RustItem::TypeAlias(data) => format!(
+                "type {} = {}",
+                data.path.full_name(None),
+                rust_type_to_code(&data.target, None)
+            ),
+            RustItem::Constant(data) => format!(
+                "const {}: {} = {}",
+                data.path.full_name(None),
+                rust_type_to_code(&data.constant_type, None),
+                data.value
+            ),
         }
     }
 }
@@ -763,6 +1038,7 @@ pub enum NameType<'a> {
         is_from_other_crate: bool,
     },
     EnumValue,
+    Constant,
     Module {
         is_from_other_crate: bool,
     },
@@ -779,6 +1055,9 @@ pub enum NameType<'a> {
     QtSignalWrapper {
         signal_arguments: &'a [CppType],
     },
+    SubclassWrapper {
+        base_class: &'a CppPath,
+    },
 }
 
 impl NameType<'_> {
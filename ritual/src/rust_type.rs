@@ -1,6 +1,6 @@
 use crate::rust_info::RustTypeCaptionStrategy;
 use itertools::Itertools;
-use ritual_common::errors::{bail, Error, Result};
+use ritual_common::errors::{bail, err_msg, Error, Result};
 use ritual_common::string_utils::CaseOperations;
 use ritual_common::utils::MapIfOk;
 use serde_derive::{Deserialize, Serialize};
@@ -139,6 +139,12 @@ pub enum RustToFfiTypeConversion {
     RefToPtr {
         lifetime: Option<String>,
     },
+    /// `&Derived` to `*const Base` (or similar mutable types), converted
+    /// using `cpp_core::StaticUpcast`
+    UpcastRefToPtr {
+        derived_type: RustType,
+        lifetime: Option<String>,
+    },
     /// `Ptr<T>` to `*const T` (or similar mutable type)
     UtilsPtrToPtr {},
     /// `Ref<T>` to `*const T` (or similar mutable types)
@@ -151,10 +157,13 @@ pub enum RustToFfiTypeConversion {
     ValueToPtr,
     /// `CppBox<T>` to `*mut T`
     CppBoxToPtr,
+    /// `CppBox<T>` to `*mut T` for rvalue reference arguments.
+    /// The object is moved from by C++ and deleted when the box is dropped after the call.
+    MovedCppBoxToPtr,
     /// `QBox<T>` to `*mut T`
     QBoxToPtr,
-    /// `qt_core::flags::Flags<T>` to `c_int`
-    QFlagsToUInt {
+    /// `qt_core::QFlags<T>` or a bitmask enum wrapper to `c_int`
+    FlagsToInt {
         api_type: RustType,
     },
     /// `()` to any type
@@ -167,12 +176,143 @@ pub enum RustToFfiTypeConversion {
     RefTo(Box<RustToFfiTypeConversion>),
     ImplCastInto(Box<RustToFfiTypeConversion>),
     ClosureToCallback(Box<RustClosureToCallbackConversion>),
+    /// `Result<T, cpp_core::CppException>` to the FFI type corresponding to `T`,
+    /// with the exception reported through an additional FFI argument
+    ExceptionResult(Box<RustToFfiTypeConversion>),
+    /// `&[T]` to `*const T` or `&mut [T]` to `*mut T`, with the length of the slice
+    /// passed through another FFI argument. If `is_str` is true, `&str` is used
+    /// instead of `&[c_char]`.
+    SliceToPtr {
+        length_ffi_index: usize,
+        length_type: RustType,
+        is_str: bool,
+    },
+    /// `Result<(), cpp_core::StatusError<S>>` to the status value `S` returned
+    /// by the FFI function
+    StatusResult {
+        success: StatusSuccess,
+    },
+    /// Rust public type contains the value returned by the FFI function
+    /// and values of its output arguments
+    OutArguments(Box<RustOutArgumentsConversion>),
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct RustClosureToCallbackConversion {
     pub closure_arguments: Vec<RustFinalType>,
     pub closure_return_type: RustFinalType,
+    /// Index of the FFI callback's argument receiving the user data
+    pub callback_data_index: usize,
+    /// Index of the FFI function's argument receiving the user data
+    pub data_ffi_index: usize,
+    /// Index of the FFI function's argument receiving the deleter of the user data.
+    /// If there is no deleter, the closure is dropped when the FFI function returns,
+    /// unless `is_leaked` is true.
+    pub deleter_ffi_index: Option<usize>,
+    /// If true and there is no deleter, the closure is never dropped because
+    /// the callback may be called after the FFI function returns.
+    pub is_leaked: bool,
+}
+
+/// Condition for the status value returned by a C++ function that indicates success
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum StatusSuccess {
+    /// The function returns `true` on success
+    True,
+    /// The function returns zero on success
+    Zero,
+    /// The function returns a non-zero value on success
+    NonZero,
+    /// The function returns a non-negative value on success
+    NonNegative,
+    /// The function returns the specified value on success
+    Value(i64),
+}
+
+impl StatusSuccess {
+    /// Returns Rust code of the condition checking success of `status_expr`.
+    pub fn condition_code(self, status_expr: &str) -> String {
+        match self {
+            StatusSuccess::True => status_expr.to_string(),
+            StatusSuccess::Zero => format!("{} == 0", status_expr),
+            StatusSuccess::NonZero => format!("{} != 0", status_expr),
+            StatusSuccess::NonNegative => format!("{} >= 0", status_expr),
+            StatusSuccess::Value(value) => format!("{} == {}", status_expr, value),
+        }
+    }
+
+    /// Returns a description of the condition for documentation.
+    pub fn description(self) -> String {
+        match self {
+            StatusSuccess::True => "is `true`".to_string(),
+            StatusSuccess::Zero => "is zero".to_string(),
+            StatusSuccess::NonZero => "is not zero".to_string(),
+            StatusSuccess::NonNegative => "is not negative".to_string(),
+            StatusSuccess::Value(value) => format!("is `{}`", value),
+        }
+    }
+}
+
+/// Output argument of an FFI function whose value is returned from the Rust wrapper
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RustOutArgument {
+    pub name: String,
+    pub ffi_index: usize,
+    /// Type of the value the argument points to
+    pub value_type: RustType,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RustOutArgumentsConversion {
+    /// API type of the value returned by the FFI function
+    pub return_api_type: RustType,
+    /// Conversion of the value returned by the FFI function
+    pub return_conversion: RustToFfiTypeConversion,
+    /// Output arguments whose values are added to the returned value
+    pub arguments: Vec<RustOutArgument>,
+    /// `bool` output argument reporting success. If it's present,
+    /// the returned value is wrapped in `Option`.
+    pub ok_flag: Option<RustOutArgument>,
+}
+
+impl RustOutArgumentsConversion {
+    /// Returns the API type of the Rust wrapper's return value: the FFI function's
+    /// return value followed by the output arguments, as a tuple if there are
+    /// multiple values. If the FFI function returns a status, the values are
+    /// wrapped in its `Result` instead.
+    pub fn api_type(&self) -> RustType {
+        let status_error =
+            if let RustToFfiTypeConversion::StatusResult { .. } = &self.return_conversion {
+                self.return_api_type
+                    .as_common()
+                    .ok()
+                    .and_then(|t| t.generic_arguments.as_ref())
+                    .and_then(|args| args.get(1))
+                    .cloned()
+            } else {
+                None
+            };
+        let mut values = Vec::new();
+        if status_error.is_none() && !self.return_api_type.is_unit() {
+            values.push(self.return_api_type.clone());
+        }
+        values.extend(self.arguments.iter().map(|arg| arg.value_type.clone()));
+        let value = if values.len() == 1 {
+            values.remove(0)
+        } else {
+            RustType::Tuple(values)
+        };
+        let value = if self.ok_flag.is_some() {
+            RustType::new_option(value)
+        } else {
+            value
+        };
+        if let Some(status_error) = status_error {
+            result_type(value, status_error)
+        } else {
+            value
+        }
+    }
 }
 
 impl RustToFfiTypeConversion {
@@ -184,6 +324,22 @@ impl RustToFfiTypeConversion {
         matches!(self, RustToFfiTypeConversion::UtilsRefToPtr { .. })
     }
 
+    pub fn is_exception_result(&self) -> bool {
+        matches!(self, RustToFfiTypeConversion::ExceptionResult(..))
+    }
+
+    pub fn is_slice_to_ptr(&self) -> bool {
+        matches!(self, RustToFfiTypeConversion::SliceToPtr { .. })
+    }
+
+    pub fn as_out_arguments_ref(&self) -> Option<&RustOutArgumentsConversion> {
+        if let RustToFfiTypeConversion::OutArguments(x) = self {
+            Some(x)
+        } else {
+            None
+        }
+    }
+
     pub fn as_callback_ref(&self) -> Option<&RustClosureToCallbackConversion> {
         if let RustToFfiTypeConversion::ClosureToCallback(x) = self {
             Some(x)
@@ -222,6 +378,30 @@ fn utils_ref(ffi_type: &RustType) -> Result<RustType> {
     }))
 }
 
+fn result_type(target: RustType, error: RustType) -> RustType {
+    RustType::Common(RustCommonType {
+        path: RustPath::from_good_str("std::result::Result"),
+        generic_arguments: Some(vec![target, error]),
+    })
+}
+
+fn exception_result(target: RustType) -> RustType {
+    result_type(
+        target,
+        RustType::Common(RustCommonType {
+            path: RustPath::from_good_str("cpp_core::CppException"),
+            generic_arguments: None,
+        }),
+    )
+}
+
+fn status_error(status_type: RustType) -> RustType {
+    RustType::Common(RustCommonType {
+        path: RustPath::from_good_str("cpp_core::StatusError"),
+        generic_arguments: Some(vec![status_type]),
+    })
+}
+
 fn class_type_to_qt_core_crate_path(t: &RustType) -> Result<RustPath> {
     if let RustType::Common(t) = t {
         let name = if t.path.crate_name().starts_with("moqt") {
@@ -252,13 +432,23 @@ impl RustFinalType {
                     bail!("not a pointer like type");
                 }
             }
+            RustToFfiTypeConversion::UpcastRefToPtr {
+                derived_type,
+                lifetime,
+            } => RustType::PointerLike {
+                is_const: true,
+                kind: RustPointerLikeTypeKind::Reference {
+                    lifetime: lifetime.clone(),
+                },
+                target: Box::new(derived_type.clone()),
+            },
             RustToFfiTypeConversion::UtilsPtrToPtr {} => utils_ptr(&ffi_type)?,
             RustToFfiTypeConversion::UtilsRefToPtr {} => utils_ref(&ffi_type)?,
             RustToFfiTypeConversion::OptionUtilsRefToPtr {} => {
                 RustType::new_option(utils_ref(&ffi_type)?)
             }
             RustToFfiTypeConversion::ValueToPtr => ffi_type.pointer_like_to_target()?,
-            RustToFfiTypeConversion::CppBoxToPtr => {
+            RustToFfiTypeConversion::CppBoxToPtr | RustToFfiTypeConversion::MovedCppBoxToPtr => {
                 let target = ffi_type.pointer_like_to_target()?;
                 RustType::Common(RustCommonType {
                     path: RustPath::from_good_str("cpp_core::CppBox"),
@@ -279,7 +469,7 @@ impl RustFinalType {
                     generic_arguments: Some(vec![target.clone()]),
                 })
             }
-            RustToFfiTypeConversion::QFlagsToUInt { api_type }
+            RustToFfiTypeConversion::FlagsToInt { api_type }
             | RustToFfiTypeConversion::AsCast { api_type } => api_type.clone(),
             RustToFfiTypeConversion::UnitToAnything => RustType::unit(),
             RustToFfiTypeConversion::RefTo(conversion) => {
@@ -297,6 +487,26 @@ impl RustFinalType {
             RustToFfiTypeConversion::ClosureToCallback { .. } => {
                 RustType::GenericParameter("T".into())
             }
+            RustToFfiTypeConversion::ExceptionResult(conversion) => {
+                let intermediate = RustFinalType::new(ffi_type.clone(), (**conversion).clone())?;
+                exception_result(intermediate.api_type)
+            }
+            RustToFfiTypeConversion::OutArguments(conversion) => conversion.api_type(),
+            RustToFfiTypeConversion::StatusResult { .. } => {
+                result_type(RustType::unit(), status_error(ffi_type.clone()))
+            }
+            RustToFfiTypeConversion::SliceToPtr { is_str, .. } => {
+                let is_const = ffi_type.is_const_pointer_like()?;
+                let target = if *is_str {
+                    if !is_const {
+                        bail!("str is only supported for const pointers");
+                    }
+                    RustType::Primitive("str".into())
+                } else {
+                    RustType::Slice(Box::new(ffi_type.pointer_like_to_target()?))
+                };
+                RustType::new_reference(is_const, target)
+            }
         };
         Ok(RustFinalType {
             api_type,
@@ -317,6 +527,78 @@ impl RustFinalType {
         &self.conversion
     }
 
+    /// Returns a copy of this type with `target` replaced with `alias` in the API type.
+    /// FFI type and conversion are unaffected because the alias is the same type.
+    pub fn with_type_alias(&self, target: &RustType, alias: &RustType) -> Self {
+        RustFinalType {
+            api_type: self.api_type.replace(target, alias),
+            ffi_type: self.ffi_type.clone(),
+            conversion: self.conversion.clone(),
+        }
+    }
+
+    /// Returns a copy of this type with the API type wrapped in
+    /// `Result<T, cpp_core::CppException>`.
+    pub fn with_exception_result(&self) -> Self {
+        RustFinalType {
+            api_type: exception_result(self.api_type.clone()),
+            ffi_type: self.ffi_type.clone(),
+            conversion: RustToFfiTypeConversion::ExceptionResult(Box::new(self.conversion.clone())),
+        }
+    }
+
+    /// Reverses `with_exception_result`, returning the type of the successful result.
+    pub fn without_exception_result(&self) -> Result<Self> {
+        if let RustToFfiTypeConversion::ExceptionResult(conversion) = &self.conversion {
+            let api_type = self
+                .api_type
+                .as_common()?
+                .generic_arguments
+                .as_ref()
+                .and_then(|args| args.get(0))
+                .ok_or_else(|| err_msg("expected generic argument for Result"))?
+                .clone();
+            Ok(RustFinalType {
+                api_type,
+                ffi_type: self.ffi_type.clone(),
+                conversion: (**conversion).clone(),
+            })
+        } else {
+            bail!("not an ExceptionResult type");
+        }
+    }
+
+    /// Returns a copy of this type with values of `arguments` added to
+    /// the API type. If `ok_flag` is specified, the API type is also wrapped in `Option`.
+    pub fn with_out_arguments(
+        &self,
+        arguments: Vec<RustOutArgument>,
+        ok_flag: Option<RustOutArgument>,
+    ) -> Result<Self> {
+        RustFinalType::new(
+            self.ffi_type.clone(),
+            RustToFfiTypeConversion::OutArguments(Box::new(RustOutArgumentsConversion {
+                return_api_type: self.api_type.clone(),
+                return_conversion: self.conversion.clone(),
+                arguments,
+                ok_flag,
+            })),
+        )
+    }
+
+    /// Reverses `with_out_arguments`, returning the type of the FFI function's return value.
+    pub fn without_out_arguments(&self) -> Result<Self> {
+        if let RustToFfiTypeConversion::OutArguments(conversion) = &self.conversion {
+            Ok(RustFinalType {
+                api_type: conversion.return_api_type.clone(),
+                ffi_type: self.ffi_type.clone(),
+                conversion: conversion.return_conversion.clone(),
+            })
+        } else {
+            bail!("not an OutArguments type");
+        }
+    }
+
     pub fn with_lifetime(&self, lifetime: String) -> Result<Self> {
         if let RustToFfiTypeConversion::RefToPtr { .. } = &self.conversion {
             RustFinalType::new(
@@ -411,6 +693,16 @@ where
 }
 
 impl RustCommonType {
+    fn replace(&self, from: &RustType, to: &RustType) -> RustCommonType {
+        RustCommonType {
+            path: self.path.clone(),
+            generic_arguments: self
+                .generic_arguments
+                .as_ref()
+                .map(|args| args.iter().map(|t| t.replace(from, to)).collect()),
+        }
+    }
+
     pub fn can_be_same_as(&self, other: &RustCommonType) -> bool {
         let self_args = self
             .generic_arguments
@@ -439,6 +731,8 @@ pub struct RustFunctionPointerType {
     pub return_type: Box<RustType>,
     /// Argument types of the function.
     pub arguments: Vec<RustType>,
+    /// True if the function accepts C variadic arguments after `arguments`.
+    pub is_variadic: bool,
 }
 
 /// A Rust type
@@ -458,6 +752,8 @@ pub enum RustType {
     },
     ImplTrait(RustCommonType),
     GenericParameter(String),
+    /// Dynamically sized slice type (`[T]`)
+    Slice(Box<RustType>),
 }
 
 impl RustType {
@@ -581,6 +877,9 @@ impl RustType {
                 name
             }
             RustType::FunctionPointer { .. } => "fn".to_string(),
+            RustType::Slice(item_type) => {
+                format!("{}_slice", item_type.caption(context, strategy)?)
+            }
             RustType::ImplTrait(trait_type) => {
                 if trait_type.path == RustPath::from_good_str("cpp_core::CastInto") {
                     trait_type
@@ -596,6 +895,42 @@ impl RustType {
         })
     }
 
+    /// Returns a copy of this type with all occurrences of `from` replaced with `to`.
+    pub fn replace(&self, from: &RustType, to: &RustType) -> RustType {
+        if self == from {
+            return to.clone();
+        }
+        match self {
+            RustType::Tuple(types) => {
+                RustType::Tuple(types.iter().map(|t| t.replace(from, to)).collect())
+            }
+            RustType::Primitive(_) | RustType::GenericParameter(_) => self.clone(),
+            RustType::Common(common) => RustType::Common(common.replace(from, to)),
+            RustType::ImplTrait(common) => RustType::ImplTrait(common.replace(from, to)),
+            RustType::Slice(item_type) => RustType::Slice(Box::new(item_type.replace(from, to))),
+            RustType::FunctionPointer(function) => {
+                RustType::FunctionPointer(RustFunctionPointerType {
+                    return_type: Box::new(function.return_type.replace(from, to)),
+                    arguments: function
+                        .arguments
+                        .iter()
+                        .map(|t| t.replace(from, to))
+                        .collect(),
+                    is_variadic: function.is_variadic,
+                })
+            }
+            RustType::PointerLike {
+                kind,
+                is_const,
+                target,
+            } => RustType::PointerLike {
+                kind: kind.clone(),
+                is_const: *is_const,
+                target: Box::new(target.replace(from, to)),
+            },
+        }
+    }
+
     /// Returns true if this type is a reference.
     pub fn is_ref(&self) -> bool {
         match self {
@@ -674,6 +1009,7 @@ impl RustType {
                     || function.arguments.iter().any(RustType::is_unsafe_argument)
             }
             RustType::ImplTrait(_) => true,
+            RustType::Slice(item_type) => item_type.is_unsafe_argument(),
         }
     }
 
@@ -685,6 +1021,16 @@ impl RustType {
         }
     }
 
+    /// Returns `T` if this type is `impl CastInto<T>`.
+    pub fn cast_into_target(&self) -> Option<&RustType> {
+        if let RustType::ImplTrait(trait_type) = self {
+            if trait_type.path == RustPath::from_good_str("cpp_core::CastInto") {
+                return trait_type.generic_arguments.as_ref()?.get(0);
+            }
+        }
+        None
+    }
+
     pub fn ptr_to_ref(&self, is_const1: bool) -> Result<Self> {
         let mut r = self.clone();
         if let RustType::PointerLike { is_const, kind, .. } = &mut r {
@@ -739,6 +1085,7 @@ impl RustType {
             RustType::FunctionPointer(function) => {
                 if let RustType::FunctionPointer(other) = other {
                     function.return_type.can_be_same_as(&other.return_type)
+                        && function.is_variadic == other.is_variadic
                         && function.arguments.len() == other.arguments.len()
                         && function
                             .arguments
@@ -775,6 +1122,13 @@ impl RustType {
                 }
             }
             RustType::GenericParameter(_) => self == other,
+            RustType::Slice(item_type) => {
+                if let RustType::Slice(other_item_type) = other {
+                    item_type.can_be_same_as(other_item_type)
+                } else {
+                    false
+                }
+            }
         }
     }
 }
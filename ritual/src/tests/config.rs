@@ -0,0 +1,97 @@
+use crate::config::*;
+use crate::cpp_data::CppPath;
+
+#[test]
+fn pointer_annotation_rules() {
+    let mut config = Config::new(CrateProperties::new("A", "0.0.0"));
+    assert!(config.pointer_annotation_rules().is_empty());
+    let rule = PointerAnnotationRule {
+        function: CppPath::from_good_str("QObject::parent"),
+        argument: None,
+        annotation: PointerAnnotation::Nullable,
+    };
+    config.add_pointer_annotation_rule(rule.clone());
+    assert_eq!(config.pointer_annotation_rules(), &[rule]);
+}
+
+#[test]
+fn pointer_annotation_rules_from_toml() {
+    let mut config = Config::new(CrateProperties::new("A", "0.0.0"));
+    config
+        .add_pointer_annotation_rules_from_toml(
+            r#"
+            [[rule]]
+            function = "QListWidget::takeItem"
+            annotation = "owned"
+
+            [[rule]]
+            function = "QObject::setParent"
+            argument = "parent"
+            annotation = "nullable"
+
+            [[rule]]
+            function = "ns::f"
+            argument = "x"
+            annotation = "non_null"
+            "#,
+        )
+        .unwrap();
+    assert_eq!(
+        config.pointer_annotation_rules(),
+        &[
+            PointerAnnotationRule {
+                function: CppPath::from_good_str("QListWidget::takeItem"),
+                argument: None,
+                annotation: PointerAnnotation::Owned,
+            },
+            PointerAnnotationRule {
+                function: CppPath::from_good_str("QObject::setParent"),
+                argument: Some("parent".to_string()),
+                annotation: PointerAnnotation::Nullable,
+            },
+            PointerAnnotationRule {
+                function: CppPath::from_good_str("ns::f"),
+                argument: Some("x".to_string()),
+                annotation: PointerAnnotation::NonNull,
+            },
+        ]
+    );
+}
+
+#[test]
+fn pointer_annotation_rules_from_empty_toml() {
+    let mut config = Config::new(CrateProperties::new("A", "0.0.0"));
+    config.add_pointer_annotation_rules_from_toml("").unwrap();
+    assert!(config.pointer_annotation_rules().is_empty());
+}
+
+#[test]
+fn pointer_annotation_rules_from_invalid_toml() {
+    let mut config = Config::new(CrateProperties::new("A", "0.0.0"));
+    let unknown_annotation = r#"
+        [[rule]]
+        function = "QObject::parent"
+        annotation = "borrowed"
+    "#;
+    assert!(config
+        .add_pointer_annotation_rules_from_toml(unknown_annotation)
+        .is_err());
+
+    let missing_annotation = r#"
+        [[rule]]
+        function = "QObject::parent"
+    "#;
+    assert!(config
+        .add_pointer_annotation_rules_from_toml(missing_annotation)
+        .is_err());
+
+    let invalid_path = r#"
+        [[rule]]
+        function = "QVector<int>::at"
+        annotation = "nullable"
+    "#;
+    assert!(config
+        .add_pointer_annotation_rules_from_toml(invalid_path)
+        .is_err());
+    assert!(config.pointer_annotation_rules().is_empty());
+}
@@ -1,5 +1,8 @@
+use crate::cpp_data::{CppPath, CppVisibility};
 use crate::cpp_ffi_data::*;
+use crate::cpp_function::CppFunctionMemberData;
 use crate::cpp_type::*;
+use crate::tests::cpp_method::{empty_membership, empty_regular_method};
 
 #[test]
 fn argument_meaning() {
@@ -68,3 +71,40 @@ fn cpp_ffi_type_void() {
     assert!(t.ffi_type().is_void());
     assert_eq!(t.conversion(), &CppToFfiTypeConversion::NoChange);
 }
+
+#[test]
+fn virtual_function_override() {
+    let mut function = empty_regular_method();
+    function.path = CppPath::from_good_str("Model::rowCount");
+    function.return_type = CppType::BuiltInNumeric(CppBuiltInNumericType::Int);
+    function.member = Some(CppFunctionMemberData {
+        is_virtual: true,
+        is_pure_virtual: true,
+        is_const: true,
+        ..empty_membership()
+    });
+    let mut item = CppVirtualFunctionOverride {
+        function,
+        callback_name: "rowCount".to_string(),
+        arguments: vec![],
+        return_type: CppFfiType::new(
+            CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
+            CppToFfiTypeConversion::NoChange,
+        )
+        .unwrap(),
+        function_type: CppFunctionPointerType {
+            allows_variadic_arguments: false,
+            return_type: Box::new(CppType::BuiltInNumeric(CppBuiltInNumericType::Int)),
+            arguments: vec![CppType::new_pointer(false, CppType::Void)],
+        },
+    };
+    assert_eq!(item.setter_name(), "set_rowCount_callback");
+    assert_eq!(item.base_function_name(), "rowCount_base");
+    assert!(!item.has_base_function());
+
+    item.function.member.as_mut().unwrap().is_pure_virtual = false;
+    assert!(item.has_base_function());
+
+    item.function.member.as_mut().unwrap().visibility = CppVisibility::Private;
+    assert!(!item.has_base_function());
+}
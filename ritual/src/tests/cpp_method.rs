@@ -42,6 +42,8 @@ pub fn empty_regular_method() -> CppFunction {
         allows_variadic_arguments: false,
         operator: None,
         declaration_code: None,
+        return_type_alias: None,
+        has_c_linkage: false,
         cast: None,
     }
 }
@@ -62,6 +64,7 @@ fn argument_types_equal2() {
         argument_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
         name: "arg1".to_string(),
         has_default_value: false,
+        type_alias: None,
     });
     assert!(!method1.argument_types_equal(&method2));
     assert!(!method2.argument_types_equal(&method1));
@@ -75,11 +78,13 @@ fn argument_types_equal3() {
         argument_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
         name: "arg1".to_string(),
         has_default_value: false,
+        type_alias: None,
     });
     method2.arguments.push(CppFunctionArgument {
         argument_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
         name: "x".to_string(),
         has_default_value: false,
+        type_alias: None,
     });
     assert!(method1.argument_types_equal(&method2));
     assert!(method2.argument_types_equal(&method1));
@@ -93,11 +98,13 @@ fn argument_types_equal4() {
         argument_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
         name: "arg1".to_string(),
         has_default_value: false,
+        type_alias: None,
     });
     method2.arguments.push(CppFunctionArgument {
         argument_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
         name: "arg1".to_string(),
         has_default_value: true,
+        type_alias: None,
     });
     assert!(method1.argument_types_equal(&method2));
     assert!(method2.argument_types_equal(&method1));
@@ -111,6 +118,7 @@ fn argument_types_equal5() {
         argument_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
         name: "arg1".to_string(),
         has_default_value: false,
+        type_alias: None,
     });
     method2.arguments.push(CppFunctionArgument {
         argument_type: CppType::Enum {
@@ -118,6 +126,7 @@ fn argument_types_equal5() {
         },
         name: "arg1".to_string(),
         has_default_value: false,
+        type_alias: None,
     });
     assert!(!method1.argument_types_equal(&method2));
     assert!(!method2.argument_types_equal(&method1));
@@ -131,6 +140,7 @@ fn argument_types_equal6() {
         argument_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
         name: "arg1".to_string(),
         has_default_value: false,
+        type_alias: None,
     });
     method2.arguments.push(CppFunctionArgument {
         argument_type: CppType::new_pointer(
@@ -139,6 +149,7 @@ fn argument_types_equal6() {
         ),
         name: "arg1".to_string(),
         has_default_value: false,
+        type_alias: None,
     });
     assert!(!method1.argument_types_equal(&method2));
     assert!(!method2.argument_types_equal(&method1));
@@ -151,6 +162,7 @@ fn argument_types_equal7() {
         argument_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
         name: "arg1".to_string(),
         has_default_value: false,
+        type_alias: None,
     };
     let mut method2 = empty_regular_method();
     method1.arguments.push(int.clone());
@@ -169,13 +181,51 @@ fn argument_types_equal8() {
     assert!(method2.argument_types_equal(&method1));
 }
 
+#[test]
+fn rvalue_reference_overload() {
+    let class_type = CppType::Class(CppPath::from_good_str("Class1"));
+    let argument = |argument_type| CppFunctionArgument {
+        argument_type,
+        name: "arg1".to_string(),
+        has_default_value: false,
+        type_alias: None,
+    };
+    let mut const_ref_method = empty_regular_method();
+    const_ref_method
+        .arguments
+        .push(argument(CppType::new_reference(true, class_type.clone())));
+    let mut rvalue_ref_method = empty_regular_method();
+    rvalue_ref_method
+        .arguments
+        .push(argument(CppType::PointerLike {
+            kind: CppPointerLikeTypeKind::RValueReference,
+            is_const: false,
+            target: Box::new(class_type.clone()),
+        }));
+    let mut mut_ref_method = empty_regular_method();
+    mut_ref_method
+        .arguments
+        .push(argument(CppType::new_reference(false, class_type)));
+
+    assert!(rvalue_ref_method.is_rvalue_reference_overload_of(&const_ref_method));
+    assert!(!const_ref_method.is_rvalue_reference_overload_of(&rvalue_ref_method));
+    assert!(!rvalue_ref_method.is_rvalue_reference_overload_of(&mut_ref_method));
+    assert!(!rvalue_ref_method.is_rvalue_reference_overload_of(&rvalue_ref_method));
+
+    let mut other_name_method = const_ref_method.clone();
+    other_name_method.path = CppPath::from_good_str("other");
+    assert!(!rvalue_ref_method.is_rvalue_reference_overload_of(&other_name_method));
+}
+
 fn to_ffi(function: &CppFunction, force_stack: Option<CppPath>) -> CppFfiFunction {
     let movable_types = force_stack.into_iter().collect_vec();
     crate::cpp_ffi_generator::to_ffi_method(
         NewFfiFunctionKind::Function {
             cpp_function: function.clone(),
+            va_list_function: None,
         },
         &movable_types,
+        &[CppFlagContainer::qflags()],
         &mut crate::cpp_ffi_generator::FfiNameProvider::testing(),
     )
     .unwrap()
@@ -206,6 +256,7 @@ fn c_signature_simple_func() {
         },
         name: "arg1".to_string(),
         has_default_value: false,
+        type_alias: None,
     });
     let r = to_ffi(&method1, None);
     assert!(r.arguments.len() == 1);
@@ -226,6 +277,193 @@ fn c_signature_simple_func() {
     );
 }
 
+#[test]
+fn c_signature_exception_argument() {
+    let mut method1 = empty_regular_method();
+    method1.return_type = CppType::BuiltInNumeric(CppBuiltInNumericType::Int);
+    let mut r = to_ffi(&method1, None);
+    crate::cpp_ffi_generator::add_exception_argument(&mut r).unwrap();
+    assert_eq!(r.arguments.len(), 1);
+    assert_eq!(r.arguments[0].name, "exception");
+    assert_eq!(r.arguments[0].meaning, CppFfiArgumentMeaning::Exception);
+    assert_eq!(
+        r.arguments[0].argument_type.ffi_type(),
+        &CppType::new_pointer(
+            false,
+            CppType::new_pointer(false, CppType::BuiltInNumeric(CppBuiltInNumericType::Char))
+        )
+    );
+    assert!(!r.arguments[0].meaning.is_argument());
+    assert_eq!(r.return_type.ffi_type(), &method1.return_type);
+}
+
+#[test]
+fn c_signature_std_function_argument() {
+    let int = CppType::BuiltInNumeric(CppBuiltInNumericType::Int);
+    let function_type = CppFunctionPointerType {
+        return_type: Box::new(int.clone()),
+        arguments: vec![CppType::BuiltInNumeric(CppBuiltInNumericType::Bool)],
+        allows_variadic_arguments: false,
+    };
+    let std_function = CppType::Class(CppPath::from_good_str("std").join(CppPathItem {
+        name: "function".into(),
+        template_arguments: Some(vec![CppType::FunctionPointer(function_type)]),
+    }));
+    assert_eq!(
+        std_function.to_cpp_code(None).unwrap(),
+        "std::function< int(bool) >"
+    );
+
+    let mut method1 = empty_regular_method();
+    method1.return_type = CppType::Void;
+    method1.arguments.push(CppFunctionArgument {
+        argument_type: CppType::new_reference(true, std_function),
+        name: "callback".to_string(),
+        has_default_value: false,
+        type_alias: None,
+    });
+    let r = to_ffi(&method1, None);
+    assert_eq!(r.arguments.len(), 3);
+    assert_eq!(r.arguments[0].name, "callback");
+    assert_eq!(r.arguments[0].meaning, CppFfiArgumentMeaning::Argument(0));
+    assert_eq!(
+        r.arguments[0].argument_type.conversion(),
+        &CppToFfiTypeConversion::FunctionToCallback
+    );
+    assert_eq!(
+        r.arguments[0].to_cpp_code().unwrap(),
+        "int (*callback)(void *, bool)"
+    );
+    assert_eq!(r.arguments[1].name, "callback_data");
+    assert_eq!(
+        r.arguments[1].meaning,
+        CppFfiArgumentMeaning::CallbackData(0)
+    );
+    assert_eq!(
+        r.arguments[1].to_cpp_code().unwrap(),
+        "void * callback_data"
+    );
+    assert_eq!(r.arguments[2].name, "callback_deleter");
+    assert_eq!(
+        r.arguments[2].meaning,
+        CppFfiArgumentMeaning::CallbackDeleter(0)
+    );
+    assert_eq!(
+        r.arguments[2].to_cpp_code().unwrap(),
+        "void (*callback_deleter)(void *)"
+    );
+}
+
+#[test]
+fn c_signature_variadic() {
+    let mut method1 = empty_regular_method();
+    method1.path = CppPath::from_good_str("log_message");
+    method1.return_type = CppType::BuiltInNumeric(CppBuiltInNumericType::Int);
+    method1.allows_variadic_arguments = true;
+    method1.arguments.push(CppFunctionArgument {
+        argument_type: CppType::new_pointer(
+            true,
+            CppType::BuiltInNumeric(CppBuiltInNumericType::Char),
+        ),
+        name: "format".to_string(),
+        has_default_value: false,
+        type_alias: None,
+    });
+
+    // a function with C++ linkage can't be called directly
+    assert!(crate::cpp_ffi_generator::to_ffi_method(
+        NewFfiFunctionKind::Function {
+            cpp_function: method1.clone(),
+            va_list_function: None,
+        },
+        &[],
+        &[],
+        &mut crate::cpp_ffi_generator::FfiNameProvider::testing(),
+    )
+    .is_err());
+
+    method1.has_c_linkage = true;
+    let r = to_ffi(&method1, None);
+    assert_eq!(
+        r.variadic_arguments,
+        Some(CppFfiVariadicArguments::DirectLinkage)
+    );
+    assert_eq!(r.path, method1.path);
+    assert_eq!(r.arguments.len(), 1);
+
+    let va_list_function = CppPath::from_good_str("vlog_message");
+    let r = crate::cpp_ffi_generator::to_ffi_method(
+        NewFfiFunctionKind::Function {
+            cpp_function: method1.clone(),
+            va_list_function: Some(va_list_function.clone()),
+        },
+        &[],
+        &[],
+        &mut crate::cpp_ffi_generator::FfiNameProvider::testing(),
+    )
+    .unwrap();
+    assert_eq!(
+        r.variadic_arguments,
+        Some(CppFfiVariadicArguments::VaList { va_list_function })
+    );
+    assert_ne!(r.path, method1.path);
+    assert_eq!(r.arguments.len(), 1);
+
+    method1.arguments.clear();
+    assert!(crate::cpp_ffi_generator::to_ffi_method(
+        NewFfiFunctionKind::Function {
+            cpp_function: method1,
+            va_list_function: None,
+        },
+        &[],
+        &[],
+        &mut crate::cpp_ffi_generator::FfiNameProvider::testing(),
+    )
+    .is_err());
+}
+
+#[test]
+fn stream_output_operator() {
+    let mut method1 = empty_regular_method();
+    method1.path = CppPath::from_good_str("operator<<");
+    method1.operator = Some(CppOperator::BitwiseLeftShift);
+    method1.return_type = CppType::new_reference(
+        false,
+        CppType::Class(CppPath::from_good_str("std::ostream")),
+    );
+    method1.arguments.push(CppFunctionArgument {
+        argument_type: method1.return_type.clone(),
+        name: "stream".to_string(),
+        has_default_value: false,
+        type_alias: None,
+    });
+    method1.arguments.push(CppFunctionArgument {
+        argument_type: CppType::new_reference(
+            true,
+            CppType::Class(CppPath::from_good_str("Point")),
+        ),
+        name: "point".to_string(),
+        has_default_value: false,
+        type_alias: None,
+    });
+    assert!(crate::cpp_ffi_generator::is_stream_output_operator(
+        &method1
+    ));
+
+    let mut method2 = method1.clone();
+    method2.arguments[1].argument_type = CppType::BuiltInNumeric(CppBuiltInNumericType::Int);
+    assert!(!crate::cpp_ffi_generator::is_stream_output_operator(
+        &method2
+    ));
+
+    let mut method3 = method1.clone();
+    method3.arguments[0].argument_type =
+        CppType::new_reference(false, CppType::Class(CppPath::from_good_str("QDebug")));
+    assert!(!crate::cpp_ffi_generator::is_stream_output_operator(
+        &method3
+    ));
+}
+
 #[test]
 fn c_signature_method_with_this() {
     let mut method1 = empty_regular_method();
@@ -236,6 +474,7 @@ fn c_signature_method_with_this() {
         argument_type: CppType::Class(CppPath::from_good_str("MyClass2")),
         name: "my_arg".to_string(),
         has_default_value: false,
+        type_alias: None,
     });
 
     assert!(!method1.is_constructor());
@@ -288,6 +527,7 @@ fn c_signature_static_method() {
         },
         name: "arg1".to_string(),
         has_default_value: false,
+        type_alias: None,
     });
     let r = to_ffi(&method1, None);
     assert!(r.arguments.len() == 1);
@@ -322,6 +562,7 @@ fn c_signature_constructor() {
         ),
         name: "arg1".to_string(),
         has_default_value: true,
+        type_alias: None,
     });
 
     assert!(method1.is_constructor());
@@ -463,6 +704,7 @@ fn c_signature_method_returning_class() {
         argument_type: CppType::Class(CppPath::from_good_str("MyClass2")),
         name: "my_arg".to_string(),
         has_default_value: false,
+        type_alias: None,
     });
     let r_stack = to_ffi(&method1, Some(CppPath::from_good_str("MyClass3")));
     assert!(r_stack.arguments.len() == 3);
@@ -613,16 +855,20 @@ fn short_text1() {
                 argument_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
                 name: "arg1".to_string(),
                 has_default_value: false,
+                type_alias: None,
             },
             CppFunctionArgument {
                 argument_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Double),
                 name: "arg2".to_string(),
                 has_default_value: true,
+                type_alias: None,
             },
         ],
         allows_variadic_arguments: false,
         cast: None,
         declaration_code: None,
+        return_type_alias: None,
+        has_c_linkage: false,
     };
     assert_eq!(
         method.short_text(),
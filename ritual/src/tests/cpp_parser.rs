@@ -14,6 +14,9 @@ struct ParserCppData {
     types: Vec<CppTypeDeclaration>,
     bases: Vec<CppBaseSpecifier>,
     fields: Vec<CppClassField>,
+    variables: Vec<CppVariable>,
+    type_aliases: Vec<CppTypeAlias>,
+    constants: Vec<CppConstant>,
     methods: Vec<CppFunction>,
     enum_values: Vec<CppEnumValue>,
     namespaces: Vec<CppPath>,
@@ -63,6 +66,21 @@ fn run_parser(code: &'static str) -> ParserCppData {
             .filter_map(|item| item.item.as_field_ref())
             .cloned()
             .collect(),
+        variables: database
+            .cpp_items()
+            .filter_map(|item| item.item.as_variable_ref())
+            .cloned()
+            .collect(),
+        type_aliases: database
+            .cpp_items()
+            .filter_map(|item| item.item.as_type_alias_ref())
+            .cloned()
+            .collect(),
+        constants: database
+            .cpp_items()
+            .filter_map(|item| item.item.as_constant_ref())
+            .cloned()
+            .collect(),
         enum_values: database
             .cpp_items()
             .filter_map(|item| item.item.as_enum_value_ref())
@@ -97,10 +115,13 @@ fn simple_func() {
                 name: "x".to_string(),
                 argument_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
                 has_default_value: false,
+                type_alias: None,
             }],
             allows_variadic_arguments: false,
             cast: None,
             declaration_code: Some("int func1 ( int x )".to_string()),
+            return_type_alias: None,
+            has_c_linkage: false,
         }
     );
 }
@@ -127,10 +148,13 @@ fn simple_func_with_default_value() {
                 name: "x".to_string(),
                 argument_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
                 has_default_value: true,
+                type_alias: None,
             }],
             allows_variadic_arguments: false,
             cast: None,
             declaration_code: Some("bool func1 ( int x = 42 )".to_string()),
+            return_type_alias: None,
+            has_c_linkage: false,
         }
     );
 }
@@ -155,14 +179,13 @@ fn functions_with_class_arg() {
 
     assert!(data.bases.is_empty());
 
-    assert_eq!(data.fields.len(), 3);
+    assert_eq!(data.fields.len(), 2);
     assert_eq!(data.fields[0].path, CppPath::from_good_str("Magic::a"));
     assert_eq!(
         data.fields[0].field_type,
         CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
     );
     assert_eq!(data.fields[0].visibility, CppVisibility::Public);
-    assert_eq!(data.fields[0].is_static, false);
 
     assert_eq!(data.fields[1].path, CppPath::from_good_str("Magic::b"));
     assert_eq!(
@@ -170,15 +193,16 @@ fn functions_with_class_arg() {
         CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
     );
     assert_eq!(data.fields[1].visibility, CppVisibility::Public);
-    assert_eq!(data.fields[1].is_static, false);
 
-    assert_eq!(data.fields[2].path, CppPath::from_good_str("Magic::c"));
     assert_eq!(
-        data.fields[2].field_type,
-        CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
+        data.variables,
+        vec![CppVariable {
+            path: CppPath::from_good_str("Magic::c"),
+            variable_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
+            is_const: false,
+            visibility: CppVisibility::Public,
+        }]
     );
-    assert_eq!(data.fields[2].visibility, CppVisibility::Public);
-    assert_eq!(data.fields[2].is_static, true);
 
     assert_eq!(data.methods.len(), 3);
     assert_eq!(
@@ -192,10 +216,13 @@ fn functions_with_class_arg() {
                 name: "x".to_string(),
                 argument_type: CppType::Class(CppPath::from_good_str("Magic")),
                 has_default_value: false,
+                type_alias: None,
             }],
             allows_variadic_arguments: false,
             cast: None,
             declaration_code: Some("bool func1 ( Magic x )".to_string()),
+            return_type_alias: None,
+            has_c_linkage: false,
         }
     );
     assert_eq!(
@@ -212,10 +239,13 @@ fn functions_with_class_arg() {
                     CppType::Class(CppPath::from_good_str("Magic"))
                 ),
                 has_default_value: false,
+                type_alias: None,
             }],
             allows_variadic_arguments: false,
             cast: None,
             declaration_code: Some("bool func1 ( Magic * x )".to_string()),
+            return_type_alias: None,
+            has_c_linkage: false,
         }
     );
     assert_eq!(
@@ -232,10 +262,13 @@ fn functions_with_class_arg() {
                     CppType::Class(CppPath::from_good_str("Magic"))
                 ),
                 has_default_value: false,
+                type_alias: None,
             }],
             allows_variadic_arguments: false,
             cast: None,
             declaration_code: Some("bool func2 ( const Magic & )".to_string()),
+            return_type_alias: None,
+            has_c_linkage: false,
         }
     );
 }
@@ -285,14 +318,43 @@ fn variadic_func() {
                     CppType::BuiltInNumeric(CppBuiltInNumericType::Char)
                 ),
                 has_default_value: false,
+                type_alias: None,
             }],
             allows_variadic_arguments: true,
             cast: None,
             declaration_code: Some("int my_printf ( const char * format , ... )".to_string()),
+            return_type_alias: None,
+            has_c_linkage: false,
         }
     );
 }
 
+#[test]
+fn c_linkage() {
+    let data = run_parser(
+        "
+        extern \"C\" int c_printf(const char* format, ...);
+        extern \"C\" {
+            int c_printf2(const char* format, ...);
+            extern \"C++\" int cpp_printf(const char* format, ...);
+        }
+        int cpp_printf2(const char* format, ...);
+        ",
+    );
+    assert_eq!(data.methods.len(), 4);
+    let has_c_linkage = |name: &str| {
+        data.methods
+            .iter()
+            .find(|method| method.path == CppPath::from_good_str(name))
+            .unwrap()
+            .has_c_linkage
+    };
+    assert!(has_c_linkage("c_printf"));
+    assert!(has_c_linkage("c_printf2"));
+    assert!(!has_c_linkage("cpp_printf"));
+    assert!(!has_c_linkage("cpp_printf2"));
+}
+
 #[test]
 fn free_template_func() {
     let data = run_parser(
@@ -332,10 +394,13 @@ fn free_template_func() {
                     name: "T".into(),
                 }),
                 has_default_value: false,
+                type_alias: None,
             }],
             allows_variadic_arguments: false,
             cast: None,
             declaration_code: Some("template < typename T > T abs ( T value )".to_string()),
+            return_type_alias: None,
+            has_c_linkage: false,
         }
     );
 }
@@ -367,16 +432,20 @@ fn free_func_operator_sub() {
                         name: "a".to_string(),
                         argument_type: CppType::Class(CppPath::from_good_str("C1")),
                         has_default_value: false,
+                        type_alias: None,
                     },
                     CppFunctionArgument {
                         name: "b".to_string(),
                         argument_type: CppType::Class(CppPath::from_good_str("C1")),
                         has_default_value: false,
+                        type_alias: None,
                     },
                 ],
                 allows_variadic_arguments: false,
                 cast: None,
                 declaration_code: Some("C1 operator - ( C1 a , C1 b )".to_string()),
+                return_type_alias: None,
+                has_c_linkage: false,
             }
         );
     }
@@ -422,10 +491,13 @@ fn simple_class_method() {
                 name: "x".to_string(),
                 argument_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
                 has_default_value: false,
+                type_alias: None,
             }],
             allows_variadic_arguments: false,
             cast: None,
             declaration_code: Some("int func1 ( int x )".to_string()),
+            return_type_alias: None,
+            has_c_linkage: false,
         }
     );
 }
@@ -616,10 +688,13 @@ fn template_class_method() {
                 name: "index".to_string(),
                 argument_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
                 has_default_value: false,
+                type_alias: None,
             }],
             allows_variadic_arguments: false,
             cast: None,
             declaration_code: Some("T get ( int index )".to_string()),
+            return_type_alias: None,
+            has_c_linkage: false,
         }
     );
     assert_eq!(
@@ -757,7 +832,8 @@ fn simple_enum() {
     );
     assert_eq!(data.types.len(), 1);
     assert_eq!(data.types[0].path, CppPath::from_good_str("Enum1"));
-    assert_eq!(data.types[0].kind, CppTypeDeclarationKind::Enum);
+    // the underlying type of an unscoped enum is platform-dependent
+    assert!(data.types[0].kind.is_enum());
     assert_eq!(
         data.enum_values,
         vec![
@@ -788,7 +864,8 @@ fn simple_enum2() {
     );
     assert_eq!(data.types.len(), 1);
     assert_eq!(data.types[0].path, CppPath::from_good_str("ns1::Enum1"));
-    assert_eq!(data.types[0].kind, CppTypeDeclarationKind::Enum);
+    // the underlying type of an unscoped enum is platform-dependent
+    assert!(data.types[0].kind.is_enum());
     assert_eq!(
         data.enum_values,
         vec![
@@ -809,6 +886,50 @@ fn simple_enum2() {
     assert_eq!(data.namespaces, vec![CppPath::from_good_str("ns1")]);
 }
 
+#[test]
+fn enum_underlying_types() {
+    let data = run_parser(
+        "
+        enum class Enum1 : unsigned char { Good, Bad };
+        enum Enum2 : long long { Small = -1, Large = 0x100000000 };
+        ",
+    );
+    assert_eq!(data.types.len(), 2);
+    assert_eq!(
+        data.types[0].kind,
+        CppTypeDeclarationKind::Enum {
+            underlying_type: CppType::BuiltInNumeric(CppBuiltInNumericType::UChar)
+        }
+    );
+    assert_eq!(
+        data.types[1].kind,
+        CppTypeDeclarationKind::Enum {
+            underlying_type: CppType::BuiltInNumeric(CppBuiltInNumericType::LongLong)
+        }
+    );
+    assert_eq!(
+        data.enum_values,
+        vec![
+            CppEnumValue {
+                value: 0,
+                path: CppPath::from_good_str("Enum1::Good"),
+            },
+            CppEnumValue {
+                value: 1,
+                path: CppPath::from_good_str("Enum1::Bad"),
+            },
+            CppEnumValue {
+                value: -1,
+                path: CppPath::from_good_str("Enum2::Small"),
+            },
+            CppEnumValue {
+                value: 0x1_0000_0000,
+                path: CppPath::from_good_str("Enum2::Large"),
+            },
+        ]
+    );
+}
+
 #[test]
 fn template_instantiation() {
     let data = run_parser(
@@ -1003,7 +1124,81 @@ fn non_type_template_parameter() {
         };
         ",
     );
-    assert!(data.types.is_empty());
+    assert_eq!(data.types.len(), 1);
+    assert_eq!(
+        data.types[0].path,
+        CppPath::from_item(CppPathItem {
+            name: "QAtomicOpsSupport".to_string(),
+            template_arguments: Some(vec![CppType::TemplateParameter(CppTemplateParameter {
+                nested_level: 0,
+                index: 0,
+                name: "Value1".to_string(),
+            })]),
+        }),
+    );
+}
+
+#[test]
+fn non_type_template_argument_values() {
+    let data = run_parser(
+        "
+        enum Mode { Fast, Precise };
+        template<typename T, int N, bool B, Mode M>
+        class Array {
+        public:
+            T at(int index) const;
+        };
+        Array<int, 4, true, Precise> make_array();
+        ",
+    );
+    assert_eq!(data.types.len(), 2);
+    assert_eq!(
+        data.types[1].path,
+        CppPath::from_item(CppPathItem {
+            name: "Array".to_string(),
+            template_arguments: Some(vec![
+                CppType::TemplateParameter(CppTemplateParameter {
+                    nested_level: 0,
+                    index: 0,
+                    name: "T".to_string(),
+                }),
+                CppType::TemplateParameter(CppTemplateParameter {
+                    nested_level: 0,
+                    index: 1,
+                    name: "N".to_string(),
+                }),
+                CppType::TemplateParameter(CppTemplateParameter {
+                    nested_level: 0,
+                    index: 2,
+                    name: "B".to_string(),
+                }),
+                CppType::TemplateParameter(CppTemplateParameter {
+                    nested_level: 0,
+                    index: 3,
+                    name: "M".to_string(),
+                }),
+            ]),
+        }),
+    );
+    let function = data
+        .methods
+        .iter()
+        .find(|m| m.path == CppPath::from_good_str("make_array"))
+        .unwrap();
+    assert_eq!(
+        function.return_type,
+        CppType::Class(CppPath::from_item(CppPathItem {
+            name: "Array".to_string(),
+            template_arguments: Some(vec![
+                CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
+                CppType::TemplateArgumentValue(CppTemplateArgumentValue::Integer(4)),
+                CppType::TemplateArgumentValue(CppTemplateArgumentValue::Bool(true)),
+                CppType::TemplateArgumentValue(CppTemplateArgumentValue::EnumValue {
+                    path: CppPath::from_good_str("Mode::Precise"),
+                }),
+            ]),
+        })),
+    );
 }
 
 #[test]
@@ -1220,3 +1415,142 @@ fn elaborated_enum_type() {
         }
     );
 }
+
+#[test]
+fn global_variables() {
+    let data = run_parser(
+        "
+        namespace ns {
+            extern const int answer;
+            extern double ratio;
+        }
+        ",
+    );
+    assert_eq!(
+        data.variables,
+        vec![
+            CppVariable {
+                path: CppPath::from_good_str("ns::answer"),
+                variable_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
+                is_const: true,
+                visibility: CppVisibility::Public,
+            },
+            CppVariable {
+                path: CppPath::from_good_str("ns::ratio"),
+                variable_type: CppType::BuiltInNumeric(CppBuiltInNumericType::Double),
+                is_const: false,
+                visibility: CppVisibility::Public,
+            },
+        ]
+    );
+}
+
+#[test]
+fn type_aliases() {
+    let data = run_parser(
+        "
+        typedef double real;
+        namespace ns {
+            class Container {
+            public:
+                using size_type = int;
+                size_type count(const real& x) const;
+            };
+        }
+        ",
+    );
+    assert_eq!(
+        data.type_aliases,
+        vec![
+            CppTypeAlias {
+                path: CppPath::from_good_str("real"),
+                target: CppType::BuiltInNumeric(CppBuiltInNumericType::Double),
+            },
+            CppTypeAlias {
+                path: CppPath::from_good_str("ns::Container::size_type"),
+                target: CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
+            },
+        ]
+    );
+
+    assert_eq!(data.methods.len(), 1);
+    let method = &data.methods[0];
+    assert_eq!(method.path, CppPath::from_good_str("ns::Container::count"));
+    assert_eq!(
+        method.return_type,
+        CppType::BuiltInNumeric(CppBuiltInNumericType::Int)
+    );
+    assert_eq!(
+        method.return_type_alias,
+        Some(CppPath::from_good_str("ns::Container::size_type"))
+    );
+    assert_eq!(
+        method.arguments[0].argument_type,
+        CppType::new_reference(true, CppType::BuiltInNumeric(CppBuiltInNumericType::Double))
+    );
+    assert_eq!(
+        method.arguments[0].type_alias,
+        Some(CppPath::from_good_str("real"))
+    );
+}
+
+#[test]
+fn constants() {
+    let data = run_parser(
+        "
+        #define MYLIB_VERSION 0x050F02
+        #define MYLIB_NAME \"my\" \"lib\"
+        #define MYLIB_SCALE (-1.5)
+        #define MYLIB_MAX(a, b) ((a) > (b) ? (a) : (b))
+        #define MYLIB_FLAGS (MYLIB_VERSION | 1)
+        namespace ns {
+            constexpr int answer = 42;
+            class C {
+            public:
+                static const bool enabled = true;
+            };
+        }
+        ",
+    );
+    let find = |path: &str| {
+        data.constants
+            .iter()
+            .find(|c| c.path == CppPath::from_good_str(path))
+            .unwrap_or_else(|| panic!("constant not found: {}", path))
+    };
+    assert_eq!(data.constants.len(), 5);
+    assert!(data.variables.is_empty());
+
+    let version = find("MYLIB_VERSION");
+    assert!(version.is_macro);
+    assert_eq!(version.value, CppConstantValue::Integer(0x05_0F02));
+    assert_eq!(
+        version.constant_type,
+        CppType::BuiltInNumeric(CppBuiltInNumericType::Int)
+    );
+    assert_eq!(version.origin_location.line, 2);
+
+    let name = find("MYLIB_NAME");
+    assert_eq!(name.value, CppConstantValue::String("mylib".to_string()));
+
+    let scale = find("MYLIB_SCALE");
+    assert_eq!(
+        scale.value,
+        CppConstantValue::FloatingPoint("-1.5".to_string())
+    );
+    assert_eq!(
+        scale.constant_type,
+        CppType::BuiltInNumeric(CppBuiltInNumericType::Double)
+    );
+
+    let answer = find("ns::answer");
+    assert!(!answer.is_macro);
+    assert_eq!(answer.value, CppConstantValue::Integer(42));
+
+    let enabled = find("ns::C::enabled");
+    assert_eq!(enabled.value, CppConstantValue::Bool(true));
+    assert_eq!(
+        enabled.constant_type,
+        CppType::BuiltInNumeric(CppBuiltInNumericType::Bool)
+    );
+}
@@ -1,14 +1,15 @@
 use crate::cpp_data::{CppPath, CppPathItem};
-use crate::cpp_ffi_data::CppToFfiTypeConversion;
+use crate::cpp_ffi_data::{CppFlagContainer, CppToFfiTypeConversion};
 use crate::cpp_ffi_generator::ffi_type;
 use crate::cpp_type::{
-    CppBuiltInNumericType, CppFunctionPointerType, CppSpecificNumericType,
-    CppSpecificNumericTypeKind, CppTemplateParameter, CppType, CppTypeRole,
+    smart_pointer_type, CppBuiltInNumericType, CppFunctionPointerType, CppPointerLikeTypeKind,
+    CppSmartPointerKind, CppSpecificNumericType, CppSpecificNumericTypeKind,
+    CppTemplateArgumentValue, CppTemplateParameter, CppType, CppTypeRole,
 };
 
 fn assert_type_to_ffi_unchanged(t: &CppType) {
     for role in &[CppTypeRole::NotReturnType, CppTypeRole::ReturnType] {
-        let ffi1 = ffi_type(t, *role).unwrap();
+        let ffi1 = ffi_type(t, *role, &[CppFlagContainer::qflags()]).unwrap();
         assert_eq!(ffi1.original_type(), t);
         assert_eq!(ffi1.ffi_type(), t);
         assert_eq!(ffi1.conversion(), &CppToFfiTypeConversion::NoChange);
@@ -124,7 +125,12 @@ fn class_value() {
     assert_eq!(type1.to_cpp_code(None).unwrap(), "QPoint");
     assert!(type1.to_cpp_code(Some(&String::new())).is_err());
 
-    let ffi_return_type = ffi_type(&type1, CppTypeRole::ReturnType).unwrap();
+    let ffi_return_type = ffi_type(
+        &type1,
+        CppTypeRole::ReturnType,
+        &[CppFlagContainer::qflags()],
+    )
+    .unwrap();
     assert_eq!(ffi_return_type.original_type(), &type1);
     assert_eq!(
         ffi_return_type.ffi_type(),
@@ -141,7 +147,12 @@ fn class_value() {
         }
     );
 
-    let ffi_arg = ffi_type(&type1, CppTypeRole::NotReturnType).unwrap();
+    let ffi_arg = ffi_type(
+        &type1,
+        CppTypeRole::NotReturnType,
+        &[CppFlagContainer::qflags()],
+    )
+    .unwrap();
     assert_eq!(ffi_arg.original_type(), &type1);
     assert_eq!(
         ffi_arg.ffi_type(),
@@ -167,7 +178,7 @@ fn class_const_ref() {
     assert!(type1.to_cpp_code(Some(&String::new())).is_err());
 
     for role in &[CppTypeRole::NotReturnType, CppTypeRole::ReturnType] {
-        let ffi1 = ffi_type(&type1, *role).unwrap();
+        let ffi1 = ffi_type(&type1, *role, &[CppFlagContainer::qflags()]).unwrap();
         assert_eq!(ffi1.original_type(), &type1);
         assert_eq!(
             ffi1.ffi_type(),
@@ -191,7 +202,7 @@ fn class_mut_ref() {
     assert!(type1.to_cpp_code(Some(&String::new())).is_err());
 
     for role in &[CppTypeRole::NotReturnType, CppTypeRole::ReturnType] {
-        let ffi1 = ffi_type(&type1, *role).unwrap();
+        let ffi1 = ffi_type(&type1, *role, &[CppFlagContainer::qflags()]).unwrap();
         assert_eq!(ffi1.original_type(), &type1);
         assert_eq!(
             ffi1.ffi_type(),
@@ -205,6 +216,40 @@ fn class_mut_ref() {
     }
 }
 
+#[test]
+fn class_rvalue_ref() {
+    let type1 = CppType::PointerLike {
+        kind: CppPointerLikeTypeKind::RValueReference,
+        is_const: false,
+        target: Box::new(CppType::Class(CppPath::from_good_str("QRectF"))),
+    };
+    assert_eq!(type1.is_class(), false);
+    assert_eq!(type1.to_cpp_code(None).unwrap(), "QRectF &&");
+
+    let ffi1 = ffi_type(
+        &type1,
+        CppTypeRole::NotReturnType,
+        &[CppFlagContainer::qflags()],
+    )
+    .unwrap();
+    assert_eq!(ffi1.original_type(), &type1);
+    assert_eq!(
+        ffi1.ffi_type(),
+        &CppType::new_pointer(false, CppType::Class(CppPath::from_good_str("QRectF")))
+    );
+    assert_eq!(
+        ffi1.conversion(),
+        &CppToFfiTypeConversion::RValueReferenceToPointer
+    );
+
+    assert!(ffi_type(
+        &type1,
+        CppTypeRole::ReturnType,
+        &[CppFlagContainer::qflags()]
+    )
+    .is_err());
+}
+
 #[test]
 fn class_mut_ptr() {
     let type1 = CppType::new_pointer(false, CppType::Class(CppPath::from_good_str("QObject")));
@@ -229,7 +274,12 @@ fn class_with_template_args() {
     assert_eq!(type1.to_cpp_code(None).unwrap(), "QVector< QString >");
     assert!(type1.to_cpp_code(Some(&String::new())).is_err());
 
-    let ffi_return_type = ffi_type(&type1, CppTypeRole::ReturnType).unwrap();
+    let ffi_return_type = ffi_type(
+        &type1,
+        CppTypeRole::ReturnType,
+        &[CppFlagContainer::qflags()],
+    )
+    .unwrap();
     assert_eq!(ffi_return_type.original_type(), &type1);
     assert_eq!(
         ffi_return_type.ffi_type(),
@@ -252,7 +302,12 @@ fn class_with_template_args() {
         }
     );
 
-    let ffi_arg = ffi_type(&type1, CppTypeRole::NotReturnType).unwrap();
+    let ffi_arg = ffi_type(
+        &type1,
+        CppTypeRole::NotReturnType,
+        &[CppFlagContainer::qflags()],
+    )
+    .unwrap();
     assert_eq!(ffi_arg.original_type(), &type1);
     assert_eq!(
         ffi_arg.ffi_type(),
@@ -313,17 +368,64 @@ fn qflags() {
     assert!(type1.to_cpp_code(Some(&String::new())).is_err());
 
     for role in &[CppTypeRole::NotReturnType, CppTypeRole::ReturnType] {
-        let ffi_type = ffi_type(&type1, *role).unwrap();
+        let ffi_type = ffi_type(&type1, *role, &[CppFlagContainer::qflags()]).unwrap();
         assert_eq!(ffi_type.original_type(), &type1);
         assert_eq!(
             ffi_type.ffi_type(),
             &CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
         );
         assert_eq!(ffi_type.ffi_type().to_cpp_code(None).unwrap(), "int");
-        assert_eq!(ffi_type.conversion(), &CppToFfiTypeConversion::QFlagsToInt);
+        assert_eq!(
+            ffi_type.conversion(),
+            &CppToFfiTypeConversion::FlagsToInt {
+                container: CppFlagContainer::qflags()
+            }
+        );
     }
 }
 
+#[test]
+fn custom_flag_container() {
+    let container = CppFlagContainer {
+        template_path: CppPath::from_good_str("ns::Flags"),
+        to_int_function: Some("ns::flags_to_int".into()),
+        from_int_function: Some("ns::flags_from_int".into()),
+    };
+    let mut path = CppPath::from_good_str("ns::Flags");
+    path.last_mut().template_arguments = Some(vec![CppType::Enum {
+        path: CppPath::from_good_str("ns::Option"),
+    }]);
+    let type1 = CppType::Class(path.clone());
+    assert!(container.matches(&path));
+    assert!(!CppFlagContainer::qflags().matches(&path));
+
+    let const_ref = CppType::new_reference(true, type1.clone());
+    for t in &[type1.clone(), const_ref] {
+        let ffi1 = ffi_type(t, CppTypeRole::NotReturnType, &[container.clone()]).unwrap();
+        assert_eq!(
+            ffi1.ffi_type(),
+            &CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
+        );
+        assert_eq!(
+            ffi1.conversion(),
+            &CppToFfiTypeConversion::FlagsToInt {
+                container: container.clone()
+            }
+        );
+    }
+
+    let ffi2 = ffi_type(
+        &type1,
+        CppTypeRole::NotReturnType,
+        &[CppFlagContainer::qflags()],
+    )
+    .unwrap();
+    assert_eq!(
+        ffi2.conversion(),
+        &CppToFfiTypeConversion::ValueToPointer { is_ffi_const: true }
+    );
+}
+
 #[test]
 fn template_parameter() {
     let type1 = CppType::new_pointer(
@@ -339,8 +441,18 @@ fn template_parameter() {
     assert_eq!(type1.is_template_parameter(), false);
     assert!(type1.to_cpp_code(None).is_err());
     assert!(type1.to_cpp_code(Some(&String::new())).is_err());
-    assert!(ffi_type(&type1, CppTypeRole::NotReturnType).is_err());
-    assert!(ffi_type(&type1, CppTypeRole::ReturnType).is_err());
+    assert!(ffi_type(
+        &type1,
+        CppTypeRole::NotReturnType,
+        &[CppFlagContainer::qflags()]
+    )
+    .is_err());
+    assert!(ffi_type(
+        &type1,
+        CppTypeRole::ReturnType,
+        &[CppFlagContainer::qflags()]
+    )
+    .is_err());
 }
 
 #[test]
@@ -380,3 +492,88 @@ fn instantiate1() {
         )
     );
 }
+
+#[test]
+fn instantiate_non_type_argument() {
+    let type1 = CppType::Class(CppPath::from_item(CppPathItem {
+        name: "QVarLengthArray".into(),
+        template_arguments: Some(vec![
+            CppType::TemplateParameter(CppTemplateParameter {
+                nested_level: 0,
+                index: 0,
+                name: "T".into(),
+            }),
+            CppType::TemplateParameter(CppTemplateParameter {
+                nested_level: 0,
+                index: 1,
+                name: "Prealloc".into(),
+            }),
+        ]),
+    }));
+    assert!(type1.is_or_contains_template_parameter());
+    let r = type1
+        .instantiate(
+            0,
+            &[
+                CppType::BuiltInNumeric(CppBuiltInNumericType::Int),
+                CppType::TemplateArgumentValue(CppTemplateArgumentValue::Integer(256)),
+            ],
+        )
+        .unwrap();
+    assert!(!r.is_or_contains_template_parameter());
+    assert_eq!(r.to_cpp_code(None).unwrap(), "QVarLengthArray< int, 256 >");
+    assert_eq!(r.ascii_caption(), "QVarLengthArray_int_256");
+
+    let ffi1 = ffi_type(&r, CppTypeRole::ReturnType, &[CppFlagContainer::qflags()]).unwrap();
+    assert_eq!(ffi1.ffi_type(), &CppType::new_pointer(false, r.clone()));
+
+    let value = CppType::TemplateArgumentValue(CppTemplateArgumentValue::Integer(-1));
+    assert_eq!(value.to_cpp_code(None).unwrap(), "-1");
+    assert_eq!(value.ascii_caption(), "minus_1");
+    assert!(ffi_type(
+        &value,
+        CppTypeRole::NotReturnType,
+        &[CppFlagContainer::qflags()]
+    )
+    .is_err());
+}
+
+#[test]
+fn smart_pointers() {
+    let class1 = CppType::Class(CppPath::from_good_str("Class1"));
+    let std_path = |name: &str, arguments: Vec<CppType>| {
+        CppPath::from_good_str("std").join(CppPathItem {
+            name: name.into(),
+            template_arguments: Some(arguments),
+        })
+    };
+
+    let shared = std_path("shared_ptr", vec![class1.clone()]);
+    assert_eq!(
+        smart_pointer_type(&shared),
+        Some((CppSmartPointerKind::Shared, &class1))
+    );
+    assert!(!CppType::Class(shared).is_unique_ptr());
+
+    let unique = std_path("unique_ptr", vec![class1.clone()]);
+    assert_eq!(
+        smart_pointer_type(&unique),
+        Some((CppSmartPointerKind::Unique, &class1))
+    );
+    assert!(CppType::Class(unique).is_unique_ptr());
+
+    let default_delete = CppType::Class(std_path("default_delete", vec![class1.clone()]));
+    let unique2 = std_path("unique_ptr", vec![class1.clone(), default_delete]);
+    assert_eq!(
+        smart_pointer_type(&unique2),
+        Some((CppSmartPointerKind::Unique, &class1))
+    );
+
+    let custom_deleter = CppType::Class(CppPath::from_good_str("Deleter"));
+    let unique3 = std_path("unique_ptr", vec![class1.clone(), custom_deleter]);
+    assert_eq!(smart_pointer_type(&unique3), None);
+
+    let int = CppType::BuiltInNumeric(CppBuiltInNumericType::Int);
+    assert_eq!(smart_pointer_type(&std_path("shared_ptr", vec![int])), None);
+    assert_eq!(smart_pointer_type(&CppPath::from_good_str("Class1")), None);
+}
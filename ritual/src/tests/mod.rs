@@ -1,5 +1,6 @@
 #![allow(clippy::cognitive_complexity)]
 
+mod config;
 mod cpp_ffi_data;
 mod cpp_method;
 mod cpp_operator;
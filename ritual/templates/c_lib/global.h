@@ -14,9 +14,27 @@
 // placement new statements require this
 #include <new>
 
-// for exit()
+// for std::move() in rvalue reference arguments
+#include <utility>
+
+// for exit() and malloc()
 #include <cstdlib>
 
+// for catching exceptions in FFI functions
+#include <exception>
+#include <cstring>
+
+// for forwarding variadic arguments
+#include <cstdarg>
+
+// for converting output stream operators to strings
+#include <sstream>
+#include <string>
+
+// for passing callbacks to std::function arguments
+#include <functional>
+#include <memory>
+
 #ifdef _WIN32
     #define RITUAL_EXPORT __declspec(dllexport)
 #else
@@ -38,6 +56,69 @@ namespace ritual {
         x->~T();
     }
 
+    // Stores a copy of `message` in `*exception`. The copy is allocated
+    // with `malloc` and is released by `cpp_core::CppException`.
+    inline void set_exception(char** exception, const char* message) {
+        size_t size = strlen(message) + 1;
+        char* buffer = static_cast<char*>(malloc(size));
+        if (!buffer) {
+            std::cout << "failed to allocate memory for exception message\n";
+            exit(1);
+        }
+        memcpy(buffer, message, size);
+        *exception = buffer;
+    }
+
+    // Calls `write` with a string stream and returns a copy of the written text.
+    // The copy is allocated with `malloc` and is released by
+    // `cpp_core::take_malloc_string`.
+    template<typename F>
+    char* write_to_string(F write) {
+        std::ostringstream stream;
+        write(stream);
+        std::string text = stream.str();
+        char* buffer = static_cast<char*>(malloc(text.size() + 1));
+        if (!buffer) {
+            std::cout << "failed to allocate memory for string\n";
+            exit(1);
+        }
+        memcpy(buffer, text.c_str(), text.size() + 1);
+        return buffer;
+    }
+
+    // Called by a subclass wrapper when a pure virtual function
+    // is invoked but no override was set.
+    [[noreturn]] inline void missing_override(const char* function) {
+        std::cout << "pure virtual function called without override: " << function << "\n";
+        abort();
+    }
+
+    // Creates a `std::function` that calls `callback` with `data`
+    // as the first argument. `data` is shared by all copies of the returned
+    // object and is passed to `deleter` when the last copy is destroyed.
+    template<typename R, typename... Args>
+    std::function<R(Args...)> make_function(
+        R (*callback)(void*, Args...),
+        void* data,
+        void (*deleter)(void*)
+    ) {
+        std::shared_ptr<void> handle(data, deleter);
+        return [callback, handle](Args... args) -> R {
+            return callback(handle.get(), args...);
+        };
+    }
+
+    // Creates a `std::shared_ptr` that shares the ownership of `*owner`
+    // but stores `ptr`. `owner` is a heap-allocated `std::shared_ptr<void>`
+    // that is deleted by this function.
+    template<typename T>
+    std::shared_ptr<T>* alias_shared_ptr(void* owner, T* ptr) {
+        std::shared_ptr<void>* owner_ptr = static_cast<std::shared_ptr<void>*>(owner);
+        std::shared_ptr<T>* result = new std::shared_ptr<T>(*owner_ptr, ptr);
+        delete owner_ptr;
+        return result;
+    }
+
     template<class T>
     class Callback {
     public:
@@ -0,0 +1,10 @@
+// Class generated by ritual.
+// See the template at "ritual/templates/c_lib/subclass_wrapper.h".
+class {class_name} : public {base_class} {{
+public:
+{public_functions}
+protected:
+{overrides}
+private:
+{callbacks}
+}};
@@ -0,0 +1,15 @@
+{condition_attribute}
+pub trait {trait_name} {{
+{methods}
+}}
+
+{condition_attribute}
+impl {subclass_path} {{
+    /// Attaches `overrides` to this object. Calls of the virtual functions
+    /// are dispatched to the corresponding methods of `overrides`.
+    pub unsafe fn set_overrides<T: {trait_path} + 'static>(&self, overrides: T) {{
+        let overrides = ::std::rc::Rc::new(overrides);
+        let this = ::cpp_core::Ptr::from_raw(self);
+{setters}
+    }}
+}}